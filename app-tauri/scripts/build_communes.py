@@ -0,0 +1,45 @@
+#!/usr/bin/env python3
+"""Génère src-tauri/data/communes_fr.csv, le gazetteer embarqué dans l'application.
+
+Source : base officielle des codes postaux (La Poste) enrichie des codes INSEE
+et des coordonnées, publiée sur data.gouv.fr sous le nom
+« communes-departement-region.csv ».
+
+Usage : python3 scripts/build_communes.py communes-departement-region.csv
+"""
+
+import csv
+import sys
+from pathlib import Path
+
+OUTPUT = Path(__file__).resolve().parent.parent / "src-tauri" / "data" / "communes_fr.csv"
+
+
+def main(source: str) -> None:
+    rows = {}
+    with open(source, newline="", encoding="utf-8") as handle:
+        for row in csv.DictReader(handle):
+            latitude, longitude = row.get("latitude"), row.get("longitude")
+            if not latitude or not longitude:
+                continue
+            postcode = row["code_postal"].zfill(5)
+            # Le nom complet garde accents et article (« Les Ulis ») ; le nom
+            # postal, en majuscules, sert de repli
+            name = row.get("nom_commune_complet") or row["nom_commune_postal"]
+            department = row.get("code_departement") or postcode[:2]
+            key = (postcode, name)
+            if key not in rows:
+                rows[key] = (postcode, name, department, f"{float(latitude):.4f}", f"{float(longitude):.4f}")
+
+    with open(OUTPUT, "w", newline="", encoding="utf-8") as handle:
+        writer = csv.writer(handle, delimiter=";", lineterminator="\n")
+        writer.writerow(["code_postal", "nom", "departement", "latitude", "longitude"])
+        writer.writerows(sorted(rows.values()))
+
+    print(f"{len(rows)} communes écrites dans {OUTPUT}")
+
+
+if __name__ == "__main__":
+    if len(sys.argv) != 2:
+        sys.exit(__doc__)
+    main(sys.argv[1])
@@ -1,58 +1,111 @@
+-- Schéma canonique de l'application.
+-- Ce fichier est appliqué par `database::migrations::run_migrations` (migration 1) ;
+-- toute évolution ultérieure passe par une nouvelle migration.
+
+CREATE TABLE IF NOT EXISTS users (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    email TEXT UNIQUE NOT NULL,
+    password_hash TEXT NOT NULL DEFAULT '',
+    name TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Utilisateur local utilisé par l'application de bureau
+INSERT OR IGNORE INTO users (id, email, name) VALUES (1, 'local@localhost', 'Utilisateur local');
+
 CREATE TABLE IF NOT EXISTS user_profiles (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL UNIQUE,
     name TEXT NOT NULL,
-    email TEXT NOT NULL UNIQUE,
+    email TEXT,
     phone TEXT,
-    location TEXT,
-    skills TEXT,
-    experience TEXT,
-    education TEXT,
+    primary_home TEXT NOT NULL,
+    secondary_home TEXT,
+    cv_path TEXT,
+    cv_last_updated TEXT,
+    cover_letter_template TEXT,
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id)
 );
 
 CREATE TABLE IF NOT EXISTS jobs (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER,
     title TEXT NOT NULL,
     company TEXT NOT NULL,
-    location TEXT,
-    description TEXT,
+    location TEXT NOT NULL DEFAULT '',
+    description TEXT NOT NULL DEFAULT '',
     requirements TEXT,
-    salary_range TEXT,
-    url TEXT,
-    status TEXT DEFAULT 'pending',
+    job_type TEXT NOT NULL DEFAULT '',
+    experience_level TEXT NOT NULL DEFAULT '',
+    salary_min INTEGER,
+    salary_max INTEGER,
+    salary_currency TEXT,
+    salary_period TEXT,
+    url TEXT NOT NULL DEFAULT '',
+    source TEXT NOT NULL DEFAULT '',
+    posted_at TEXT NOT NULL DEFAULT '',
+    skills TEXT NOT NULL DEFAULT '[]',
+    remote BOOLEAN NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'new',
+    matching_score REAL NOT NULL DEFAULT 0,
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id)
 );
 
-CREATE TABLE IF NOT EXISTS llm_providers (
+CREATE TABLE IF NOT EXISTS commute_times (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
-    name TEXT NOT NULL UNIQUE,
-    api_key TEXT,
-    model TEXT,
+    job_id INTEGER NOT NULL,
+    home_type TEXT NOT NULL,
+    duration INTEGER NOT NULL,
+    distance REAL NOT NULL,
+    mode TEXT,
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (job_id) REFERENCES jobs(id)
 );
 
-CREATE TABLE IF NOT EXISTS job_stats (
+CREATE TABLE IF NOT EXISTS documents (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
-    total_applications INTEGER DEFAULT 0,
-    pending_applications INTEGER DEFAULT 0,
-    accepted_applications INTEGER DEFAULT 0,
-    rejected_applications INTEGER DEFAULT 0,
+    user_id INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    file_path TEXT,
+    content TEXT,
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id)
 );
 
-CREATE TABLE IF NOT EXISTS user_profile (
+CREATE TABLE IF NOT EXISTS document_templates (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER,
     name TEXT NOT NULL,
-    email TEXT,
-    phone TEXT,
-    primary_home TEXT NOT NULL,
-    secondary_home TEXT,
-    cv_path TEXT,
-    cv_last_updated TEXT
+    template_type TEXT NOT NULL,
+    content TEXT NOT NULL,
+    variables TEXT,
+    is_default BOOLEAN DEFAULT 0,
+    language TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE TABLE IF NOT EXISTS generated_documents (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    job_id INTEGER NOT NULL,
+    template_id INTEGER NOT NULL,
+    document_type TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    version INTEGER,
+    rating INTEGER,
+    comments TEXT,
+    FOREIGN KEY (job_id) REFERENCES jobs(id),
+    FOREIGN KEY (template_id) REFERENCES document_templates(id)
 );
 
 CREATE TABLE IF NOT EXISTS kanban_columns (
@@ -60,7 +113,7 @@ CREATE TABLE IF NOT EXISTS kanban_columns (
     name TEXT NOT NULL,
     position INTEGER NOT NULL,
     color TEXT,
-    "limit" INTEGER
+    card_limit INTEGER
 );
 
 CREATE TABLE IF NOT EXISTS kanban_cards (
@@ -71,6 +124,9 @@ CREATE TABLE IF NOT EXISTS kanban_cards (
     notes TEXT,
     applied_at TEXT,
     follow_up_date TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (job_id) REFERENCES jobs(id),
     FOREIGN KEY (column_id) REFERENCES kanban_columns(id)
 );
 
@@ -108,6 +164,15 @@ CREATE TABLE IF NOT EXISTS keywords (
     FOREIGN KEY (category_id) REFERENCES search_categories(id)
 );
 
+CREATE TABLE IF NOT EXISTS llm_providers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    api_key TEXT,
+    model TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
 CREATE TABLE IF NOT EXISTS llm_models (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     provider_id INTEGER NOT NULL,
@@ -117,27 +182,3 @@ CREATE TABLE IF NOT EXISTS llm_models (
     is_fine_tuned BOOLEAN DEFAULT 0,
     FOREIGN KEY (provider_id) REFERENCES llm_providers(id)
 );
-
-CREATE TABLE IF NOT EXISTS document_templates (
-    id INTEGER PRIMARY KEY AUTOINCREMENT,
-    name TEXT NOT NULL,
-    template_type TEXT NOT NULL,
-    content TEXT NOT NULL,
-    variables TEXT,
-    is_default BOOLEAN DEFAULT 0,
-    language TEXT
-);
-
-CREATE TABLE IF NOT EXISTS generated_documents (
-    id INTEGER PRIMARY KEY AUTOINCREMENT,
-    job_id INTEGER NOT NULL,
-    template_id INTEGER NOT NULL,
-    document_type TEXT NOT NULL,
-    content TEXT NOT NULL,
-    created_at TEXT NOT NULL,
-    version INTEGER,
-    rating INTEGER,
-    comments TEXT,
-    FOREIGN KEY (job_id) REFERENCES jobs(id),
-    FOREIGN KEY (template_id) REFERENCES document_templates(id)
-); 
\ No newline at end of file
@@ -0,0 +1,251 @@
+// Lecture et écriture de fichiers iCalendar (RFC 5545), limitées aux
+// événements : de quoi échanger entretiens et étapes avec un agenda.
+// Les dates sont échangées avec le reste de l'application au format
+// `YYYY-MM-DD HH:MM:SS` (UTC)
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+
+const PRODID: &str = "-//JobApp Tauri//Candidatures//FR";
+// Suffixe des UID générés : un événement réexporté garde le même UID et
+// remplace l'ancien dans l'agenda au lieu de s'y ajouter
+pub const UID_DOMAIN: &str = "jobapp-tauri";
+// Durée par défaut d'un événement exporté sans fin connue
+pub const DEFAULT_EVENT_MINUTES: i64 = 60;
+// Longueur maximale d'une ligne, en octets, avant repli
+const MAX_LINE_OCTETS: usize = 75;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const ICS_UTC_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub start: String,
+    pub end: Option<String>,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+// Événement lu dans un fichier importé ; `start` est absent si la date est
+// illisible
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportedEvent {
+    pub uid: Option<String>,
+    pub start: Option<String>,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub organizer: Option<String>,
+    pub cancelled: bool,
+}
+
+pub fn event_uid(kind: &str, id: i64) -> String {
+    format!("{}-{}@{}", kind, id, UID_DOMAIN)
+}
+
+// Inverse de `event_uid` pour les UID produits par cette application
+pub fn parse_event_uid(uid: &str) -> Option<(&str, i64)> {
+    let (local, domain) = uid.split_once('@')?;
+    if domain != UID_DOMAIN {
+        return None;
+    }
+    let (kind, id) = local.rsplit_once('-')?;
+    Some((kind, id.parse().ok()?))
+}
+
+pub fn write_calendar(events: &[CalendarEvent], stamp: &str) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+    let stamp = to_ics_time(stamp).unwrap_or_default();
+
+    for event in events {
+        let Some(start) = parse_timestamp(&event.start) else {
+            continue;
+        };
+        let end = event
+            .end
+            .as_deref()
+            .and_then(parse_timestamp)
+            .filter(|end| *end > start)
+            .unwrap_or(start + Duration::minutes(DEFAULT_EVENT_MINUTES));
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", escape_text(&event.uid)));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART:{}", start.format(ICS_UTC_FORMAT)));
+        lines.push(format!("DTEND:{}", end.format(ICS_UTC_FORMAT)));
+        if let Some(modified) = event.last_modified.as_deref().and_then(to_ics_time) {
+            lines.push(format!("LAST-MODIFIED:{}", modified));
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let Some(location) = &event.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect::<Vec<_>>().join("")
+}
+
+// Les heures en temps local (TZID ou heure « flottante ») sont interprétées
+// dans le fuseau de la machine, faute de base de fuseaux horaires
+pub fn parse_calendar(text: &str) -> Vec<ImportedEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<ImportedEvent> = None;
+    // Composants imbriqués dans un événement (VALARM) : ignorés
+    let mut nested = 0usize;
+
+    for line in unfold(text) {
+        let Some((name, params, value)) = split_property(&line) else {
+            continue;
+        };
+        match (name.as_str(), value) {
+            ("BEGIN", "VEVENT") => current = Some(ImportedEvent::default()),
+            ("END", "VEVENT") => events.extend(current.take()),
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", _) if current.is_some() => nested = nested.saturating_sub(1),
+            _ => {}
+        }
+        let Some(event) = current.as_mut().filter(|_| nested == 0) else {
+            continue;
+        };
+        match name.as_str() {
+            "UID" => event.uid = Some(unescape_text(value)),
+            "SUMMARY" => event.summary = unescape_text(value),
+            "DESCRIPTION" => event.description = Some(unescape_text(value)),
+            "LOCATION" => event.location = Some(unescape_text(value)),
+            "ORGANIZER" => {
+                let name = params.iter().find(|(key, _)| key == "CN").map(|(_, cn)| cn.clone());
+                let address = value.trim_start_matches("mailto:").trim_start_matches("MAILTO:");
+                event.organizer = Some(match name {
+                    Some(name) => format!("{} {}", name, address),
+                    None => address.to_string(),
+                });
+            }
+            "DTSTART" => event.start = parse_ics_time(value),
+            "STATUS" => event.cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+            _ => {}
+        }
+    }
+    events
+}
+
+fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, TIMESTAMP_FORMAT)
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S"))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M"))
+        .ok()
+        .or_else(|| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))
+}
+
+fn to_ics_time(value: &str) -> Option<String> {
+    parse_timestamp(value).map(|time| time.format(ICS_UTC_FORMAT).to_string())
+}
+
+// `20240502T140000Z` (UTC), `20240502T140000` (local) ou `20240502` (journée)
+fn parse_ics_time(value: &str) -> Option<String> {
+    let value = value.trim();
+    let time = if let Some(utc) = value.strip_suffix('Z') {
+        NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?
+    } else {
+        let local = match NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+            Ok(time) => time,
+            Err(_) => NaiveDate::parse_from_str(value, "%Y%m%d").ok()?.and_hms_opt(0, 0, 0)?,
+        };
+        Local.from_local_datetime(&local).earliest()?.naive_utc()
+    };
+    Some(time.format(TIMESTAMP_FORMAT).to_string())
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => text.push('\n'),
+            Some(other) => text.push(other),
+            None => text.push('\\'),
+        }
+    }
+    text
+}
+
+// Replie les lignes longues sans couper un caractère UTF-8
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // L'espace de continuation compte dans la ligne suivante
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match (raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ if raw.is_empty() => {}
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+type PropertyParams = Vec<(String, String)>;
+
+// `NOM;PARAM=valeur;PARAM="a:b":valeur` ; les deux-points entre guillemets
+// appartiennent aux paramètres
+fn split_property(line: &str) -> Option<(String, PropertyParams, &str)> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(index, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(index),
+        _ => None,
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((key.trim().to_ascii_uppercase(), value.trim_matches('"').to_string()))
+        })
+        .collect();
+    Some((name, params, value))
+}
@@ -1,194 +1,698 @@
 use crate::{
-    models::{Application, ApplicationDocument, ApplicationNote, ApplicationStage},
+    error::AppError,
+    models::{
+        Application, ApplicationDocument, ApplicationNote, ApplicationStage, ApplicationStatus,
+        ApplicationStatusChange,
+    },
+    time::now_timestamp,
     AppState,
 };
-use rusqlite::params;
-use serde::{Deserialize, Serialize};
+use rusqlite::{params, params_from_iter, types::Value, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Deserializer, Serialize};
 use tauri::State;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateApplicationRequest {
     pub job_id: i64,
-    pub status: String,
+    pub status: ApplicationStatus,
     pub notes: Option<String>,
 }
 
-#[tauri::command]
-pub async fn create_application(
-    state: State<'_, AppState>,
-    request: CreateApplicationRequest,
-) -> Result<Application, String> {
-    let conn = state.db.lock().await;
-    let conn = conn.as_ref().ok_or("Database connection not initialized")?;
-
-    let mut stmt = conn
-        .prepare(
-            "INSERT INTO applications (user_id, job_id, status, notes) 
-             VALUES (?, ?, ?, ?) 
-             RETURNING id, user_id, job_id, status, applied_at, response_received, notes, created_at, updated_at",
-        )
-        .map_err(|e| e.to_string())?;
+// Filtres de la liste des candidatures ; les dates (`YYYY-MM-DD`, bornes
+// incluses) portent sur la date d'envoi, ou de création pour un brouillon
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApplicationFilter {
+    #[serde(default)]
+    pub statuses: Vec<ApplicationStatus>,
+    #[serde(default)]
+    pub company: Option<String>,
+    #[serde(default)]
+    pub date_from: Option<String>,
+    #[serde(default)]
+    pub date_to: Option<String>,
+    // Une étape planifiée à venir et non terminée
+    #[serde(default)]
+    pub has_upcoming_stage: Option<bool>,
+    // "updated" (défaut), "applied", "company" ou "next_stage"
+    #[serde(default)]
+    pub sort_by: Option<String>,
+    // Sens par défaut propre à chaque tri : dates récentes d'abord,
+    // entreprises et prochaines étapes dans l'ordre croissant
+    #[serde(default)]
+    pub ascending: Option<bool>,
+}
 
-    let application = stmt
-        .query_row(
-            params![1, request.job_id, request.status, request.notes],
-            |row| {
-                Ok(Application {
-                    id: row.get(0)?,
-                    user_id: row.get(1)?,
-                    job_id: row.get(2)?,
-                    status: row.get(3)?,
-                    applied_at: row.get(4)?,
-                    response_received: row.get(5)?,
-                    notes: row.get(6)?,
-                    created_at: row.get(7)?,
-                    updated_at: row.get(8)?,
-                    stages: None,
-                    documents: None,
-                    application_notes: None,
-                })
-            },
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApplicationListItem {
+    #[serde(flatten)]
+    pub application: Application,
+    pub job_title: String,
+    pub company: String,
+    pub next_stage_at: Option<String>,
+}
+
+// Champ facultatif d'une mise à jour partielle : absent (`None`) il reste
+// inchangé, à `null` (`Some(None)`) il est effacé
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+// Champs absents laissés inchangés, champs à null effacés
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateStageRequest {
+    pub stage_type: Option<String>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub scheduled_at: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<Option<String>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateDocumentRequest {
+    pub document_type: Option<String>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub content: Option<Option<String>>,
+}
+
+pub(crate) const STAGE_COLUMNS: &str =
+    "id, application_id, stage_type, scheduled_at, completed_at, notes, outcome, created_at, updated_at";
+const DOCUMENT_COLUMNS: &str = "id, application_id, document_type, file_path, content, created_at, updated_at";
+const NOTE_COLUMNS: &str = "id, application_id, content, created_at, updated_at";
+
+// Restreint une table enfant aux candidatures de l'utilisateur
+pub(crate) const OWNED_CHILD: &str = "id = ? AND application_id IN (SELECT id FROM applications WHERE user_id = ?)";
+
+const APPLICATION_COLUMNS: &str =
+    "id, user_id, job_id, status, applied_at, response_received, notes, created_at, updated_at";
+
+fn status_column(row: &Row, index: usize) -> rusqlite::Result<ApplicationStatus> {
+    let status: String = row.get(index)?;
+    ApplicationStatus::parse(&status).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            index,
+            rusqlite::types::Type::Text,
+            format!("Statut de candidature inconnu : {}", status).into(),
         )
-        .map_err(|e| e.to_string())?;
+    })
+}
 
-    Ok(application)
+fn application_from_row(row: &Row) -> rusqlite::Result<Application> {
+    Ok(Application {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        job_id: row.get(2)?,
+        status: status_column(row, 3)?,
+        applied_at: row.get(4)?,
+        response_received: row.get(5)?,
+        notes: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+        stages: None,
+        documents: None,
+        application_notes: None,
+        status_history: None,
+    })
 }
 
-#[tauri::command]
-pub async fn get_application(
-    state: State<'_, AppState>,
+fn status_change_from_row(row: &Row) -> rusqlite::Result<ApplicationStatusChange> {
+    let from_status: Option<String> = row.get(2)?;
+    Ok(ApplicationStatusChange {
+        id: row.get(0)?,
+        application_id: row.get(1)?,
+        from_status: match from_status {
+            Some(_) => Some(status_column(row, 2)?),
+            None => None,
+        },
+        to_status: status_column(row, 3)?,
+        reason: row.get(4)?,
+        changed_at: row.get(5)?,
+    })
+}
+
+fn record_status_change(
+    conn: &Connection,
     application_id: i64,
-) -> Result<Application, String> {
-    let conn = state.db.lock().await;
-    let conn = conn.as_ref().ok_or("Database connection not initialized")?;
-
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, user_id, job_id, status, applied_at, response_received, notes, created_at, updated_at 
-             FROM applications 
-             WHERE id = ?",
-        )
-        .map_err(|e| e.to_string())?;
+    from: Option<ApplicationStatus>,
+    to: ApplicationStatus,
+    reason: Option<&str>,
+    now: &str,
+) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO application_status_history (application_id, from_status, to_status, reason, changed_at)
+         VALUES (?, ?, ?, ?, ?)",
+        params![application_id, from.map(|from| from.as_str()), to.as_str(), reason, now],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn stage_from_row(row: &Row) -> rusqlite::Result<ApplicationStage> {
+    Ok(ApplicationStage {
+        id: row.get(0)?,
+        application_id: row.get(1)?,
+        stage_type: row.get(2)?,
+        scheduled_at: row.get(3)?,
+        completed_at: row.get(4)?,
+        notes: row.get(5)?,
+        outcome: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+fn document_from_row(row: &Row) -> rusqlite::Result<ApplicationDocument> {
+    Ok(ApplicationDocument {
+        id: row.get(0)?,
+        application_id: row.get(1)?,
+        document_type: row.get(2)?,
+        file_path: row.get(3)?,
+        content: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+fn note_from_row(row: &Row) -> rusqlite::Result<ApplicationNote> {
+    Ok(ApplicationNote {
+        id: row.get(0)?,
+        application_id: row.get(1)?,
+        content: row.get(2)?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+// Une candidature peut être créée à n'importe quel statut (suivi d'une
+// candidature déjà avancée) ; le statut initial ouvre l'historique
+pub fn insert_application(
+    conn: &Connection,
+    user_id: i64,
+    request: &CreateApplicationRequest,
+) -> Result<Application, AppError> {
+    let now = now_timestamp();
+    let applied_at = (request.status != ApplicationStatus::Draft).then_some(now.as_str());
+
+    let tx = conn.unchecked_transaction()?;
+    let application = tx.query_row(
+        &format!(
+            "INSERT INTO applications (user_id, job_id, status, applied_at, response_received, notes)
+             VALUES (?, ?, ?, ?, ?, ?)
+             RETURNING {}",
+            APPLICATION_COLUMNS
+        ),
+        params![
+            user_id,
+            request.job_id,
+            request.status.as_str(),
+            applied_at,
+            request.status.is_response(),
+            request.notes
+        ],
+        application_from_row,
+    )?;
+    record_status_change(&tx, application.id, None, request.status, None, &now)?;
+    tx.commit()?;
+
+    Ok(application)
+}
+
+// Une candidature d'un autre profil est introuvable, qu'elle existe ou non
+pub(crate) fn ensure_owned(conn: &Connection, user_id: i64, application_id: i64) -> Result<(), AppError> {
+    let owned: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM applications WHERE id = ? AND user_id = ?)",
+        params![application_id, user_id],
+        |row| row.get(0),
+    )?;
+    if owned {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!("Candidature {} introuvable", application_id)))
+    }
+}
+
+pub fn load_application(conn: &Connection, user_id: i64, application_id: i64) -> Result<Application, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM applications WHERE id = ? AND user_id = ?",
+        APPLICATION_COLUMNS
+    ))?;
 
     let application = stmt
-        .query_row(params![application_id], |row| {
-            Ok(Application {
-                id: row.get(0)?,
-                user_id: row.get(1)?,
-                job_id: row.get(2)?,
-                status: row.get(3)?,
-                applied_at: row.get(4)?,
-                response_received: row.get(5)?,
-                notes: row.get(6)?,
-                created_at: row.get(7)?,
-                updated_at: row.get(8)?,
-                stages: None,
-                documents: None,
-                application_notes: None,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+        .query_row(params![application_id, user_id], application_from_row)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Candidature {} introuvable", application_id)))?;
 
     // Récupérer les étapes
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, application_id, stage_type, scheduled_at, completed_at, notes, outcome, created_at, updated_at 
-             FROM application_stages 
-             WHERE application_id = ?",
-        )
-        .map_err(|e| e.to_string())?;
+    let mut stmt = conn.prepare(
+        "SELECT id, application_id, stage_type, scheduled_at, completed_at, notes, outcome, created_at, updated_at
+         FROM application_stages
+         WHERE application_id = ?",
+    )?;
 
     let stages: Vec<ApplicationStage> = stmt
-        .query_map(params![application_id], |row| {
-            Ok(ApplicationStage {
-                id: row.get(0)?,
-                application_id: row.get(1)?,
-                stage_type: row.get(2)?,
-                scheduled_at: row.get(3)?,
-                completed_at: row.get(4)?,
-                notes: row.get(5)?,
-                outcome: row.get(6)?,
-                created_at: row.get(7)?,
-                updated_at: row.get(8)?,
-            })
-        })
-        .map_err(|e| e.to_string())?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.to_string())?;
+        .query_map(params![application_id], stage_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
 
     // Récupérer les documents
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, application_id, document_type, file_path, content, created_at, updated_at 
-             FROM application_documents 
-             WHERE application_id = ?",
-        )
-        .map_err(|e| e.to_string())?;
+    let mut stmt = conn.prepare(
+        "SELECT id, application_id, document_type, file_path, content, created_at, updated_at
+         FROM application_documents
+         WHERE application_id = ?",
+    )?;
 
     let documents: Vec<ApplicationDocument> = stmt
-        .query_map(params![application_id], |row| {
-            Ok(ApplicationDocument {
-                id: row.get(0)?,
-                application_id: row.get(1)?,
-                document_type: row.get(2)?,
-                file_path: row.get(3)?,
-                content: row.get(4)?,
-                created_at: row.get(5)?,
-                updated_at: row.get(6)?,
-            })
-        })
-        .map_err(|e| e.to_string())?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.to_string())?;
+        .query_map(params![application_id], document_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
 
     // Récupérer les notes
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, application_id, content, created_at, updated_at 
-             FROM application_notes 
-             WHERE application_id = ?",
-        )
-        .map_err(|e| e.to_string())?;
+    let mut stmt = conn.prepare(
+        "SELECT id, application_id, content, created_at, updated_at
+         FROM application_notes
+         WHERE application_id = ?",
+    )?;
 
     let notes: Vec<ApplicationNote> = stmt
-        .query_map(params![application_id], |row| {
-            Ok(ApplicationNote {
-                id: row.get(0)?,
-                application_id: row.get(1)?,
-                content: row.get(2)?,
-                created_at: row.get(3)?,
-                updated_at: row.get(4)?,
-            })
-        })
-        .map_err(|e| e.to_string())?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.to_string())?;
+        .query_map(params![application_id], note_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Récupérer l'historique des statuts
+    let mut stmt = conn.prepare(
+        "SELECT id, application_id, from_status, to_status, reason, changed_at
+         FROM application_status_history
+         WHERE application_id = ?
+         ORDER BY id",
+    )?;
+
+    let status_history: Vec<ApplicationStatusChange> = stmt
+        .query_map(params![application_id], status_change_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok(Application {
         stages: Some(stages),
         documents: Some(documents),
         application_notes: Some(notes),
+        status_history: Some(status_history),
         ..application
     })
 }
 
+// Fait passer la candidature au statut demandé si la transition est permise
+// et l'inscrit dans l'historique. `now` au format `YYYY-MM-DD HH:MM:SS`
+pub fn set_application_status(
+    conn: &Connection,
+    user_id: i64,
+    application_id: i64,
+    status: ApplicationStatus,
+    reason: Option<&str>,
+    now: &str,
+) -> Result<Application, AppError> {
+    let current = load_application(conn, user_id, application_id)?.status;
+    if !current.can_transition_to(status) {
+        let allowed: Vec<&str> = current.allowed_transitions().iter().map(|next| next.as_str()).collect();
+        return Err(AppError::Validation(format!(
+            "Transition de statut interdite : {} → {} (possibles : {})",
+            current.as_str(),
+            status.as_str(),
+            if allowed.is_empty() { "aucune".to_string() } else { allowed.join(", ") }
+        )));
+    }
+    let reason = reason.map(str::trim).filter(|reason| !reason.is_empty());
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "UPDATE applications SET
+            status = ?1,
+            applied_at = CASE WHEN ?1 = 'applied' THEN COALESCE(applied_at, ?2) ELSE applied_at END,
+            response_received = response_received OR ?3
+         WHERE id = ?4 AND user_id = ?5",
+        params![status.as_str(), now, status.is_response(), application_id, user_id],
+    )?;
+    record_status_change(&tx, application_id, Some(current), status, reason, now)?;
+    tx.commit()?;
+
+    load_application(conn, user_id, application_id)
+}
+
+pub fn insert_stage(
+    conn: &Connection,
+    user_id: i64,
+    application_id: i64,
+    stage_type: &str,
+    scheduled_at: Option<&str>,
+) -> Result<ApplicationStage, AppError> {
+    ensure_owned(conn, user_id, application_id)?;
+    let mut stmt = conn.prepare(
+        "INSERT INTO application_stages (application_id, stage_type, scheduled_at)
+         VALUES (?, ?, ?)
+         RETURNING id, application_id, stage_type, scheduled_at, completed_at, notes, outcome, created_at, updated_at",
+    )?;
+
+    let stage = stmt.query_row(params![application_id, stage_type, scheduled_at], stage_from_row)?;
+
+    Ok(stage)
+}
+
+pub fn insert_note(
+    conn: &Connection,
+    user_id: i64,
+    application_id: i64,
+    content: &str,
+) -> Result<ApplicationNote, AppError> {
+    ensure_owned(conn, user_id, application_id)?;
+    let mut stmt = conn.prepare(
+        "INSERT INTO application_notes (application_id, content)
+         VALUES (?, ?)
+         RETURNING id, application_id, content, created_at, updated_at",
+    )?;
+
+    let note = stmt.query_row(params![application_id, content], note_from_row)?;
+
+    Ok(note)
+}
+
+pub fn insert_document(
+    conn: &Connection,
+    user_id: i64,
+    application_id: i64,
+    document_type: &str,
+    file_path: Option<&str>,
+    content: Option<&str>,
+) -> Result<ApplicationDocument, AppError> {
+    ensure_owned(conn, user_id, application_id)?;
+    let mut stmt = conn.prepare(
+        "INSERT INTO application_documents (application_id, document_type, file_path, content)
+         VALUES (?, ?, ?, ?)
+         RETURNING id, application_id, document_type, file_path, content, created_at, updated_at",
+    )?;
+
+    let document = stmt.query_row(
+        params![application_id, document_type, file_path, content],
+        document_from_row,
+    )?;
+
+    Ok(document)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApplicationSortKey {
+    Updated,
+    Applied,
+    Company,
+    NextStage,
+}
+
+impl ApplicationSortKey {
+    fn resolve(sort_by: Option<&str>) -> Self {
+        match sort_by {
+            Some("applied") => ApplicationSortKey::Applied,
+            Some("company") => ApplicationSortKey::Company,
+            Some("next_stage") => ApplicationSortKey::NextStage,
+            _ => ApplicationSortKey::Updated,
+        }
+    }
+
+    fn expression(self) -> &'static str {
+        match self {
+            ApplicationSortKey::Updated => "applications.updated_at",
+            ApplicationSortKey::Applied => "applications.applied_at",
+            ApplicationSortKey::Company => "jobs.company COLLATE NOCASE",
+            ApplicationSortKey::NextStage => "next_stage.scheduled_at",
+        }
+    }
+
+    fn ascending_by_default(self) -> bool {
+        matches!(self, ApplicationSortKey::Company | ApplicationSortKey::NextStage)
+    }
+}
+
+// Candidatures de l'utilisateur avec leur offre et leur prochaine étape.
+// `now` au format `YYYY-MM-DD HH:MM:SS` délimite les étapes à venir
+pub fn find_applications(
+    conn: &Connection,
+    user_id: i64,
+    filter: &ApplicationFilter,
+    now: &str,
+) -> Result<Vec<ApplicationListItem>, AppError> {
+    let mut query = String::from(
+        "SELECT applications.id, applications.user_id, applications.job_id, applications.status,
+                applications.applied_at, applications.response_received, applications.notes,
+                applications.created_at, applications.updated_at,
+                jobs.title, jobs.company, next_stage.scheduled_at
+         FROM applications
+         JOIN jobs ON jobs.id = applications.job_id
+         LEFT JOIN (
+             SELECT application_id, MIN(datetime(scheduled_at)) AS scheduled_at
+             FROM application_stages
+             WHERE completed_at IS NULL AND datetime(scheduled_at) >= datetime(?)
+             GROUP BY application_id
+         ) next_stage ON next_stage.application_id = applications.id
+         WHERE applications.user_id = ?",
+    );
+    let mut params: Vec<Value> = vec![now.to_string().into(), user_id.into()];
+
+    if !filter.statuses.is_empty() {
+        let placeholders = vec!["?"; filter.statuses.len()].join(", ");
+        query.push_str(&format!(" AND applications.status IN ({})", placeholders));
+        params.extend(filter.statuses.iter().map(|status| Value::from(status.as_str().to_string())));
+    }
+    if let Some(company) = filter.company.as_deref().map(str::trim).filter(|company| !company.is_empty()) {
+        query.push_str(" AND jobs.company LIKE '%' || ? || '%'");
+        params.push(company.to_string().into());
+    }
+    let sent_on = "date(COALESCE(applications.applied_at, applications.created_at))";
+    if let Some(date_from) = &filter.date_from {
+        query.push_str(&format!(" AND {} >= date(?)", sent_on));
+        params.push(date_from.clone().into());
+    }
+    if let Some(date_to) = &filter.date_to {
+        query.push_str(&format!(" AND {} <= date(?)", sent_on));
+        params.push(date_to.clone().into());
+    }
+    match filter.has_upcoming_stage {
+        Some(true) => query.push_str(" AND next_stage.scheduled_at IS NOT NULL"),
+        Some(false) => query.push_str(" AND next_stage.scheduled_at IS NULL"),
+        None => {}
+    }
+
+    // Les valeurs manquantes passent toujours en dernier
+    let sort_key = ApplicationSortKey::resolve(filter.sort_by.as_deref());
+    let direction = if filter.ascending.unwrap_or(sort_key.ascending_by_default()) { "ASC" } else { "DESC" };
+    query.push_str(&format!(
+        " ORDER BY {expr} IS NULL, {expr} {dir}, applications.id {dir}",
+        expr = sort_key.expression(),
+        dir = direction
+    ));
+
+    let mut stmt = conn.prepare(&query)?;
+    let applications = stmt
+        .query_map(params_from_iter(params), |row| {
+            Ok(ApplicationListItem {
+                application: application_from_row(row)?,
+                job_title: row.get(9)?,
+                company: row.get(10)?,
+                next_stage_at: row.get(11)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(applications)
+}
+
+// Étapes, documents, notes et historique suivent par ON DELETE CASCADE
+pub fn remove_application(conn: &Connection, user_id: i64, application_id: i64) -> Result<bool, AppError> {
+    let rows_affected = conn.execute(
+        "DELETE FROM applications WHERE id = ? AND user_id = ?",
+        params![application_id, user_id],
+    )?;
+    Ok(rows_affected > 0)
+}
+
+pub fn modify_stage(
+    conn: &Connection,
+    user_id: i64,
+    stage_id: i64,
+    request: UpdateStageRequest,
+) -> Result<ApplicationStage, AppError> {
+    if request.stage_type.as_deref().is_some_and(|stage_type| stage_type.trim().is_empty()) {
+        return Err(AppError::Validation("Le type d'étape est obligatoire".to_string()));
+    }
+    let mut stmt = conn.prepare(&format!(
+        "UPDATE application_stages SET
+            stage_type = COALESCE(?, stage_type),
+            scheduled_at = CASE WHEN ? THEN ? ELSE scheduled_at END,
+            completed_at = CASE WHEN ? THEN ? ELSE completed_at END,
+            notes = CASE WHEN ? THEN ? ELSE notes END,
+            outcome = CASE WHEN ? THEN ? ELSE outcome END
+         WHERE {}
+         RETURNING {}",
+        OWNED_CHILD, STAGE_COLUMNS
+    ))?;
+
+    stmt.query_row(
+        params![
+            request.stage_type.as_deref().map(str::trim),
+            request.scheduled_at.is_some(),
+            request.scheduled_at.flatten(),
+            request.completed_at.is_some(),
+            request.completed_at.flatten(),
+            request.notes.is_some(),
+            request.notes.flatten(),
+            request.outcome.is_some(),
+            request.outcome.flatten(),
+            stage_id,
+            user_id
+        ],
+        stage_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Étape {} introuvable", stage_id)))
+}
+
+pub fn remove_stage(conn: &Connection, user_id: i64, stage_id: i64) -> Result<bool, AppError> {
+    let rows_affected = conn.execute(
+        &format!("DELETE FROM application_stages WHERE {}", OWNED_CHILD),
+        params![stage_id, user_id],
+    )?;
+    Ok(rows_affected > 0)
+}
+
+pub fn modify_note(conn: &Connection, user_id: i64, note_id: i64, content: &str) -> Result<ApplicationNote, AppError> {
+    if content.trim().is_empty() {
+        return Err(AppError::Validation("Une note ne peut pas être vide".to_string()));
+    }
+    let mut stmt = conn.prepare(&format!(
+        "UPDATE application_notes SET content = ? WHERE {} RETURNING {}",
+        OWNED_CHILD, NOTE_COLUMNS
+    ))?;
+
+    stmt.query_row(params![content, note_id, user_id], note_from_row)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Note {} introuvable", note_id)))
+}
+
+pub fn remove_note(conn: &Connection, user_id: i64, note_id: i64) -> Result<bool, AppError> {
+    let rows_affected = conn.execute(
+        &format!("DELETE FROM application_notes WHERE {}", OWNED_CHILD),
+        params![note_id, user_id],
+    )?;
+    Ok(rows_affected > 0)
+}
+
+pub fn modify_document(
+    conn: &Connection,
+    user_id: i64,
+    document_id: i64,
+    request: UpdateDocumentRequest,
+) -> Result<ApplicationDocument, AppError> {
+    if request.document_type.as_deref().is_some_and(|document_type| document_type.trim().is_empty()) {
+        return Err(AppError::Validation("Le type de document est obligatoire".to_string()));
+    }
+    let mut stmt = conn.prepare(&format!(
+        "UPDATE application_documents SET
+            document_type = COALESCE(?, document_type),
+            file_path = CASE WHEN ? THEN ? ELSE file_path END,
+            content = CASE WHEN ? THEN ? ELSE content END
+         WHERE {}
+         RETURNING {}",
+        OWNED_CHILD, DOCUMENT_COLUMNS
+    ))?;
+
+    stmt.query_row(
+        params![
+            request.document_type.as_deref().map(str::trim),
+            request.file_path.is_some(),
+            request.file_path.flatten(),
+            request.content.is_some(),
+            request.content.flatten(),
+            document_id,
+            user_id
+        ],
+        document_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Document {} introuvable", document_id)))
+}
+
+pub fn remove_document(conn: &Connection, user_id: i64, document_id: i64) -> Result<bool, AppError> {
+    let rows_affected = conn.execute(
+        &format!("DELETE FROM application_documents WHERE {}", OWNED_CHILD),
+        params![document_id, user_id],
+    )?;
+    Ok(rows_affected > 0)
+}
+
 #[tauri::command]
-pub async fn update_application_status(
+pub async fn list_applications(
+    state: State<'_, AppState>,
+    filter: Option<ApplicationFilter>,
+) -> Result<Vec<ApplicationListItem>, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| find_applications(conn, user_id, &filter.unwrap_or_default(), &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_application(state: State<'_, AppState>, application_id: i64) -> Result<bool, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| remove_application(conn, user_id, application_id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_application(
+    state: State<'_, AppState>,
+    request: CreateApplicationRequest,
+) -> Result<Application, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| insert_application(conn, user_id, &request))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_application(
     state: State<'_, AppState>,
     application_id: i64,
-    status: String,
-) -> Result<bool, String> {
-    let conn = state.db.lock().await;
-    let conn = conn.as_ref().ok_or("Database connection not initialized")?;
-
-    let rows_affected = conn
-        .execute(
-            "UPDATE applications SET status = ? WHERE id = ?",
-            params![status, application_id],
-        )
-        .map_err(|e| e.to_string())?;
+) -> Result<Application, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| load_application(conn, user_id, application_id))
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    Ok(rows_affected > 0)
+#[tauri::command]
+pub async fn update_application_status(
+    state: State<'_, AppState>,
+    application_id: i64,
+    status: ApplicationStatus,
+    reason: Option<String>,
+) -> Result<Application, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| {
+            set_application_status(conn, user_id, application_id, status, reason.as_deref(), &now_timestamp())
+        })
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -198,37 +702,12 @@ pub async fn add_application_stage(
     stage_type: String,
     scheduled_at: Option<String>,
 ) -> Result<ApplicationStage, String> {
-    let conn = state.db.lock().await;
-    let conn = conn.as_ref().ok_or("Database connection not initialized")?;
-
-    let mut stmt = conn
-        .prepare(
-            "INSERT INTO application_stages (application_id, stage_type, scheduled_at) 
-             VALUES (?, ?, ?) 
-             RETURNING id, application_id, stage_type, scheduled_at, completed_at, notes, outcome, created_at, updated_at",
-        )
-        .map_err(|e| e.to_string())?;
-
-    let stage = stmt
-        .query_row(
-            params![application_id, stage_type, scheduled_at],
-            |row| {
-                Ok(ApplicationStage {
-                    id: row.get(0)?,
-                    application_id: row.get(1)?,
-                    stage_type: row.get(2)?,
-                    scheduled_at: row.get(3)?,
-                    completed_at: row.get(4)?,
-                    notes: row.get(5)?,
-                    outcome: row.get(6)?,
-                    created_at: row.get(7)?,
-                    updated_at: row.get(8)?,
-                })
-            },
-        )
-        .map_err(|e| e.to_string())?;
-
-    Ok(stage)
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| insert_stage(conn, user_id, application_id, &stage_type, scheduled_at.as_deref()))
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -237,30 +716,12 @@ pub async fn add_application_note(
     application_id: i64,
     content: String,
 ) -> Result<ApplicationNote, String> {
-    let conn = state.db.lock().await;
-    let conn = conn.as_ref().ok_or("Database connection not initialized")?;
-
-    let mut stmt = conn
-        .prepare(
-            "INSERT INTO application_notes (application_id, content) 
-             VALUES (?, ?) 
-             RETURNING id, application_id, content, created_at, updated_at",
-        )
-        .map_err(|e| e.to_string())?;
-
-    let note = stmt
-        .query_row(params![application_id, content], |row| {
-            Ok(ApplicationNote {
-                id: row.get(0)?,
-                application_id: row.get(1)?,
-                content: row.get(2)?,
-                created_at: row.get(3)?,
-                updated_at: row.get(4)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
-
-    Ok(note)
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| insert_note(conn, user_id, application_id, &content))
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -271,33 +732,91 @@ pub async fn add_application_document(
     file_path: Option<String>,
     content: Option<String>,
 ) -> Result<ApplicationDocument, String> {
-    let conn = state.db.lock().await;
-    let conn = conn.as_ref().ok_or("Database connection not initialized")?;
-
-    let mut stmt = conn
-        .prepare(
-            "INSERT INTO application_documents (application_id, document_type, file_path, content) 
-             VALUES (?, ?, ?, ?) 
-             RETURNING id, application_id, document_type, file_path, content, created_at, updated_at",
-        )
-        .map_err(|e| e.to_string())?;
-
-    let document = stmt
-        .query_row(
-            params![application_id, document_type, file_path, content],
-            |row| {
-                Ok(ApplicationDocument {
-                    id: row.get(0)?,
-                    application_id: row.get(1)?,
-                    document_type: row.get(2)?,
-                    file_path: row.get(3)?,
-                    content: row.get(4)?,
-                    created_at: row.get(5)?,
-                    updated_at: row.get(6)?,
-                })
-            },
-        )
-        .map_err(|e| e.to_string())?;
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| {
+            insert_document(
+                conn,
+                user_id,
+                application_id,
+                &document_type,
+                file_path.as_deref(),
+                content.as_deref(),
+            )
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    Ok(document)
-} 
\ No newline at end of file
+#[tauri::command]
+pub async fn update_application_stage(
+    state: State<'_, AppState>,
+    stage_id: i64,
+    request: UpdateStageRequest,
+) -> Result<ApplicationStage, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| modify_stage(conn, user_id, stage_id, request))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_application_stage(state: State<'_, AppState>, stage_id: i64) -> Result<bool, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| remove_stage(conn, user_id, stage_id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_application_note(
+    state: State<'_, AppState>,
+    note_id: i64,
+    content: String,
+) -> Result<ApplicationNote, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| modify_note(conn, user_id, note_id, &content))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_application_note(state: State<'_, AppState>, note_id: i64) -> Result<bool, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| remove_note(conn, user_id, note_id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_application_document(
+    state: State<'_, AppState>,
+    document_id: i64,
+    request: UpdateDocumentRequest,
+) -> Result<ApplicationDocument, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| modify_document(conn, user_id, document_id, request))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_application_document(state: State<'_, AppState>, document_id: i64) -> Result<bool, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| remove_document(conn, user_id, document_id))
+        .await
+        .map_err(|e| e.to_string())
+}
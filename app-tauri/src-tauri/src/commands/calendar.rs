@@ -0,0 +1,291 @@
+use crate::{
+    calendar::{event_uid, parse_calendar, parse_event_uid, write_calendar, CalendarEvent, ImportedEvent},
+    commands::applications::{ensure_owned, stage_from_row, OWNED_CHILD, STAGE_COLUMNS},
+    dedup::{company_tokens, title_tokens, tokens},
+    error::AppError,
+    models::ApplicationStage,
+    time::now_timestamp,
+    AppState,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use tauri::State;
+
+// Type d'étape donné aux invitations importées
+pub const IMPORTED_STAGE_TYPE: &str = "interview";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CalendarImport {
+    pub created: Vec<ApplicationStage>,
+    pub updated: Vec<ApplicationStage>,
+    // Intitulés des événements sans candidature correspondante (ou ambigus)
+    pub unmatched: Vec<String>,
+    // Événements annulés, entretiens du Kanban ou invitations sans UID déjà importés
+    pub ignored: usize,
+}
+
+fn summary(kind: &str, title: &str, company: &str) -> String {
+    format!("{} : {} ({})", kind, title, company)
+}
+
+// Étapes et entretiens d'une candidature, ou tous ceux à venir à partir de `now`
+pub fn calendar_events(
+    conn: &Connection,
+    user_id: i64,
+    application_id: Option<i64>,
+    now: &str,
+) -> Result<Vec<CalendarEvent>, AppError> {
+    if let Some(application_id) = application_id {
+        ensure_owned(conn, user_id, application_id)?;
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT application_stages.id, application_stages.calendar_uid, datetime(application_stages.scheduled_at),
+                application_stages.stage_type, application_stages.notes, application_stages.updated_at,
+                jobs.title, jobs.company, jobs.location
+         FROM application_stages
+         JOIN applications ON applications.id = application_stages.application_id
+         JOIN jobs ON jobs.id = applications.job_id
+         WHERE applications.user_id = ?1
+           AND datetime(application_stages.scheduled_at) IS NOT NULL
+           AND CASE WHEN ?2 IS NULL
+                    THEN datetime(application_stages.scheduled_at) >= datetime(?3)
+                    ELSE applications.id = ?2 END
+         ORDER BY datetime(application_stages.scheduled_at), application_stages.id",
+    )?;
+    let mut events = stmt
+        .query_map(params![user_id, application_id, now], |row| {
+            let id: i64 = row.get(0)?;
+            let uid: Option<String> = row.get(1)?;
+            Ok(CalendarEvent {
+                uid: uid.unwrap_or_else(|| event_uid("stage", id)),
+                start: row.get(2)?,
+                end: None,
+                summary: summary(&row.get::<_, String>(3)?, &row.get::<_, String>(6)?, &row.get::<_, String>(7)?),
+                description: row.get(4)?,
+                location: row.get(8)?,
+                last_modified: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Entretiens saisis sur les cartes du Kanban de la même offre
+    let mut stmt = conn.prepare(
+        "SELECT interviews.id, datetime(interviews.date), interviews.interview_type, interviews.notes,
+                jobs.title, jobs.company, jobs.location
+         FROM interviews
+         JOIN kanban_cards ON kanban_cards.id = interviews.card_id
+         JOIN kanban_columns ON kanban_columns.id = kanban_cards.column_id
+         JOIN jobs ON jobs.id = kanban_cards.job_id
+         WHERE kanban_columns.user_id = ?1
+           AND datetime(interviews.date) IS NOT NULL
+           AND CASE WHEN ?2 IS NULL
+                    THEN datetime(interviews.date) >= datetime(?3)
+                    ELSE kanban_cards.job_id = (SELECT job_id FROM applications WHERE id = ?2) END",
+    )?;
+    let interviews = stmt
+        .query_map(params![user_id, application_id, now], |row| {
+            Ok(CalendarEvent {
+                uid: event_uid("interview", row.get(0)?),
+                start: row.get(1)?,
+                end: None,
+                summary: summary(&row.get::<_, String>(2)?, &row.get::<_, String>(4)?, &row.get::<_, String>(5)?),
+                description: row.get(3)?,
+                location: row.get(6)?,
+                last_modified: None,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    events.extend(interviews);
+    events.sort_by(|a, b| a.start.cmp(&b.start));
+    Ok(events)
+}
+
+pub fn export_calendar_text(
+    conn: &Connection,
+    user_id: i64,
+    application_id: Option<i64>,
+    now: &str,
+) -> Result<String, AppError> {
+    Ok(write_calendar(&calendar_events(conn, user_id, application_id, now)?, now))
+}
+
+struct ApplicationCandidate {
+    id: i64,
+    company: HashSet<String>,
+    title: HashSet<String>,
+}
+
+fn application_candidates(conn: &Connection, user_id: i64) -> Result<Vec<ApplicationCandidate>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT applications.id, jobs.title, jobs.company
+         FROM applications
+         JOIN jobs ON jobs.id = applications.job_id
+         WHERE applications.user_id = ? AND applications.status NOT IN ('rejected', 'withdrawn')",
+    )?;
+    let candidates = stmt
+        .query_map([user_id], |row| {
+            Ok(ApplicationCandidate {
+                id: row.get(0)?,
+                title: title_tokens(&row.get::<_, String>(1)?),
+                company: company_tokens(&row.get::<_, String>(2)?),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(candidates)
+}
+
+// L'entreprise doit apparaître en entier dans l'invitation (intitulé,
+// description, lieu ou organisateur) ; l'intitulé du poste départage
+// plusieurs candidatures chez la même entreprise. Une égalité reste ambiguë
+fn match_application(candidates: &[ApplicationCandidate], event: &ImportedEvent) -> Option<i64> {
+    let text = [
+        Some(event.summary.as_str()),
+        event.description.as_deref(),
+        event.location.as_deref(),
+        event.organizer.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+    let words: HashSet<String> = tokens(&text).into_iter().collect();
+
+    let mut scored: Vec<(usize, i64)> = candidates
+        .iter()
+        .filter(|candidate| !candidate.company.is_empty() && candidate.company.is_subset(&words))
+        .map(|candidate| (candidate.title.intersection(&words).count(), candidate.id))
+        .collect();
+    scored.sort_unstable_by_key(|&(score, _)| Reverse(score));
+    match scored.as_slice() {
+        [] => None,
+        [(best, id), rest @ ..] if rest.first().is_none_or(|(next, _)| next < best) => Some(*id),
+        _ => None,
+    }
+}
+
+// Étape déjà connue : exportée par l'application (UID généré) ou importée
+// auparavant (UID d'origine conservé)
+fn existing_stage(conn: &Connection, user_id: i64, uid: &str) -> Result<Option<i64>, AppError> {
+    if let Some(("stage", id)) = parse_event_uid(uid) {
+        return Ok(Some(id));
+    }
+    let id = conn
+        .query_row(
+            "SELECT application_stages.id FROM application_stages
+             JOIN applications ON applications.id = application_stages.application_id
+             WHERE application_stages.calendar_uid = ? AND applications.user_id = ?",
+            params![uid, user_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(id)
+}
+
+fn stage_at(conn: &Connection, application_id: i64, scheduled_at: &str) -> Result<bool, AppError> {
+    let exists = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM application_stages WHERE application_id = ? AND scheduled_at = ?)",
+        params![application_id, scheduled_at],
+        |row| row.get(0),
+    )?;
+    Ok(exists)
+}
+
+pub fn import_calendar_events(
+    conn: &Connection,
+    user_id: i64,
+    events: &[ImportedEvent],
+) -> Result<CalendarImport, AppError> {
+    let candidates = application_candidates(conn, user_id)?;
+    let mut report = CalendarImport::default();
+
+    let tx = conn.unchecked_transaction()?;
+    for event in events {
+        let own_interview = event
+            .uid
+            .as_deref()
+            .and_then(parse_event_uid)
+            .is_some_and(|(kind, _)| kind == "interview");
+        if event.cancelled || own_interview {
+            report.ignored += 1;
+            continue;
+        }
+        let Some(start) = &event.start else {
+            report.unmatched.push(event.summary.clone());
+            continue;
+        };
+
+        if let Some(uid) = &event.uid {
+            if let Some(stage_id) = existing_stage(&tx, user_id, uid)? {
+                let updated = tx
+                    .query_row(
+                        &format!(
+                            "UPDATE application_stages SET scheduled_at = ? WHERE {} RETURNING {}",
+                            OWNED_CHILD, STAGE_COLUMNS
+                        ),
+                        params![start, stage_id, user_id],
+                        stage_from_row,
+                    )
+                    .optional()?;
+                match updated {
+                    Some(stage) => report.updated.push(stage),
+                    None => report.unmatched.push(event.summary.clone()),
+                }
+                continue;
+            }
+        }
+
+        match match_application(&candidates, event) {
+            // Sans UID, une invitation déjà importée se reconnaît à sa candidature et son horaire
+            Some(application_id) if event.uid.is_none() && stage_at(&tx, application_id, start)? => {
+                report.ignored += 1;
+            }
+            Some(application_id) => {
+                let stage = tx.query_row(
+                    &format!(
+                        "INSERT INTO application_stages (application_id, stage_type, scheduled_at, notes, calendar_uid)
+                         VALUES (?, ?, ?, ?, ?)
+                         RETURNING {}",
+                        STAGE_COLUMNS
+                    ),
+                    params![application_id, IMPORTED_STAGE_TYPE, start, event.summary, event.uid],
+                    stage_from_row,
+                )?;
+                report.created.push(stage);
+            }
+            None => report.unmatched.push(event.summary.clone()),
+        }
+    }
+    tx.commit()?;
+
+    Ok(report)
+}
+
+pub fn import_calendar_text(conn: &Connection, user_id: i64, text: &str) -> Result<CalendarImport, AppError> {
+    import_calendar_events(conn, user_id, &parse_calendar(text))
+}
+
+// Sans candidature : toutes les étapes et tous les entretiens à venir
+#[tauri::command]
+pub async fn export_calendar(state: State<'_, AppState>, application_id: Option<i64>) -> Result<String, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| export_calendar_text(conn, user_id, application_id, &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn import_calendar(state: State<'_, AppState>, path: String) -> Result<CalendarImport, String> {
+    let text = tokio::fs::read_to_string(&path).await.map_err(|e| e.to_string())?;
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| import_calendar_text(conn, user_id, &text))
+        .await
+        .map_err(|e| e.to_string())
+}
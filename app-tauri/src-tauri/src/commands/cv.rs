@@ -0,0 +1,205 @@
+use crate::{
+    commands::profile::to_json,
+    cv::{extract_text, parse_cv, CvFormat, CvSection, ParsedCv, CV_PARSER_VERSION},
+    error::AppError,
+    models::CVInfo,
+    time::now_timestamp,
+    AppState,
+};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+// CV importé : texte brut conservé et résultat de sa dernière analyse
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CvDocument {
+    pub id: i64,
+    pub file_path: String,
+    pub format: CvFormat,
+    pub parser_version: i32,
+    pub cv: CVInfo,
+    pub language: Option<String>,
+    pub sections: Vec<CvSection>,
+    pub raw_text: String,
+    pub imported_at: String,
+    pub parsed_at: String,
+    // Vrai si l'import ou la réanalyse vient de compléter le profil
+    #[serde(default)]
+    pub profile_updated: bool,
+}
+
+const CV_DOCUMENT_COLUMNS: &str = "id, file_path, format, raw_text, parsed, parser_version, imported_at, parsed_at";
+
+fn cv_document_from_row(row: &Row) -> rusqlite::Result<CvDocument> {
+    let conversion_error = |index: usize, e: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, e)
+    };
+    let file_path: String = row.get(1)?;
+    let format: String = row.get(2)?;
+    let parsed: String = row.get(4)?;
+    let parsed: ParsedCv = serde_json::from_str(&parsed).map_err(|e| conversion_error(4, Box::new(e)))?;
+    let imported_at: String = row.get(6)?;
+
+    Ok(CvDocument {
+        id: row.get(0)?,
+        format: CvFormat::parse(&format)
+            .ok_or_else(|| conversion_error(2, format!("Format de CV inconnu : {}", format).into()))?,
+        parser_version: row.get(5)?,
+        cv: parsed.cv_info(PathBuf::from(&file_path), imported_at.chars().take(10).collect()),
+        language: parsed.language,
+        sections: parsed.sections,
+        raw_text: row.get(3)?,
+        file_path,
+        imported_at,
+        parsed_at: row.get(7)?,
+        profile_updated: false,
+    })
+}
+
+pub fn load_cv_document(conn: &Connection, user_id: i64, id: i64) -> Result<CvDocument, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM cv_documents WHERE id = ? AND user_id = ?", CV_DOCUMENT_COLUMNS),
+        params![id, user_id],
+        cv_document_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("CV {} introuvable", id)))
+}
+
+pub fn load_latest_cv_document(conn: &Connection, user_id: i64) -> Result<Option<CvDocument>, AppError> {
+    Ok(conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM cv_documents WHERE user_id = ? ORDER BY imported_at DESC, id DESC LIMIT 1",
+                CV_DOCUMENT_COLUMNS
+            ),
+            [user_id],
+            cv_document_from_row,
+        )
+        .optional()?)
+}
+
+// Reporte le CV dans le profil s'il existe. Une information que l'analyse
+// n'a pas trouvée ne remplace pas celle déjà saisie
+fn apply_to_profile(conn: &Connection, user_id: i64, cv: &CVInfo, now: &str) -> Result<bool, AppError> {
+    let updated = conn.execute(
+        "UPDATE user_profiles SET
+            cv_path = ?1,
+            cv_last_updated = ?2,
+            skills = COALESCE(?3, skills),
+            experience_years = COALESCE(?4, experience_years),
+            education = COALESCE(?5, education),
+            certifications = COALESCE(?6, certifications),
+            updated_at = ?7
+         WHERE user_id = ?8",
+        params![
+            cv.path.to_string_lossy(),
+            cv.last_updated,
+            to_json(&cv.skills)?,
+            cv.experience_years,
+            to_json(&cv.education)?,
+            to_json(&cv.certifications)?,
+            now,
+            user_id,
+        ],
+    )?;
+    Ok(updated > 0)
+}
+
+// Importe un CV (PDF, DOCX ou texte) : le texte extrait est conservé, analysé
+// puis reporté dans le profil de l'utilisateur
+pub fn import_cv_document(
+    conn: &Connection,
+    user_id: i64,
+    path: &Path,
+    bytes: &[u8],
+    today: NaiveDate,
+    now: &str,
+) -> Result<CvDocument, AppError> {
+    let format = CvFormat::from_path(path)?;
+    let raw_text = extract_text(bytes, format)?;
+    let parsed = parse_cv(&raw_text, today);
+    let parsed_json = serde_json::to_string(&parsed).map_err(|e| AppError::Internal(e.to_string()))?;
+    let file_path = path.to_string_lossy();
+
+    conn.execute(
+        "INSERT INTO cv_documents (user_id, file_path, format, raw_text, parsed, parser_version, imported_at, parsed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+        params![user_id, file_path, format.as_str(), raw_text, parsed_json, CV_PARSER_VERSION, now],
+    )?;
+
+    let mut document = load_cv_document(conn, user_id, conn.last_insert_rowid())?;
+    document.profile_updated = apply_to_profile(conn, user_id, &document.cv, now)?;
+    Ok(document)
+}
+
+// Réanalyse le texte brut d'un CV déjà importé avec la version actuelle de
+// l'analyse, sans relire le fichier d'origine
+pub fn reparse_cv_document(
+    conn: &Connection,
+    user_id: i64,
+    id: i64,
+    today: NaiveDate,
+    now: &str,
+) -> Result<CvDocument, AppError> {
+    let document = load_cv_document(conn, user_id, id)?;
+    let parsed = parse_cv(&document.raw_text, today);
+    let parsed_json = serde_json::to_string(&parsed).map_err(|e| AppError::Internal(e.to_string()))?;
+    conn.execute(
+        "UPDATE cv_documents SET parsed = ?, parser_version = ?, parsed_at = ? WHERE id = ? AND user_id = ?",
+        params![parsed_json, CV_PARSER_VERSION, now, id, user_id],
+    )?;
+
+    let mut document = load_cv_document(conn, user_id, id)?;
+    // Seul le CV le plus récent fait foi pour le profil
+    let latest = load_latest_cv_document(conn, user_id)?.map(|latest| latest.id);
+    if latest == Some(id) {
+        document.profile_updated = apply_to_profile(conn, user_id, &document.cv, now)?;
+    }
+    Ok(document)
+}
+
+// Importe le CV et complète le profil ; les scores de matching sont recalculés
+// en arrière-plan si le profil a changé
+#[tauri::command]
+pub async fn import_cv(app: tauri::AppHandle, state: State<'_, AppState>, path: String) -> Result<CvDocument, String> {
+    let bytes = tokio::fs::read(&path).await.map_err(|e| e.to_string())?;
+    let today = chrono::Local::now().date_naive();
+    let user_id = state.user_id();
+    let document = state
+        .db
+        .call(move |conn| import_cv_document(conn, user_id, Path::new(&path), &bytes, today, &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())?;
+    if document.profile_updated {
+        state.rescorer.start(app, state.db.clone(), state.operations.clone(), user_id);
+    }
+    Ok(document)
+}
+
+#[tauri::command]
+pub async fn reparse_cv(app: tauri::AppHandle, state: State<'_, AppState>, id: i64) -> Result<CvDocument, String> {
+    let today = chrono::Local::now().date_naive();
+    let user_id = state.user_id();
+    let document = state
+        .db
+        .call(move |conn| reparse_cv_document(conn, user_id, id, today, &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())?;
+    if document.profile_updated {
+        state.rescorer.start(app, state.db.clone(), state.operations.clone(), user_id);
+    }
+    Ok(document)
+}
+
+#[tauri::command]
+pub async fn get_latest_cv(state: State<'_, AppState>) -> Result<Option<CvDocument>, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| load_latest_cv_document(conn, user_id))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,31 @@
+use crate::{
+    database::{cache::CacheStats, executor::DbMetrics, pool::PoolMetrics},
+    AppState,
+};
+use serde::Serialize;
+use tauri::State;
+
+#[derive(Debug, Serialize)]
+pub struct DatabaseMetrics {
+    pub operations: PoolMetrics,
+    pub executor: DbMetrics,
+}
+
+#[tauri::command]
+pub async fn get_pool_metrics(state: State<'_, AppState>) -> Result<DatabaseMetrics, String> {
+    Ok(DatabaseMetrics {
+        operations: state.operations.pool_metrics(),
+        executor: state.db.metrics(),
+    })
+}
+
+#[tauri::command]
+pub async fn get_cache_stats(state: State<'_, AppState>) -> Result<CacheStats, String> {
+    Ok(state.operations.cache_stats())
+}
+
+#[tauri::command]
+pub async fn flush_job_cache(state: State<'_, AppState>) -> Result<CacheStats, String> {
+    state.operations.flush_cache();
+    Ok(state.operations.cache_stats())
+}
@@ -0,0 +1,447 @@
+use crate::{
+    database::operations::{job_from_row, scored_job_columns, SCORED_JOBS_FROM},
+    dedup::{candidate_pairs, similarity, JobFingerprint},
+    error::AppError,
+    models::Job,
+    time::now_timestamp,
+    AppState,
+};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::State;
+
+// Score à partir duquel deux offres sont proposées comme doublons
+pub const DEFAULT_DUPLICATE_THRESHOLD: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateStatus {
+    Pending,
+    Ignored,
+    Merged,
+}
+
+impl DuplicateStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DuplicateStatus::Pending => "pending",
+            DuplicateStatus::Ignored => "ignored",
+            DuplicateStatus::Merged => "merged",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "ignored" => DuplicateStatus::Ignored,
+            "merged" => DuplicateStatus::Merged,
+            _ => DuplicateStatus::Pending,
+        }
+    }
+}
+
+// Paire d'offres proposée au dédoublonnage. `job` et `duplicate` sont absents
+// une fois l'offre supprimée (fusionnée dans une autre)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateCandidate {
+    pub id: i64,
+    pub job_id: i64,
+    pub duplicate_job_id: i64,
+    pub score: f64,
+    pub title_similarity: f64,
+    pub description_similarity: Option<f64>,
+    // « fingerprint » (titre, entreprise et lieu identiques) ou « similarity »
+    pub reason: String,
+    pub status: DuplicateStatus,
+    pub merged_into: Option<i64>,
+    pub detected_at: String,
+    pub resolved_at: Option<String>,
+    pub job: Option<Job>,
+    pub duplicate: Option<Job>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateDetectionStats {
+    pub scanned: usize,
+    // Paires en attente après la détection, dont `new` apparues à cette détection
+    pub pending: usize,
+    pub new: usize,
+}
+
+const CANDIDATE_COLUMNS: &str = "id, job_id, duplicate_job_id, score, title_similarity, description_similarity,
+    reason, status, merged_into, detected_at, resolved_at";
+
+fn candidate_from_row(row: &Row) -> rusqlite::Result<DuplicateCandidate> {
+    let status: String = row.get(7)?;
+    Ok(DuplicateCandidate {
+        id: row.get(0)?,
+        job_id: row.get(1)?,
+        duplicate_job_id: row.get(2)?,
+        score: row.get(3)?,
+        title_similarity: row.get(4)?,
+        description_similarity: row.get(5)?,
+        reason: row.get(6)?,
+        status: DuplicateStatus::parse(&status),
+        merged_into: row.get(8)?,
+        detected_at: row.get(9)?,
+        resolved_at: row.get(10)?,
+        job: None,
+        duplicate: None,
+    })
+}
+
+// Offre avec le score de matching du profil
+fn find_job(conn: &Connection, user_id: i64, id: i64) -> Result<Option<Job>, AppError> {
+    let sql = format!("SELECT {} FROM {} WHERE jobs.id = ?", scored_job_columns(), SCORED_JOBS_FROM);
+    Ok(conn.query_row(&sql, [user_id, id], job_from_row).optional()?)
+}
+
+// Compare les offres et enregistre les paires candidates. Les paires en
+// attente qui ne sont plus retrouvées disparaissent ; les paires ignorées ou
+// fusionnées ne sont jamais reproposées
+pub fn find_duplicate_candidates(
+    conn: &Connection,
+    threshold: f64,
+    now: &str,
+) -> Result<DuplicateDetectionStats, AppError> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(AppError::Validation("Le seuil de similarité doit être entre 0 et 1".to_string()));
+    }
+
+    let jobs = {
+        let mut stmt = conn.prepare("SELECT id, title, company, location, description FROM jobs ORDER BY id")?;
+        let rows = stmt.query_map([], |row| {
+            let (title, company, location, description): (String, String, String, String) =
+                (row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?);
+            Ok(JobFingerprint::new(row.get(0)?, &title, &company, &location, &description))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    let mut stats = DuplicateDetectionStats {
+        scanned: jobs.len(),
+        ..Default::default()
+    };
+    let mut found = HashSet::new();
+
+    for (i, j) in candidate_pairs(&jobs) {
+        let similarity = similarity(&jobs[i], &jobs[j]);
+        if !similarity.same_fingerprint && similarity.score < threshold {
+            continue;
+        }
+        let reason = if similarity.same_fingerprint { "fingerprint" } else { "similarity" };
+        let (job_id, duplicate_job_id) = (jobs[i].job_id, jobs[j].job_id);
+
+        let status: Option<String> = tx
+            .query_row(
+                "SELECT status FROM duplicate_candidates WHERE job_id = ? AND duplicate_job_id = ?",
+                [job_id, duplicate_job_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match status.as_deref() {
+            None => {
+                tx.execute(
+                    "INSERT INTO duplicate_candidates (job_id, duplicate_job_id, score, title_similarity,
+                        description_similarity, reason, detected_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)",
+                    params![job_id, duplicate_job_id, similarity.score, similarity.title, similarity.description, reason, now],
+                )?;
+                stats.new += 1;
+            }
+            Some("pending") => {
+                tx.execute(
+                    "UPDATE duplicate_candidates
+                     SET score = ?, title_similarity = ?, description_similarity = ?, reason = ?, detected_at = ?
+                     WHERE job_id = ? AND duplicate_job_id = ?",
+                    params![similarity.score, similarity.title, similarity.description, reason, now, job_id, duplicate_job_id],
+                )?;
+            }
+            Some(_) => continue,
+        }
+        found.insert((job_id, duplicate_job_id));
+    }
+
+    // Paires en attente devenues trop éloignées (offres modifiées entre-temps)
+    let pending: Vec<(i64, i64, i64)> = {
+        let mut stmt = tx.prepare("SELECT id, job_id, duplicate_job_id FROM duplicate_candidates WHERE status = 'pending'")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+    for (id, job_id, duplicate_job_id) in pending {
+        if !found.contains(&(job_id, duplicate_job_id)) {
+            tx.execute("DELETE FROM duplicate_candidates WHERE id = ?", [id])?;
+        }
+    }
+
+    tx.commit()?;
+    stats.pending = found.len();
+    Ok(stats)
+}
+
+// Paires candidates, les plus probables d'abord, avec les deux offres
+pub fn load_duplicate_candidates(
+    conn: &Connection,
+    user_id: i64,
+    status: Option<DuplicateStatus>,
+) -> Result<Vec<DuplicateCandidate>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM duplicate_candidates WHERE ?1 IS NULL OR status = ?1 ORDER BY score DESC, id",
+        CANDIDATE_COLUMNS
+    ))?;
+    let candidates = stmt
+        .query_map([status.map(|status| status.as_str())], candidate_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    candidates
+        .into_iter()
+        .map(|mut candidate| {
+            candidate.job = find_job(conn, user_id, candidate.job_id)?;
+            candidate.duplicate = find_job(conn, user_id, candidate.duplicate_job_id)?;
+            Ok(candidate)
+        })
+        .collect()
+}
+
+pub fn load_duplicate_candidate(conn: &Connection, id: i64) -> Result<DuplicateCandidate, AppError> {
+    let sql = format!("SELECT {} FROM duplicate_candidates WHERE id = ?", CANDIDATE_COLUMNS);
+    conn.query_row(&sql, [id], candidate_from_row)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Doublon {} introuvable", id)))
+}
+
+pub fn ignore_duplicate_candidate(conn: &Connection, id: i64, now: &str) -> Result<DuplicateCandidate, AppError> {
+    let candidate = load_duplicate_candidate(conn, id)?;
+    if candidate.status == DuplicateStatus::Merged {
+        return Err(AppError::Validation("Ce doublon a déjà été fusionné".to_string()));
+    }
+    conn.execute(
+        "UPDATE duplicate_candidates SET status = 'ignored', resolved_at = ? WHERE id = ?",
+        params![now, id],
+    )?;
+    load_duplicate_candidate(conn, id)
+}
+
+// Fusionne la paire : `survivor_id` (par défaut l'offre la plus ancienne)
+// est conservée, l'autre supprimée
+pub fn merge_duplicate_candidate(
+    conn: &Connection,
+    user_id: i64,
+    id: i64,
+    survivor_id: Option<i64>,
+    now: &str,
+) -> Result<Job, AppError> {
+    let candidate = load_duplicate_candidate(conn, id)?;
+    if candidate.status == DuplicateStatus::Merged {
+        return Err(AppError::Validation("Ce doublon a déjà été fusionné".to_string()));
+    }
+    let survivor_id = survivor_id.unwrap_or(candidate.job_id);
+    let duplicate_id = if survivor_id == candidate.job_id {
+        candidate.duplicate_job_id
+    } else if survivor_id == candidate.duplicate_job_id {
+        candidate.job_id
+    } else {
+        return Err(AppError::Validation(format!(
+            "L'offre {} ne fait pas partie de ce doublon",
+            survivor_id
+        )));
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    let job = merge_jobs(&tx, user_id, survivor_id, duplicate_id)?;
+    tx.execute(
+        "UPDATE duplicate_candidates SET status = 'merged', merged_into = ?, resolved_at = ? WHERE id = ?",
+        params![survivor_id, now, id],
+    )?;
+    tx.commit()?;
+    Ok(job)
+}
+
+// Rattache à `survivor_id` tout ce qui référence `duplicate_id`
+// (candidatures, cartes Kanban, temps de trajet, documents générés,
+// résultats de recherches sauvegardées, scores de matching), complète ses
+// champs vides puis supprime le doublon. L'offre renvoyée porte le score de
+// `user_id`. À appeler dans une transaction
+pub fn merge_jobs(conn: &Connection, user_id: i64, survivor_id: i64, duplicate_id: i64) -> Result<Job, AppError> {
+    if survivor_id == duplicate_id {
+        return Err(AppError::Validation("Une offre ne peut pas être fusionnée avec elle-même".to_string()));
+    }
+    for id in [survivor_id, duplicate_id] {
+        if find_job(conn, user_id, id)?.is_none() {
+            return Err(AppError::NotFound(format!("Offre {} introuvable", id)));
+        }
+    }
+
+    conn.execute(
+        "UPDATE jobs SET
+            description = CASE WHEN jobs.description = '' THEN d.description ELSE jobs.description END,
+            requirements = COALESCE(jobs.requirements, d.requirements),
+            url = CASE WHEN jobs.url = '' THEN d.url ELSE jobs.url END,
+            salary_min = COALESCE(jobs.salary_min, d.salary_min),
+            salary_max = COALESCE(jobs.salary_max, d.salary_max),
+            salary_currency = COALESCE(jobs.salary_currency, d.salary_currency),
+            salary_period = COALESCE(jobs.salary_period, d.salary_period),
+            skills = CASE WHEN jobs.skills IN ('', '[]') THEN d.skills ELSE jobs.skills END,
+            valid_through = COALESCE(jobs.valid_through, d.valid_through),
+            latitude = COALESCE(jobs.latitude, d.latitude),
+            longitude = COALESCE(jobs.longitude, d.longitude),
+            updated_at = CURRENT_TIMESTAMP
+         FROM (SELECT * FROM jobs WHERE id = ?1) AS d
+         WHERE jobs.id = ?2",
+        params![duplicate_id, survivor_id],
+    )?;
+
+    merge_profile_rows(conn, survivor_id, duplicate_id)?;
+    conn.execute("UPDATE applications SET job_id = ? WHERE job_id = ?", [survivor_id, duplicate_id])?;
+    conn.execute("UPDATE kanban_cards SET job_id = ? WHERE job_id = ?", [survivor_id, duplicate_id])?;
+    conn.execute("UPDATE reminders SET job_id = ? WHERE job_id = ?", [survivor_id, duplicate_id])?;
+    conn.execute("UPDATE generated_documents SET job_id = ? WHERE job_id = ?", [survivor_id, duplicate_id])?;
+    // Un trajet déjà calculé pour l'offre conservée (même domicile, même mode) prime
+    conn.execute(
+        "UPDATE commute_times SET job_id = ?1 WHERE job_id = ?2 AND NOT EXISTS (
+            SELECT 1 FROM commute_times kept
+            WHERE kept.job_id = ?1 AND kept.home_type = commute_times.home_type
+              AND kept.mode IS commute_times.mode
+         )",
+        [survivor_id, duplicate_id],
+    )?;
+    conn.execute("DELETE FROM commute_times WHERE job_id = ?", [duplicate_id])?;
+    conn.execute(
+        "UPDATE OR IGNORE saved_search_results SET job_id = ? WHERE job_id = ?",
+        [survivor_id, duplicate_id],
+    )?;
+    conn.execute("DELETE FROM saved_search_results WHERE job_id = ?", [duplicate_id])?;
+    // Pour chaque profil, le meilleur des deux scores est conservé
+    conn.execute(
+        "INSERT INTO job_match_scores (user_id, job_id, score, breakdown, computed_at, profile_version)
+         SELECT user_id, ?1, score, json_set(breakdown, '$.job_id', ?1), computed_at, profile_version
+         FROM job_match_scores WHERE job_id = ?2
+         ON CONFLICT(user_id, job_id) DO UPDATE SET
+            score = excluded.score, breakdown = excluded.breakdown, computed_at = excluded.computed_at,
+            profile_version = excluded.profile_version
+         WHERE excluded.score > job_match_scores.score",
+        [survivor_id, duplicate_id],
+    )?;
+    // Les autres paires du doublon seront recalculées avec l'offre conservée
+    conn.execute(
+        "DELETE FROM duplicate_candidates
+         WHERE status != 'merged' AND (job_id = ?1 OR duplicate_job_id = ?1)
+           AND NOT (job_id = MIN(?1, ?2) AND duplicate_job_id = MAX(?1, ?2))",
+        [duplicate_id, survivor_id],
+    )?;
+    conn.execute("DELETE FROM jobs WHERE id = ?", [duplicate_id])?;
+
+    find_job(conn, user_id, survivor_id)?.ok_or_else(|| AppError::NotFound(format!("Offre {} introuvable", survivor_id)))
+}
+
+// Un profil qui suivait les deux offres garderait deux candidatures et deux
+// cartes pour la même : celles du doublon sont fondues dans celles de l'offre
+// conservée (étapes, documents, notes, entretiens) puis supprimées
+fn merge_profile_rows(conn: &Connection, survivor_id: i64, duplicate_id: i64) -> Result<(), AppError> {
+    let applications = {
+        let mut stmt = conn.prepare(
+            "SELECT dup.id, MIN(kept.id) FROM applications dup
+             JOIN applications kept ON kept.user_id = dup.user_id AND kept.job_id = ?1
+             WHERE dup.job_id = ?2
+             GROUP BY dup.id",
+        )?;
+        let pairs = stmt
+            .query_map([survivor_id, duplicate_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        pairs
+    };
+    for (duplicate, kept) in applications {
+        // Une invitation importée des deux côtés n'est gardée qu'une fois
+        conn.execute(
+            "UPDATE OR IGNORE application_stages SET application_id = ? WHERE application_id = ?",
+            [kept, duplicate],
+        )?;
+        for table in ["application_stages", "application_status_history"] {
+            conn.execute(&format!("DELETE FROM {} WHERE application_id = ?", table), [duplicate])?;
+        }
+        for table in ["application_documents", "application_notes"] {
+            let sql = format!("UPDATE {} SET application_id = ? WHERE application_id = ?", table);
+            conn.execute(&sql, [kept, duplicate])?;
+        }
+        conn.execute("DELETE FROM applications WHERE id = ?", [duplicate])?;
+    }
+
+    let cards = {
+        let mut stmt = conn.prepare(
+            "SELECT dup.id, MIN(kept.id), dup.column_id, dup.position FROM kanban_cards dup
+             JOIN kanban_columns dup_column ON dup_column.id = dup.column_id
+             JOIN kanban_cards kept ON kept.job_id = ?1
+             JOIN kanban_columns kept_column ON kept_column.id = kept.column_id
+             WHERE dup.job_id = ?2 AND kept_column.user_id = dup_column.user_id
+             GROUP BY dup.id",
+        )?;
+        let cards = stmt
+            .query_map([survivor_id, duplicate_id], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?, row.get::<_, i64>(3)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        cards
+    };
+    for (duplicate, kept, column_id, position) in cards {
+        conn.execute("UPDATE interviews SET card_id = ? WHERE card_id = ?", [kept, duplicate])?;
+        conn.execute("DELETE FROM kanban_cards WHERE id = ?", [duplicate])?;
+        // Les cartes suivantes de la colonne remontent d'un rang
+        conn.execute(
+            "UPDATE kanban_cards SET position = position - 1 WHERE column_id = ? AND position > ?",
+            [column_id, position],
+        )?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn detect_duplicates(
+    state: State<'_, AppState>,
+    threshold: Option<f64>,
+) -> Result<DuplicateDetectionStats, String> {
+    let threshold = threshold.unwrap_or(DEFAULT_DUPLICATE_THRESHOLD);
+    state
+        .db
+        .call(move |conn| find_duplicate_candidates(conn, threshold, &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_duplicates(
+    state: State<'_, AppState>,
+    status: Option<DuplicateStatus>,
+) -> Result<Vec<DuplicateCandidate>, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| load_duplicate_candidates(conn, user_id, status))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn merge_duplicates(
+    state: State<'_, AppState>,
+    id: i64,
+    survivor_id: Option<i64>,
+) -> Result<Job, String> {
+    let user_id = state.user_id();
+    let job = state
+        .db
+        .call(move |conn| merge_duplicate_candidate(conn, user_id, id, survivor_id, &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())?;
+    state.operations.flush_cache();
+    Ok(job)
+}
+
+#[tauri::command]
+pub async fn ignore_duplicate(state: State<'_, AppState>, id: i64) -> Result<DuplicateCandidate, String> {
+    state
+        .db
+        .call(move |conn| ignore_duplicate_candidate(conn, id, &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,101 @@
+use crate::{
+    commands::search::{build_filtered_query, resolve_origin, FilteredQuery, SearchCriteria, SortKey},
+    error::AppError,
+    AppState,
+};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+// Nombre maximal d'entreprises listées dans la barre de filtres
+const COMPANY_FACET_LIMIT: usize = 20;
+
+// Tranches de salaire annuel (borne haute exclue), appliquées au salaire
+// maximal affiché ou à défaut au minimum
+const SALARY_BUCKETS: &str = "CASE
+        WHEN COALESCE(salary_max, salary_min) IS NULL THEN 'unknown'
+        WHEN COALESCE(salary_max, salary_min) < 30000 THEN '0-30k'
+        WHEN COALESCE(salary_max, salary_min) < 45000 THEN '30k-45k'
+        WHEN COALESCE(salary_max, salary_min) < 60000 THEN '45k-60k'
+        WHEN COALESCE(salary_max, salary_min) < 80000 THEN '60k-80k'
+        ELSE '80k+'
+    END";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FacetValue {
+    pub value: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobFacets {
+    pub total: i64,
+    pub job_type: Vec<FacetValue>,
+    pub experience_level: Vec<FacetValue>,
+    pub source: Vec<FacetValue>,
+    pub remote: Vec<FacetValue>,
+    pub company: Vec<FacetValue>,
+    pub salary_bucket: Vec<FacetValue>,
+}
+
+// Agrège les offres correspondant aux critères ; la requête filtrée est celle
+// de `find_jobs`, les totaux correspondent donc toujours à la liste affichée
+pub fn find_job_facets(conn: &Connection, user_id: i64, criteria: &SearchCriteria) -> Result<JobFacets, AppError> {
+    let origin = resolve_origin(conn, user_id, criteria)?;
+    let FilteredQuery { sql, params, .. } = build_filtered_query(criteria, SortKey::Date, origin.as_ref());
+
+    let count = |expression: &str, limit: Option<usize>| -> Result<Vec<FacetValue>, AppError> {
+        // Les valeurs vides (NULLIF) ne forment pas de facette
+        let mut query = format!(
+            "SELECT value, COUNT(*) AS count FROM (SELECT {} AS value FROM ({}))
+             WHERE value IS NOT NULL GROUP BY value ORDER BY count DESC, value",
+            expression, sql
+        );
+        if let Some(limit) = limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut stmt = conn.prepare(&query)?;
+        let values = stmt
+            .query_map(
+                rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+                |row| {
+                    Ok(FacetValue {
+                        value: row.get(0)?,
+                        count: row.get(1)?,
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(values)
+    };
+
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM ({})", sql),
+        rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+        |row| row.get(0),
+    )?;
+
+    Ok(JobFacets {
+        total,
+        job_type: count("NULLIF(job_type, '')", None)?,
+        experience_level: count("NULLIF(experience_level, '')", None)?,
+        source: count("NULLIF(source, '')", None)?,
+        remote: count("CASE WHEN remote THEN 'true' ELSE 'false' END", None)?,
+        company: count("NULLIF(company, '')", Some(COMPANY_FACET_LIMIT))?,
+        salary_bucket: count(SALARY_BUCKETS, None)?,
+    })
+}
+
+#[tauri::command]
+pub async fn search_job_facets(
+    state: State<'_, AppState>,
+    criteria: SearchCriteria,
+) -> Result<JobFacets, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| find_job_facets(conn, user_id, &criteria))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,69 @@
+use crate::{
+    ingest::{job_page::import_job_page, runner::run_source, source_by_name, source_names, FeedSource, IngestQuery},
+    models::{Job, JobSearchStats},
+    AppState,
+};
+use tauri::State;
+
+#[tauri::command]
+pub async fn list_job_sources() -> Result<Vec<String>, String> {
+    Ok(source_names().into_iter().map(str::to_string).collect())
+}
+
+// Collecte les offres d'une source et les enregistre dans `jobs`
+#[tauri::command]
+pub async fn ingest_jobs(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    source: String,
+    query: IngestQuery,
+) -> Result<JobSearchStats, String> {
+    let source = source_by_name(&source).map_err(|e| e.to_string())?;
+    let stats = run_source(&state.db, source.as_ref(), &query).await.map_err(|e| e.to_string())?;
+    if stats.new > 0 || stats.updated > 0 {
+        state.operations.flush_cache();
+        rescore_imported_jobs(app, &state);
+    }
+    Ok(stats)
+}
+
+// Importe les offres d'un flux RSS, Atom ou JSON Feed (URL ou fichier local)
+#[tauri::command]
+pub async fn import_job_feed(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    name: String,
+    url: String,
+) -> Result<JobSearchStats, String> {
+    let source = FeedSource::new(&name, &url).map_err(|e| e.to_string())?;
+    let stats = run_source(&state.db, &source, &IngestQuery::default())
+        .await
+        .map_err(|e| e.to_string())?;
+    if stats.new > 0 || stats.updated > 0 {
+        state.operations.flush_cache();
+        rescore_imported_jobs(app, &state);
+    }
+    Ok(stats)
+}
+
+// Importe l'offre d'une page HTML enregistrée depuis le navigateur ; elle est
+// notée pour le profil actif avant d'être renvoyée
+#[tauri::command]
+pub async fn import_job_from_html(state: State<'_, AppState>, path: String) -> Result<Job, String> {
+    let html = tokio::fs::read_to_string(&path).await.map_err(|e| e.to_string())?;
+    let today = chrono::Local::now().date_naive();
+    let user_id = state.user_id();
+    let job = state
+        .db
+        .call(move |conn| import_job_page(conn, user_id, &html, None, today))
+        .await
+        .map_err(|e| e.to_string())?;
+    state.operations.flush_cache();
+    Ok(job)
+}
+
+// Les offres nouvelles ou modifiées n'ont pas encore de score à jour pour le
+// profil actif : le recalcul ne reprend que les offres périmées
+fn rescore_imported_jobs(app: tauri::AppHandle, state: &AppState) {
+    state.rescorer.start(app, state.db.clone(), state.operations.clone(), state.user_id());
+}
@@ -0,0 +1,464 @@
+use crate::{
+    database::operations::{job_from_row, scored_job_columns, SCORED_JOBS_FROM},
+    error::AppError,
+    models::{Interview, Job, KanbanCard, KanbanColumn},
+    AppState,
+};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateKanbanColumnRequest {
+    pub name: String,
+    pub color: Option<String>,
+    pub limit: Option<i32>,
+}
+
+// Champs absents laissés inchangés ; une limite à 0 retire la limite
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateKanbanColumnRequest {
+    pub name: Option<String>,
+    pub color: Option<String>,
+    pub limit: Option<i32>,
+}
+
+const COLUMN_COLUMNS: &str = "id, name, position, color, card_limit";
+
+// Colonne sans ses cartes, chargées à part
+fn column_from_row(row: &Row) -> rusqlite::Result<KanbanColumn> {
+    Ok(KanbanColumn {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        position: row.get(2)?,
+        cards: Vec::new(),
+        color: row.get(3)?,
+        limit: row.get(4)?,
+    })
+}
+
+fn validate_name(name: &str) -> Result<&str, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Le nom de la colonne est obligatoire".to_string()));
+    }
+    Ok(name)
+}
+
+// `#rgb` ou `#rrggbb`
+fn validate_color(color: &str) -> Result<&str, AppError> {
+    let color = color.trim();
+    let valid = color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()));
+    if !valid {
+        return Err(AppError::Validation(format!("Couleur invalide : {}", color)));
+    }
+    Ok(color)
+}
+
+fn validate_limit(limit: i32) -> Result<Option<i32>, AppError> {
+    match limit {
+        0 => Ok(None),
+        limit if limit > 0 => Ok(Some(limit)),
+        _ => Err(AppError::Validation("La limite de cartes doit être positive".to_string())),
+    }
+}
+
+// Comparaison en Rust : `lower()` de SQLite ignore les lettres accentuées
+fn ensure_unique_name(conn: &Connection, user_id: i64, name: &str, except: Option<i64>) -> Result<(), AppError> {
+    let mut stmt = conn.prepare("SELECT name FROM kanban_columns WHERE user_id = ? AND id IS NOT ?")?;
+    let names = stmt
+        .query_map(params![user_id, except], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    let lowered = name.to_lowercase();
+    if names.iter().any(|existing| existing.trim().to_lowercase() == lowered) {
+        return Err(AppError::Validation(format!("Une colonne « {} » existe déjà", name)));
+    }
+    Ok(())
+}
+
+fn load_column(conn: &Connection, user_id: i64, column_id: i64) -> Result<KanbanColumn, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM kanban_columns WHERE id = ? AND user_id = ?", COLUMN_COLUMNS),
+        params![column_id, user_id],
+        column_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Colonne {} introuvable", column_id)))
+}
+
+fn card_count(conn: &Connection, column_id: i64) -> Result<i64, AppError> {
+    let count = conn.query_row("SELECT COUNT(*) FROM kanban_cards WHERE column_id = ?", [column_id], |row| row.get(0))?;
+    Ok(count)
+}
+
+// Cartes d'une colonne dans l'ordre d'affichage
+fn ordered_card_ids(conn: &Connection, column_id: i64) -> Result<Vec<i64>, AppError> {
+    let mut stmt = conn.prepare("SELECT id FROM kanban_cards WHERE column_id = ? ORDER BY position, id")?;
+    let ids = stmt
+        .query_map([column_id], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ids)
+}
+
+fn renumber_cards(conn: &Connection, column_id: i64, card_ids: &[i64]) -> Result<(), AppError> {
+    let mut stmt = conn.prepare(
+        "UPDATE kanban_cards SET column_id = ?, position = ?, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ? AND (column_id != ? OR position != ?)",
+    )?;
+    for (position, card_id) in card_ids.iter().enumerate() {
+        stmt.execute(params![column_id, position as i64, card_id, column_id, position as i64])?;
+    }
+    Ok(())
+}
+
+fn renumber_columns(conn: &Connection, column_ids: &[i64]) -> Result<(), AppError> {
+    let mut stmt = conn.prepare("UPDATE kanban_columns SET position = ? WHERE id = ?")?;
+    for (position, column_id) in column_ids.iter().enumerate() {
+        stmt.execute(params![position as i64, column_id])?;
+    }
+    Ok(())
+}
+
+fn user_column_ids(conn: &Connection, user_id: i64) -> Result<Vec<i64>, AppError> {
+    let mut stmt = conn.prepare("SELECT id FROM kanban_columns WHERE user_id = ? ORDER BY position, id")?;
+    let ids = stmt
+        .query_map([user_id], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ids)
+}
+
+// Tableau du profil : colonnes dans l'ordre, chacune avec ses cartes et
+// l'offre de chaque carte (score de matching du profil)
+pub fn load_kanban_board(conn: &Connection, user_id: i64) -> Result<Vec<KanbanColumn>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM kanban_columns WHERE user_id = ? ORDER BY position, id",
+        COLUMN_COLUMNS
+    ))?;
+    let mut columns = stmt
+        .query_map([user_id], column_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM {}
+         WHERE jobs.id IN (SELECT kanban_cards.job_id FROM kanban_cards
+                           JOIN kanban_columns ON kanban_columns.id = kanban_cards.column_id
+                           WHERE kanban_columns.user_id = ?)",
+        scored_job_columns(),
+        SCORED_JOBS_FROM
+    ))?;
+    let jobs: HashMap<i64, Job> = stmt
+        .query_map(params![user_id, user_id], job_from_row)?
+        .map(|job| job.map(|job| (job.id, job)))
+        .collect::<Result<_, _>>()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT interviews.card_id, interviews.date, interviews.interview_type, interviews.notes, interviews.outcome
+         FROM interviews
+         JOIN kanban_cards ON kanban_cards.id = interviews.card_id
+         JOIN kanban_columns ON kanban_columns.id = kanban_cards.column_id
+         WHERE kanban_columns.user_id = ?
+         ORDER BY interviews.date, interviews.id",
+    )?;
+    let mut interviews: HashMap<i64, Vec<Interview>> = HashMap::new();
+    for row in stmt.query_map([user_id], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            Interview {
+                date: row.get(1)?,
+                interview_type: row.get(2)?,
+                notes: row.get(3)?,
+                outcome: row.get(4)?,
+            },
+        ))
+    })? {
+        let (card_id, interview) = row?;
+        interviews.entry(card_id).or_default().push(interview);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT kanban_cards.id, kanban_cards.job_id, kanban_cards.column_id, kanban_cards.position,
+                kanban_cards.notes, kanban_cards.applied_at, kanban_cards.follow_up_date
+         FROM kanban_cards
+         JOIN kanban_columns ON kanban_columns.id = kanban_cards.column_id
+         WHERE kanban_columns.user_id = ?
+         ORDER BY kanban_cards.position, kanban_cards.id",
+    )?;
+    let rows = stmt
+        .query_map([user_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i32>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<String>>(6)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut cards_by_column: HashMap<i64, Vec<KanbanCard>> = HashMap::new();
+    for (id, job_id, column_id, position, notes, applied_at, follow_up_date) in rows {
+        // Une carte dont l'offre a disparu n'a rien à afficher
+        let Some(job) = jobs.get(&job_id) else {
+            continue;
+        };
+        cards_by_column.entry(column_id).or_default().push(KanbanCard {
+            id,
+            job_id,
+            column_id,
+            position,
+            job: job.clone(),
+            notes,
+            applied_at,
+            follow_up_date,
+            documents: None,
+            interviews: Some(interviews.remove(&id).unwrap_or_default()),
+        });
+    }
+    for column in &mut columns {
+        column.cards = cards_by_column.remove(&column.id).unwrap_or_default();
+    }
+
+    Ok(columns)
+}
+
+// Déplace la carte à `position` (à partir de 0) dans la colonne cible et
+// renumérote les deux colonnes, le tout dans une même transaction. Une
+// position au-delà de la fin place la carte en dernier
+pub fn relocate_card(
+    conn: &Connection,
+    user_id: i64,
+    card_id: i64,
+    to_column_id: i64,
+    position: i32,
+) -> Result<(), AppError> {
+    if position < 0 {
+        return Err(AppError::Validation("La position d'une carte ne peut pas être négative".to_string()));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let from_column_id: i64 = tx
+        .query_row(
+            "SELECT kanban_cards.column_id FROM kanban_cards
+             JOIN kanban_columns ON kanban_columns.id = kanban_cards.column_id
+             WHERE kanban_cards.id = ? AND kanban_columns.user_id = ?",
+            params![card_id, user_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Carte {} introuvable", card_id)))?;
+    let target = load_column(&tx, user_id, to_column_id)?;
+
+    if from_column_id != to_column_id {
+        if let Some(limit) = target.limit {
+            if card_count(&tx, to_column_id)? >= i64::from(limit) {
+                return Err(AppError::Validation(format!(
+                    "La colonne « {} » est pleine ({} cartes au maximum)",
+                    target.name, limit
+                )));
+            }
+        }
+        let mut source = ordered_card_ids(&tx, from_column_id)?;
+        source.retain(|&id| id != card_id);
+        renumber_cards(&tx, from_column_id, &source)?;
+    }
+
+    let mut cards = ordered_card_ids(&tx, to_column_id)?;
+    cards.retain(|&id| id != card_id);
+    let index = (position as usize).min(cards.len());
+    cards.insert(index, card_id);
+    renumber_cards(&tx, to_column_id, &cards)?;
+    tx.commit()?;
+
+    Ok(())
+}
+
+pub fn insert_kanban_column(
+    conn: &Connection,
+    user_id: i64,
+    request: &CreateKanbanColumnRequest,
+) -> Result<KanbanColumn, AppError> {
+    let name = validate_name(&request.name)?;
+    let color = request.color.as_deref().map(validate_color).transpose()?;
+    let limit = match request.limit {
+        Some(limit) => validate_limit(limit)?,
+        None => None,
+    };
+    ensure_unique_name(conn, user_id, name, None)?;
+
+    // Ajoutée à droite des colonnes existantes
+    let column = conn.query_row(
+        &format!(
+            "INSERT INTO kanban_columns (user_id, name, position, color, card_limit)
+             VALUES (?1, ?2, (SELECT COUNT(*) FROM kanban_columns WHERE user_id = ?1), ?3, ?4)
+             RETURNING {}",
+            COLUMN_COLUMNS
+        ),
+        params![user_id, name, color, limit],
+        column_from_row,
+    )?;
+    Ok(column)
+}
+
+pub fn modify_kanban_column(
+    conn: &Connection,
+    user_id: i64,
+    column_id: i64,
+    request: UpdateKanbanColumnRequest,
+) -> Result<KanbanColumn, AppError> {
+    let current = load_column(conn, user_id, column_id)?;
+    let name = match &request.name {
+        Some(name) => {
+            let name = validate_name(name)?;
+            ensure_unique_name(conn, user_id, name, Some(column_id))?;
+            name.to_string()
+        }
+        None => current.name,
+    };
+    let color = match &request.color {
+        Some(color) => Some(validate_color(color)?.to_string()),
+        None => current.color,
+    };
+    let limit = match request.limit {
+        Some(limit) => validate_limit(limit)?,
+        None => current.limit,
+    };
+    if let Some(limit) = limit {
+        let count = card_count(conn, column_id)?;
+        if count > i64::from(limit) {
+            return Err(AppError::Validation(format!(
+                "La colonne « {} » contient déjà {} cartes, au-delà de la limite de {}",
+                name, count, limit
+            )));
+        }
+    }
+
+    conn.execute(
+        "UPDATE kanban_columns SET name = ?, color = ?, card_limit = ? WHERE id = ? AND user_id = ?",
+        params![name, color, limit, column_id, user_id],
+    )?;
+    load_column(conn, user_id, column_id)
+}
+
+// `column_ids` donne le nouvel ordre de toutes les colonnes du profil
+pub fn reorder_user_columns(conn: &Connection, user_id: i64, column_ids: &[i64]) -> Result<(), AppError> {
+    let current = user_column_ids(conn, user_id)?;
+    let requested: HashSet<i64> = column_ids.iter().copied().collect();
+    if requested.len() != column_ids.len()
+        || current.len() != column_ids.len()
+        || !current.iter().all(|id| requested.contains(id))
+    {
+        return Err(AppError::Validation(
+            "Le nouvel ordre doit reprendre chaque colonne du tableau une seule fois".to_string(),
+        ));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    renumber_columns(&tx, column_ids)?;
+    tx.commit()?;
+    Ok(())
+}
+
+// Seule une colonne vide peut être supprimée
+pub fn remove_kanban_column(conn: &Connection, user_id: i64, column_id: i64) -> Result<bool, AppError> {
+    let column = match load_column(conn, user_id, column_id) {
+        Ok(column) => column,
+        Err(AppError::NotFound(_)) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    if card_count(conn, column_id)? > 0 {
+        return Err(AppError::Validation(format!(
+            "La colonne « {} » contient encore des cartes",
+            column.name
+        )));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM kanban_columns WHERE id = ?", [column_id])?;
+    renumber_columns(&tx, &user_column_ids(&tx, user_id)?)?;
+    tx.commit()?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn get_kanban_columns(state: State<'_, AppState>) -> Result<Vec<KanbanColumn>, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| load_kanban_board(conn, user_id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn move_kanban_card(
+    state: State<'_, AppState>,
+    card_id: i64,
+    to_column_id: i64,
+    position: i32,
+) -> Result<bool, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| relocate_card(conn, user_id, card_id, to_column_id, position))
+        .await
+        .map(|_| true)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_kanban_column(
+    state: State<'_, AppState>,
+    request: CreateKanbanColumnRequest,
+) -> Result<KanbanColumn, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| insert_kanban_column(conn, user_id, &request))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_kanban_column(
+    state: State<'_, AppState>,
+    column_id: i64,
+    request: UpdateKanbanColumnRequest,
+) -> Result<KanbanColumn, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| modify_kanban_column(conn, user_id, column_id, request))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn reorder_kanban_columns(
+    state: State<'_, AppState>,
+    column_ids: Vec<i64>,
+) -> Result<Vec<KanbanColumn>, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| {
+            reorder_user_columns(conn, user_id, &column_ids)?;
+            load_kanban_board(conn, user_id)
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_kanban_column(state: State<'_, AppState>, column_id: i64) -> Result<bool, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| remove_kanban_column(conn, user_id, column_id))
+        .await
+        .map_err(|e| e.to_string())
+}
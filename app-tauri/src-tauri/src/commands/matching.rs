@@ -0,0 +1,70 @@
+use crate::{
+    matching::{load_match_explanation, load_match_profile, profile_version, score_all_jobs, stale_job_ids, MatchExplanation},
+    time::now_timestamp,
+    AppState,
+};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RescoringStatus {
+    pub running: bool,
+    pub profile_version: String,
+    // Offres dont le score a été calculé avec une autre version du profil
+    pub stale: usize,
+}
+
+// Recalcule `matching_score` pour toutes les offres
+#[tauri::command]
+pub async fn compute_matching_scores(state: State<'_, AppState>) -> Result<usize, String> {
+    let user_id = state.user_id();
+    let scored = state
+        .db
+        .call(move |conn| score_all_jobs(conn, user_id, &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())?;
+    state.operations.flush_cache();
+    Ok(scored)
+}
+
+// Détail du score d'une offre, facteur par facteur
+#[tauri::command]
+pub async fn explain_match(state: State<'_, AppState>, job_id: i64) -> Result<MatchExplanation, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| load_match_explanation(conn, user_id, job_id, &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// Relance en arrière-plan le calcul des scores périmés ; la progression est
+// publiée par l'événement `matching-rescore-progress`
+#[tauri::command]
+pub async fn start_rescoring(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    state.rescorer.start(app, state.db.clone(), state.operations.clone(), state.user_id());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn cancel_rescoring(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.rescorer.cancel())
+}
+
+#[tauri::command]
+pub async fn get_rescoring_status(state: State<'_, AppState>) -> Result<RescoringStatus, String> {
+    let running = state.rescorer.is_running();
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| {
+            let version = profile_version(&load_match_profile(conn, user_id)?);
+            Ok(RescoringStatus {
+                running,
+                stale: stale_job_ids(conn, user_id, &version)?.len(),
+                profile_version: version,
+            })
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,9 +1,26 @@
 use crate::models::*;
+use crate::AppState;
 use tauri::State;
-use std::sync::Mutex;
-use std::path::PathBuf;
+use std::path::Path;
+use std::process::Command;
 use serde::{Deserialize, Serialize};
 
+pub mod applications;
+pub mod calendar;
+pub mod cv;
+pub mod database;
+pub mod duplicates;
+pub mod facets;
+pub mod ingest;
+pub mod kanban;
+pub mod matching;
+pub mod profile;
+pub mod reminders;
+pub mod saved_searches;
+pub mod search;
+pub mod search_preferences;
+pub mod skills;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SearchCriteria {
     pub keywords: Vec<String>,
@@ -16,11 +33,48 @@ pub struct SearchCriteria {
     pub date_posted: Option<String>,
 }
 
-// Structure pour stocker l'état de l'application
-#[allow(dead_code)]
-pub struct AppState {
-    pub python_path: Mutex<String>,
-    pub app_path: Mutex<String>,
+// Fonction pour exécuter une commande Python
+#[tauri::command]
+pub async fn run_python_command(
+    state: State<'_, AppState>,
+    script: &str,
+    args: Vec<String>,
+) -> Result<String, String> {
+    let python_path = state.python_path.lock().unwrap().clone();
+    let app_path = state.app_path.lock().unwrap().clone();
+
+    let output = Command::new(&python_path)
+        .current_dir(&app_path)
+        .arg(script)
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn init_app(
+    state: State<'_, AppState>,
+    python_path: String,
+    app_path: String,
+) -> Result<(), String> {
+    if !Path::new(&python_path).exists() {
+        return Err("Python path does not exist".to_string());
+    }
+
+    if !Path::new(&app_path).exists() {
+        return Err("App path does not exist".to_string());
+    }
+
+    *state.python_path.lock().unwrap() = python_path;
+    *state.app_path.lock().unwrap() = app_path;
+
+    Ok(())
 }
 
 // Commande pour rechercher des offres d'emploi
@@ -38,87 +92,33 @@ pub async fn search_jobs(
 // Commande pour obtenir le profil utilisateur
 #[tauri::command]
 pub async fn get_user_profile(
-    _state: State<'_, AppState>,
+    state: State<'_, AppState>,
 ) -> Result<UserProfile, String> {
-    // Implémentation de la récupération du profil utilisateur
-    // TODO: Implémenter la logique de récupération
-    Ok(UserProfile {
-        id: 1,
-        name: "Test User".to_string(),
-        email: Some("test@example.com".to_string()),
-        phone: None,
-        locations: UserLocations {
-            primary: "Paris".to_string(),
-            secondary: None,
-            coordinates: None,
-        },
-        cv: CVInfo {
-            path: PathBuf::from("test.pdf"),
-            last_updated: "2024-04-14".to_string(),
-            skills: None,
-            experience_years: None,
-            education: None,
-            certifications: None,
-        },
-        preferences: None,
-        job_preferences: None,
-    })
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| profile::load_user_profile(conn, user_id))
+        .await
+        .map_err(|e| e.to_string())
 }
 
 // Commande pour mettre à jour le profil utilisateur
 #[tauri::command]
 pub async fn update_user_profile(
-    _state: State<'_, AppState>,
-    _profile: UserProfile,
-) -> Result<(), String> {
-    // Implémentation de la mise à jour du profil utilisateur
-    // TODO: Implémenter la logique de mise à jour
-    Ok(())
-}
-
-// Commande pour obtenir les colonnes du Kanban
-#[tauri::command]
-pub async fn get_kanban_columns(
-    _state: State<'_, AppState>,
-) -> Result<Vec<KanbanColumn>, String> {
-    // Implémentation de la récupération des colonnes du Kanban
-    // TODO: Implémenter la logique de récupération
-    Ok(vec![])
-}
-
-// Commande pour déplacer une carte dans le Kanban
-#[tauri::command]
-pub async fn move_kanban_card(
-    _state: State<'_, AppState>,
-    _card_id: i64,
-    _to_column_id: i64,
-    _position: i32,
-) -> Result<bool, String> {
-    // Implémentation du déplacement de carte
-    // TODO: Implémenter la logique de déplacement
-    Ok(true)
-}
-
-// Commande pour obtenir les préférences de recherche
-#[tauri::command]
-pub async fn get_search_preferences(
-    _state: State<'_, AppState>,
-) -> Result<Vec<SearchPreference>, String> {
-    // Implémentation de la récupération des préférences de recherche
-    // TODO: Implémenter la logique de récupération
-    Ok(vec![])
-}
-
-// Commande pour mettre à jour les préférences de recherche
-#[allow(dead_code)]
-#[tauri::command]
-pub async fn update_search_preferences(
-    _state: State<'_, AppState>,
-    _preferences: SearchPreference,
-) -> Result<bool, String> {
-    // Implémentation de la mise à jour des préférences de recherche
-    // TODO: Implémenter la logique de mise à jour
-    Ok(true)
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    profile: UserProfile,
+) -> Result<UserProfile, String> {
+    let user_id = state.user_id();
+    let saved = state
+        .db
+        .call(move |conn| profile::save_user_profile(conn, user_id, &profile))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Les scores de matching dépendent du profil
+    state.rescorer.start(app, state.db.clone(), state.operations.clone(), user_id);
+    Ok(saved)
 }
 
 // Commande pour obtenir les fournisseurs LLM
@@ -0,0 +1,282 @@
+use crate::{
+    error::{validate_email, AppError, Validate},
+    models::{CVInfo, Coordinates, LocationCoordinates, UserLocations, UserProfile},
+    skills::SkillTaxonomy,
+    time::now_timestamp,
+    AppState,
+};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::State;
+
+const PROFILE_COLUMNS: &str = "name, email, phone, primary_home, secondary_home, cv_path, cv_last_updated,
+    primary_latitude, primary_longitude, secondary_latitude, secondary_longitude,
+    skills, experience_years, education, certifications, preferences, job_preferences";
+
+fn json_column<T: DeserializeOwned>(row: &Row, index: usize) -> rusqlite::Result<Option<T>> {
+    let json: Option<String> = row.get(index)?;
+    json.map(|json| serde_json::from_str(&json))
+        .transpose()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+pub(crate) fn to_json<T: Serialize>(value: &Option<T>) -> Result<Option<String>, AppError> {
+    value
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+fn coordinates(lat: Option<f64>, lng: Option<f64>) -> Option<Coordinates> {
+    Some(Coordinates { lat: lat?, lng: lng? })
+}
+
+fn profile_from_row(user_id: i64, row: &Row) -> rusqlite::Result<UserProfile> {
+    // Les coordonnées ne sont renvoyées que si elles ont été enregistrées,
+    // pas celles que le géocodeur déduirait de l'adresse
+    let primary = coordinates(row.get(7)?, row.get(8)?);
+    let secondary = coordinates(row.get(9)?, row.get(10)?);
+    // Une liste de compétences vide équivaut à l'absence de compétences
+    let skills = json_column::<Vec<String>>(row, 11)?.filter(|skills| !skills.is_empty());
+
+    Ok(UserProfile {
+        id: user_id,
+        name: row.get(0)?,
+        email: row.get(1)?,
+        phone: row.get(2)?,
+        locations: UserLocations {
+            primary: row.get(3)?,
+            secondary: row.get(4)?,
+            coordinates: primary.map(|primary| LocationCoordinates { primary, secondary }),
+        },
+        cv: CVInfo {
+            path: PathBuf::from(row.get::<_, Option<String>>(5)?.unwrap_or_default()),
+            last_updated: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+            skills,
+            experience_years: row.get(12)?,
+            education: json_column(row, 13)?,
+            certifications: json_column(row, 14)?,
+        },
+        preferences: json_column(row, 15)?,
+        job_preferences: json_column(row, 16)?,
+    })
+}
+
+pub fn load_user_profile(conn: &Connection, user_id: i64) -> Result<UserProfile, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM user_profiles WHERE user_id = ?", PROFILE_COLUMNS),
+        [user_id],
+        |row| profile_from_row(user_id, row),
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Profil de l'utilisateur {} introuvable", user_id)))
+}
+
+// Enregistre le profil complet : chaque appel remplace le précédent, les
+// champs absents sont donc effacés. Renvoie le profil tel que relu en base
+pub fn save_user_profile(conn: &Connection, user_id: i64, profile: &UserProfile) -> Result<UserProfile, AppError> {
+    profile.validate()?;
+
+    let coordinates = profile.locations.coordinates.as_ref();
+    let primary = coordinates.map(|coordinates| &coordinates.primary);
+    let secondary = coordinates.and_then(|coordinates| coordinates.secondary.as_ref());
+    // Compétences enregistrées sous leur nom canonique (« JS » → « JavaScript »)
+    let skills = SkillTaxonomy::bundled().canonicalize(profile.cv.skills.as_deref().unwrap_or_default());
+    let skills = serde_json::to_string(&skills).map_err(|e| AppError::Internal(e.to_string()))?;
+    let cv_path = profile.cv.path.to_string_lossy();
+
+    conn.execute(
+        &format!(
+            "INSERT INTO user_profiles (user_id, {}, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)
+             ON CONFLICT(user_id) DO UPDATE SET
+                name = excluded.name, email = excluded.email, phone = excluded.phone,
+                primary_home = excluded.primary_home, secondary_home = excluded.secondary_home,
+                cv_path = excluded.cv_path, cv_last_updated = excluded.cv_last_updated,
+                primary_latitude = excluded.primary_latitude, primary_longitude = excluded.primary_longitude,
+                secondary_latitude = excluded.secondary_latitude, secondary_longitude = excluded.secondary_longitude,
+                skills = excluded.skills, experience_years = excluded.experience_years,
+                education = excluded.education, certifications = excluded.certifications,
+                preferences = excluded.preferences, job_preferences = excluded.job_preferences,
+                updated_at = excluded.updated_at",
+            PROFILE_COLUMNS
+        ),
+        params![
+            user_id,
+            profile.name.trim(),
+            profile.email,
+            profile.phone,
+            profile.locations.primary.trim(),
+            profile.locations.secondary,
+            (!cv_path.is_empty()).then_some(cv_path.as_ref()),
+            (!profile.cv.last_updated.is_empty()).then_some(profile.cv.last_updated.as_str()),
+            primary.map(|c| c.lat),
+            primary.map(|c| c.lng),
+            secondary.map(|c| c.lat),
+            secondary.map(|c| c.lng),
+            skills,
+            profile.cv.experience_years,
+            to_json(&profile.cv.education)?,
+            to_json(&profile.cv.certifications)?,
+            to_json(&profile.preferences)?,
+            to_json(&profile.job_preferences)?,
+            now_timestamp(),
+        ],
+    )?;
+
+    load_user_profile(conn, user_id)
+}
+
+// Utilisateur local créé par la migration initiale, actif tant qu'aucun
+// autre profil n'a été sélectionné
+pub const LOCAL_USER_ID: i64 = 1;
+
+// Entrée du sélecteur de profils : chaque personne partageant la machine a
+// son utilisateur, ses candidatures, recherches, CV et scores de matching
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSummary {
+    pub user_id: i64,
+    pub name: String,
+    pub email: String,
+    // Faux tant que le profil détaillé n'a pas été enregistré
+    pub has_profile: bool,
+    pub last_active_at: Option<String>,
+}
+
+const PROFILE_SUMMARY_SELECT: &str = "SELECT users.id, COALESCE(user_profiles.name, users.name, users.email),
+        users.email, user_profiles.id IS NOT NULL, users.last_active_at
+    FROM users LEFT JOIN user_profiles ON user_profiles.user_id = users.id";
+
+fn profile_summary_from_row(row: &Row) -> rusqlite::Result<ProfileSummary> {
+    Ok(ProfileSummary {
+        user_id: row.get(0)?,
+        name: row.get(1)?,
+        email: row.get(2)?,
+        has_profile: row.get(3)?,
+        last_active_at: row.get(4)?,
+    })
+}
+
+pub fn list_profile_summaries(conn: &Connection) -> Result<Vec<ProfileSummary>, AppError> {
+    let mut stmt = conn.prepare(&format!("{} ORDER BY users.id", PROFILE_SUMMARY_SELECT))?;
+    let profiles = stmt
+        .query_map([], profile_summary_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(profiles)
+}
+
+pub fn load_profile_summary(conn: &Connection, user_id: i64) -> Result<ProfileSummary, AppError> {
+    conn.query_row(
+        &format!("{} WHERE users.id = ?", PROFILE_SUMMARY_SELECT),
+        [user_id],
+        profile_summary_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Profil {} introuvable", user_id)))
+}
+
+// Crée l'utilisateur d'un nouveau profil. Le profil détaillé est ensuite
+// renseigné avec `save_user_profile` une fois ce profil actif
+pub fn create_profile_user(conn: &Connection, name: &str, email: Option<&str>) -> Result<ProfileSummary, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Le nom du profil est requis".to_string()));
+    }
+    let email = match email.map(str::trim).filter(|email| !email.is_empty()) {
+        Some(email) => {
+            validate_email(email)?;
+            email.to_string()
+        }
+        // L'email est unique et obligatoire : adresse locale par défaut
+        None => {
+            let next_id: i64 = conn.query_row("SELECT COALESCE(MAX(id), 0) + 1 FROM users", [], |row| row.get(0))?;
+            format!("profil-{}@localhost", next_id)
+        }
+    };
+
+    let taken: bool = conn.query_row("SELECT EXISTS(SELECT 1 FROM users WHERE email = ?)", [&email], |row| row.get(0))?;
+    if taken {
+        return Err(AppError::Validation(format!("L'adresse {} est déjà utilisée par un autre profil", email)));
+    }
+
+    conn.execute("INSERT INTO users (email, name) VALUES (?, ?)", params![email, name])?;
+    load_profile_summary(conn, conn.last_insert_rowid())
+}
+
+// Marque le profil comme actif ; c'est lui qui sera rouvert au prochain démarrage
+pub fn activate_profile(conn: &Connection, user_id: i64, now: &str) -> Result<ProfileSummary, AppError> {
+    let updated = conn.execute("UPDATE users SET last_active_at = ? WHERE id = ?", params![now, user_id])?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Profil {} introuvable", user_id)));
+    }
+    load_profile_summary(conn, user_id)
+}
+
+// Profil à ouvrir au démarrage : le dernier activé, sinon l'utilisateur local
+pub fn last_active_user_id(conn: &Connection) -> Result<i64, AppError> {
+    Ok(conn
+        .query_row(
+            "SELECT id FROM users WHERE last_active_at IS NOT NULL ORDER BY last_active_at DESC, id DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?
+        .unwrap_or(LOCAL_USER_ID))
+}
+
+#[tauri::command]
+pub async fn list_profiles(state: State<'_, AppState>) -> Result<Vec<ProfileSummary>, String> {
+    state
+        .db
+        .read(list_profile_summaries)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_profile(
+    state: State<'_, AppState>,
+    name: String,
+    email: Option<String>,
+) -> Result<ProfileSummary, String> {
+    state
+        .db
+        .call(move |conn| create_profile_user(conn, &name, email.as_deref()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// Change de profil actif. Les scores du profil sélectionné sont remis à jour
+// en arrière-plan, le recalcul éventuel du profil précédent étant annulé
+#[tauri::command]
+pub async fn switch_profile(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    user_id: i64,
+) -> Result<ProfileSummary, String> {
+    let summary = state
+        .db
+        .call(move |conn| activate_profile(conn, user_id, &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())?;
+    state.set_user_id(user_id);
+
+    if summary.has_profile {
+        state.rescorer.start(app, state.db.clone(), state.operations.clone(), user_id);
+    } else {
+        state.rescorer.cancel();
+    }
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn get_active_profile(state: State<'_, AppState>) -> Result<ProfileSummary, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| load_profile_summary(conn, user_id))
+        .await
+        .map_err(|e| e.to_string())
+}
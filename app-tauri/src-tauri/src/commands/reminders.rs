@@ -0,0 +1,355 @@
+use crate::{error::AppError, time::now_timestamp, AppState};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::State;
+
+// Une étape planifiée est rappelée la veille
+pub const STAGE_REMINDER_LEAD_HOURS: i64 = 24;
+// Relance proposée quand une candidature envoyée reste sans réponse
+pub const NO_RESPONSE_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderKind {
+    // Étape de candidature planifiée (`application_stages.scheduled_at`)
+    Stage,
+    // Date de relance d'une carte du Kanban (`kanban_cards.follow_up_date`)
+    FollowUp,
+    // Candidature envoyée restée sans réponse
+    NoResponse,
+}
+
+impl ReminderKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReminderKind::Stage => "stage",
+            ReminderKind::FollowUp => "follow_up",
+            ReminderKind::NoResponse => "no_response",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "stage" => Some(ReminderKind::Stage),
+            "follow_up" => Some(ReminderKind::FollowUp),
+            "no_response" => Some(ReminderKind::NoResponse),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReminderStatus {
+    Pending,
+    Notified,
+    Snoozed,
+    Dismissed,
+}
+
+impl ReminderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReminderStatus::Pending => "pending",
+            ReminderStatus::Notified => "notified",
+            ReminderStatus::Snoozed => "snoozed",
+            ReminderStatus::Dismissed => "dismissed",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "notified" => ReminderStatus::Notified,
+            "snoozed" => ReminderStatus::Snoozed,
+            "dismissed" => ReminderStatus::Dismissed,
+            _ => ReminderStatus::Pending,
+        }
+    }
+}
+
+// `source_id` désigne l'étape, la carte ou la candidature selon `kind`.
+// Dates au format `YYYY-MM-DD HH:MM:SS` (UTC)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: i64,
+    pub user_id: i64,
+    pub kind: ReminderKind,
+    pub source_id: i64,
+    pub job_id: i64,
+    pub title: String,
+    pub due_at: String,
+    pub status: ReminderStatus,
+    pub snoozed_until: Option<String>,
+    pub notified_at: Option<String>,
+    pub created_at: String,
+}
+
+const REMINDER_COLUMNS: &str =
+    "id, user_id, kind, source_id, job_id, title, due_at, status, snoozed_until, notified_at, created_at";
+
+fn reminder_from_row(row: &Row) -> rusqlite::Result<Reminder> {
+    let kind: String = row.get(2)?;
+    let status: String = row.get(7)?;
+    Ok(Reminder {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        kind: ReminderKind::parse(&kind).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                2,
+                rusqlite::types::Type::Text,
+                format!("Type de rappel inconnu : {}", kind).into(),
+            )
+        })?,
+        source_id: row.get(3)?,
+        job_id: row.get(4)?,
+        title: row.get(5)?,
+        due_at: row.get(6)?,
+        status: ReminderStatus::parse(&status),
+        snoozed_until: row.get(8)?,
+        notified_at: row.get(9)?,
+        created_at: row.get(10)?,
+    })
+}
+
+struct ReminderCandidate {
+    user_id: i64,
+    kind: ReminderKind,
+    source_id: i64,
+    job_id: i64,
+    title: String,
+    due_at: String,
+}
+
+// Échéances calculées à partir des données actuelles, tous profils confondus.
+// Les candidatures closes ne génèrent plus de rappel d'étape
+fn reminder_candidates(conn: &Connection) -> Result<Vec<ReminderCandidate>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT 'stage', applications.user_id, application_stages.id, applications.job_id,
+                application_stages.stage_type || ' : ' || jobs.title || ' (' || jobs.company || ')',
+                datetime(application_stages.scheduled_at, '-' || ?1 || ' hours')
+         FROM application_stages
+         JOIN applications ON applications.id = application_stages.application_id
+         JOIN jobs ON jobs.id = applications.job_id
+         WHERE application_stages.completed_at IS NULL
+           AND datetime(application_stages.scheduled_at) IS NOT NULL
+           AND applications.status NOT IN ('accepted', 'rejected', 'withdrawn')
+         UNION ALL
+         SELECT 'follow_up', kanban_columns.user_id, kanban_cards.id, kanban_cards.job_id,
+                'Relancer : ' || jobs.title || ' (' || jobs.company || ')',
+                datetime(kanban_cards.follow_up_date)
+         FROM kanban_cards
+         JOIN kanban_columns ON kanban_columns.id = kanban_cards.column_id
+         JOIN jobs ON jobs.id = kanban_cards.job_id
+         WHERE datetime(kanban_cards.follow_up_date) IS NOT NULL
+         UNION ALL
+         SELECT 'no_response', applications.user_id, applications.id, applications.job_id,
+                'Sans réponse : ' || jobs.title || ' (' || jobs.company || ')',
+                datetime(applications.applied_at, '+' || ?2 || ' days')
+         FROM applications
+         JOIN jobs ON jobs.id = applications.job_id
+         WHERE applications.status = 'applied'
+           AND NOT applications.response_received
+           AND datetime(applications.applied_at) IS NOT NULL",
+    )?;
+
+    let candidates = stmt
+        .query_map(params![STAGE_REMINDER_LEAD_HOURS, NO_RESPONSE_DAYS], |row| {
+            let kind: String = row.get(0)?;
+            Ok(ReminderCandidate {
+                user_id: row.get(1)?,
+                kind: ReminderKind::parse(&kind).unwrap_or(ReminderKind::Stage),
+                source_id: row.get(2)?,
+                job_id: row.get(3)?,
+                title: row.get(4)?,
+                due_at: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(candidates)
+}
+
+// Aligne la table des rappels sur les échéances actuelles : les nouvelles
+// sont ajoutées, celles qui ont disparu (étape terminée, date modifiée,
+// réponse reçue) sont supprimées. L'état des rappels conservés est préservé
+pub fn sync_reminders(conn: &Connection) -> Result<(), AppError> {
+    let candidates = reminder_candidates(conn)?;
+
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut upsert = tx.prepare(
+            "INSERT INTO reminders (user_id, kind, source_id, job_id, title, due_at)
+             VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT (kind, source_id, due_at)
+             DO UPDATE SET user_id = excluded.user_id, job_id = excluded.job_id, title = excluded.title",
+        )?;
+        for candidate in &candidates {
+            upsert.execute(params![
+                candidate.user_id,
+                candidate.kind.as_str(),
+                candidate.source_id,
+                candidate.job_id,
+                candidate.title,
+                candidate.due_at
+            ])?;
+        }
+
+        let current: HashSet<(&str, i64, &str)> = candidates
+            .iter()
+            .map(|candidate| (candidate.kind.as_str(), candidate.source_id, candidate.due_at.as_str()))
+            .collect();
+        let mut stmt = tx.prepare("SELECT id, kind, source_id, due_at FROM reminders")?;
+        let stale: Vec<i64> = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?, row.get::<_, String>(3)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|(_, kind, source_id, due_at)| !current.contains(&(kind.as_str(), *source_id, due_at.as_str())))
+            .map(|(id, ..)| id)
+            .collect();
+        for id in stale {
+            tx.execute("DELETE FROM reminders WHERE id = ?", [id])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// Rappels du profil arrivés à échéance (ou au bout de leur report) depuis le
+// dernier passage : ils sont marqués notifiés et ne seront plus renvoyés. Ceux
+// des autres profils restent en attente jusqu'à leur activation
+pub fn take_due_reminders(conn: &Connection, user_id: i64, now: &str) -> Result<Vec<Reminder>, AppError> {
+    sync_reminders(conn)?;
+
+    let tx = conn.unchecked_transaction()?;
+    let due = {
+        let mut stmt = tx.prepare(&format!(
+            "UPDATE reminders SET status = 'notified', notified_at = ?1, snoozed_until = NULL
+             WHERE user_id = ?2
+               AND ((status = 'pending' AND due_at <= datetime(?1))
+                    OR (status = 'snoozed' AND snoozed_until <= datetime(?1)))
+             RETURNING {}",
+            REMINDER_COLUMNS
+        ))?;
+        let mut due = stmt
+            .query_map(params![now, user_id], reminder_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        due.sort_by(|a, b| a.due_at.cmp(&b.due_at).then(a.id.cmp(&b.id)));
+        due
+    };
+    tx.commit()?;
+    Ok(due)
+}
+
+// Rappels non écartés du profil, par échéance effective
+pub fn list_user_reminders(conn: &Connection, user_id: i64) -> Result<Vec<Reminder>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM reminders
+         WHERE user_id = ? AND status != 'dismissed'
+         ORDER BY COALESCE(snoozed_until, due_at), id",
+        REMINDER_COLUMNS
+    ))?;
+    let reminders = stmt
+        .query_map([user_id], reminder_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(reminders)
+}
+
+fn update_reminder(
+    conn: &Connection,
+    user_id: i64,
+    reminder_id: i64,
+    status: ReminderStatus,
+    snoozed_until: Option<&str>,
+) -> Result<Reminder, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "UPDATE reminders SET status = ?, snoozed_until = ?
+         WHERE id = ? AND user_id = ?
+         RETURNING {}",
+        REMINDER_COLUMNS
+    ))?;
+    stmt.query_row(
+        params![status.as_str(), snoozed_until, reminder_id, user_id],
+        reminder_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Rappel {} introuvable", reminder_id)))
+}
+
+// Reporte le rappel : il sera de nouveau notifié à `until`
+pub fn postpone_reminder(
+    conn: &Connection,
+    user_id: i64,
+    reminder_id: i64,
+    until: &str,
+    now: &str,
+) -> Result<Reminder, AppError> {
+    let until: Option<String> = conn.query_row(
+        "SELECT datetime(?) WHERE datetime(?) > datetime(?)",
+        params![until, until, now],
+        |row| row.get(0),
+    )
+    .optional()?;
+    let until = until.ok_or_else(|| AppError::Validation("Le report doit se terminer dans le futur".to_string()))?;
+    update_reminder(conn, user_id, reminder_id, ReminderStatus::Snoozed, Some(&until))
+}
+
+// Un rappel écarté n'est plus notifié tant que son échéance ne change pas
+pub fn discard_reminder(conn: &Connection, user_id: i64, reminder_id: i64) -> Result<Reminder, AppError> {
+    update_reminder(conn, user_id, reminder_id, ReminderStatus::Dismissed, None)
+}
+
+#[tauri::command]
+pub async fn list_reminders(state: State<'_, AppState>) -> Result<Vec<Reminder>, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| {
+            sync_reminders(conn)?;
+            list_user_reminders(conn, user_id)
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// Report maximal d'un rappel : un an
+pub const MAX_SNOOZE_MINUTES: i64 = 365 * 24 * 60;
+
+// Échéance d'un report de `minutes` à partir de `now`
+pub fn snooze_until(now: chrono::DateTime<chrono::Utc>, minutes: i64) -> Result<String, AppError> {
+    if minutes <= 0 {
+        return Err(AppError::Validation("La durée du report doit être positive".to_string()));
+    }
+    if minutes > MAX_SNOOZE_MINUTES {
+        return Err(AppError::Validation("Un rappel ne peut pas être reporté de plus d'un an".to_string()));
+    }
+    now.checked_add_signed(chrono::Duration::minutes(minutes))
+        .map(|until| until.format("%Y-%m-%d %H:%M:%S").to_string())
+        .ok_or_else(|| AppError::Validation("Date de report invalide".to_string()))
+}
+
+#[tauri::command]
+pub async fn snooze_reminder(
+    state: State<'_, AppState>,
+    reminder_id: i64,
+    minutes: i64,
+) -> Result<Reminder, String> {
+    let until = snooze_until(chrono::Utc::now(), minutes).map_err(|e| e.to_string())?;
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| postpone_reminder(conn, user_id, reminder_id, &until, &now_timestamp()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn dismiss_reminder(state: State<'_, AppState>, reminder_id: i64) -> Result<Reminder, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| discard_reminder(conn, user_id, reminder_id))
+        .await
+        .map_err(|e| e.to_string())
+}
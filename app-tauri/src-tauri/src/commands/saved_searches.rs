@@ -0,0 +1,428 @@
+use crate::{
+    commands::search::{
+        build_filtered_query, find_jobs, resolve_origin, FilteredQuery, JobResult, SearchCriteria, SortKey,
+        MAX_PAGE_SIZE,
+    },
+    error::AppError,
+    time::now_timestamp,
+    AppState,
+};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+// Au-delà, une recherche sauvegardée est trop large pour être suivie
+pub const MAX_TRACKED_RESULTS: usize = 1000;
+pub const DEFAULT_INTERVAL_MINUTES: i64 = 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: i64,
+    pub user_id: i64,
+    pub name: String,
+    pub criteria: SearchCriteria,
+    pub is_active: bool,
+    pub interval_minutes: i64,
+    pub last_run_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveSearchRequest {
+    pub name: String,
+    pub criteria: SearchCriteria,
+    pub interval_minutes: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateSavedSearchRequest {
+    pub name: Option<String>,
+    pub criteria: Option<SearchCriteria>,
+    pub is_active: Option<bool>,
+    pub interval_minutes: Option<i64>,
+}
+
+// Résultat d'une exécution comparée à la précédente. La première exécution
+// sert de référence : ses offres sont toutes « nouvelles »
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearchRun {
+    pub saved_search_id: i64,
+    pub user_id: i64,
+    pub name: String,
+    pub ran_at: String,
+    pub first_run: bool,
+    pub total: usize,
+    pub new_jobs: Vec<JobSummary>,
+    pub changed_jobs: Vec<JobSummary>,
+    pub removed_job_ids: Vec<i64>,
+}
+
+impl SavedSearchRun {
+    pub fn has_changes(&self) -> bool {
+        !self.new_jobs.is_empty() || !self.changed_jobs.is_empty() || !self.removed_job_ids.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobSummary {
+    pub id: i64,
+    pub title: String,
+    pub company: String,
+    pub location: String,
+    pub url: String,
+}
+
+impl From<&JobResult> for JobSummary {
+    fn from(job: &JobResult) -> Self {
+        Self {
+            id: job.id,
+            title: job.title.clone(),
+            company: job.company.clone(),
+            location: job.location.clone(),
+            url: job.url.clone(),
+        }
+    }
+}
+
+const SAVED_SEARCH_COLUMNS: &str =
+    "id, user_id, name, criteria, is_active, interval_minutes, last_run_at, created_at, updated_at";
+
+fn saved_search_from_row(row: &Row) -> rusqlite::Result<SavedSearch> {
+    let criteria: String = row.get(3)?;
+    Ok(SavedSearch {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        name: row.get(2)?,
+        criteria: serde_json::from_str(&criteria).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+        is_active: row.get(4)?,
+        interval_minutes: row.get(5)?,
+        last_run_at: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+fn validate(name: &str, interval_minutes: i64) -> Result<(), AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::Validation("Le nom de la recherche ne peut pas être vide".to_string()));
+    }
+    if interval_minutes < 1 {
+        return Err(AppError::Validation("L'intervalle doit être d'au moins une minute".to_string()));
+    }
+    Ok(())
+}
+
+// La position de pagination n'a pas de sens pour une recherche rejouée
+fn storable(criteria: SearchCriteria) -> SearchCriteria {
+    SearchCriteria {
+        limit: None,
+        offset: None,
+        page: None,
+        cursor: None,
+        ..criteria
+    }
+}
+
+fn criteria_json(criteria: &SearchCriteria) -> Result<String, AppError> {
+    serde_json::to_string(criteria).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+pub fn insert_saved_search(
+    conn: &Connection,
+    user_id: i64,
+    request: SaveSearchRequest,
+) -> Result<SavedSearch, AppError> {
+    let interval_minutes = request.interval_minutes.unwrap_or(DEFAULT_INTERVAL_MINUTES);
+    validate(&request.name, interval_minutes)?;
+
+    let saved = conn.query_row(
+        &format!(
+            "INSERT INTO saved_searches (user_id, name, criteria, interval_minutes)
+             VALUES (?, ?, ?, ?)
+             RETURNING {}",
+            SAVED_SEARCH_COLUMNS
+        ),
+        params![
+            user_id,
+            request.name.trim(),
+            criteria_json(&storable(request.criteria))?,
+            interval_minutes
+        ],
+        saved_search_from_row,
+    )?;
+    Ok(saved)
+}
+
+// Recherche sauvegardée de l'utilisateur : celle d'un autre profil est introuvable
+pub fn load_saved_search(conn: &Connection, user_id: i64, id: i64) -> Result<SavedSearch, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM saved_searches WHERE id = ? AND user_id = ?", SAVED_SEARCH_COLUMNS),
+        [id, user_id],
+        saved_search_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Recherche sauvegardée {}", id)))
+}
+
+pub fn list_user_saved_searches(conn: &Connection, user_id: i64) -> Result<Vec<SavedSearch>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM saved_searches WHERE user_id = ? ORDER BY name, id",
+        SAVED_SEARCH_COLUMNS
+    ))?;
+    let searches = stmt
+        .query_map([user_id], saved_search_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(searches)
+}
+
+pub fn modify_saved_search(
+    conn: &Connection,
+    user_id: i64,
+    id: i64,
+    request: UpdateSavedSearchRequest,
+) -> Result<SavedSearch, AppError> {
+    let current = load_saved_search(conn, user_id, id)?;
+    let name = request.name.unwrap_or(current.name);
+    let interval_minutes = request.interval_minutes.unwrap_or(current.interval_minutes);
+    validate(&name, interval_minutes)?;
+
+    let tx = conn.unchecked_transaction()?;
+    if let Some(criteria) = request.criteria {
+        // D'autres critères : l'ancienne référence ne permet plus de comparer
+        tx.execute(
+            "UPDATE saved_searches SET criteria = ?, last_run_at = NULL WHERE id = ?",
+            params![criteria_json(&storable(criteria))?, id],
+        )?;
+        tx.execute("DELETE FROM saved_search_results WHERE saved_search_id = ?", [id])?;
+    }
+    tx.execute(
+        "UPDATE saved_searches
+         SET name = ?, is_active = ?, interval_minutes = ?, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?",
+        params![
+            name.trim(),
+            request.is_active.unwrap_or(current.is_active),
+            interval_minutes,
+            id
+        ],
+    )?;
+    tx.commit()?;
+
+    load_saved_search(conn, user_id, id)
+}
+
+pub fn remove_saved_search(conn: &Connection, user_id: i64, id: i64) -> Result<bool, AppError> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "DELETE FROM saved_search_results
+         WHERE saved_search_id = (SELECT id FROM saved_searches WHERE id = ? AND user_id = ?)",
+        [id, user_id],
+    )?;
+    let rows_affected = tx.execute("DELETE FROM saved_searches WHERE id = ? AND user_id = ?", [id, user_id])?;
+    tx.commit()?;
+    Ok(rows_affected > 0)
+}
+
+// Empreinte des champs visibles d'une offre : un changement signale une mise à jour
+fn fingerprint(job: &JobResult) -> String {
+    let mut hasher = Sha256::new();
+    for field in [
+        job.title.as_str(),
+        job.company.as_str(),
+        job.location.as_str(),
+        job.job_type.as_str(),
+        job.description.as_str(),
+        job.url.as_str(),
+    ] {
+        hasher.update(field.as_bytes());
+        hasher.update([0]);
+    }
+    hasher.update(format!("{:?}|{:?}|{}", job.salary_min, job.salary_max, job.remote).as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+// Toutes les offres correspondant aux critères, page par page
+fn collect_matches(conn: &Connection, user_id: i64, criteria: &SearchCriteria) -> Result<Vec<JobResult>, AppError> {
+    let mut jobs = Vec::new();
+    let mut cursor = None;
+    loop {
+        let response = find_jobs(
+            conn,
+            user_id,
+            SearchCriteria {
+                limit: Some(MAX_PAGE_SIZE),
+                cursor: cursor.take(),
+                ..criteria.clone()
+            },
+        )?;
+        jobs.extend(response.jobs);
+        match response.next_cursor {
+            Some(next) if jobs.len() < MAX_TRACKED_RESULTS => cursor = Some(next),
+            _ => break,
+        }
+    }
+    jobs.truncate(MAX_TRACKED_RESULTS);
+    Ok(jobs)
+}
+
+// Identifiants de toutes les offres correspondant aux critères, sans la
+// limite de suivi : une offre repoussée au-delà de `MAX_TRACKED_RESULTS`
+// correspond toujours et n'est pas retirée
+fn matching_job_ids(conn: &Connection, user_id: i64, criteria: &SearchCriteria) -> Result<HashSet<i64>, AppError> {
+    let origin = resolve_origin(conn, user_id, criteria)?;
+    let FilteredQuery { sql, params, .. } = build_filtered_query(criteria, SortKey::Date, origin.as_ref());
+    let mut stmt = conn.prepare(&format!("SELECT id FROM ({})", sql))?;
+    let ids = stmt
+        .query_map(rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())), |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    Ok(ids)
+}
+
+// Rejoue une recherche sauvegardée, la compare à l'exécution précédente et
+// enregistre le nouvel état. `now` au format SQLite `YYYY-MM-DD HH:MM:SS`.
+// Appelée aussi par le planificateur, pour les recherches du profil actif
+pub fn execute_saved_search(conn: &Connection, id: i64, now: &str) -> Result<SavedSearchRun, AppError> {
+    let owner: i64 = conn
+        .query_row("SELECT user_id FROM saved_searches WHERE id = ?", [id], |row| row.get(0))
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Recherche sauvegardée {}", id)))?;
+    let saved = load_saved_search(conn, owner, id)?;
+    let jobs = collect_matches(conn, saved.user_id, &saved.criteria)?;
+    let matching = matching_job_ids(conn, saved.user_id, &saved.criteria)?;
+
+    let mut stmt = conn.prepare("SELECT job_id, fingerprint FROM saved_search_results WHERE saved_search_id = ?")?;
+    let mut previous: HashMap<i64, String> = stmt
+        .query_map([id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+
+    let mut run = SavedSearchRun {
+        saved_search_id: id,
+        user_id: owner,
+        name: saved.name,
+        ran_at: now.to_string(),
+        first_run: saved.last_run_at.is_none(),
+        total: matching.len(),
+        new_jobs: Vec::new(),
+        changed_jobs: Vec::new(),
+        removed_job_ids: Vec::new(),
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    for job in &jobs {
+        let current = fingerprint(job);
+        match previous.remove(&job.id) {
+            None => {
+                run.new_jobs.push(job.into());
+                tx.execute(
+                    "INSERT INTO saved_search_results (saved_search_id, job_id, fingerprint, first_seen_at)
+                     VALUES (?, ?, ?, ?)",
+                    params![id, job.id, current, now],
+                )?;
+            }
+            Some(before) if before != current => {
+                run.changed_jobs.push(job.into());
+                tx.execute(
+                    "UPDATE saved_search_results SET fingerprint = ? WHERE saved_search_id = ? AND job_id = ?",
+                    params![current, id, job.id],
+                )?;
+            }
+            Some(_) => {}
+        }
+    }
+
+    // Ce qui reste n'est plus dans les résultats, sauf au-delà de la limite de suivi
+    for job_id in previous.into_keys().filter(|job_id| !matching.contains(job_id)) {
+        run.removed_job_ids.push(job_id);
+        tx.execute(
+            "DELETE FROM saved_search_results WHERE saved_search_id = ? AND job_id = ?",
+            params![id, job_id],
+        )?;
+    }
+    run.removed_job_ids.sort_unstable();
+
+    tx.execute("UPDATE saved_searches SET last_run_at = ? WHERE id = ?", params![now, id])?;
+    tx.commit()?;
+
+    Ok(run)
+}
+
+// Identifiants des recherches actives du profil dont l'intervalle est écoulé
+pub fn due_saved_searches(conn: &Connection, user_id: i64, now: &str) -> Result<Vec<i64>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM saved_searches
+         WHERE user_id = ? AND is_active = 1
+           AND (last_run_at IS NULL
+                OR datetime(last_run_at, '+' || interval_minutes || ' minutes') <= datetime(?))
+         ORDER BY id",
+    )?;
+    let ids = stmt
+        .query_map(params![user_id, now], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ids)
+}
+
+#[tauri::command]
+pub async fn create_saved_search(
+    state: State<'_, AppState>,
+    request: SaveSearchRequest,
+) -> Result<SavedSearch, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| insert_saved_search(conn, user_id, request))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_saved_searches(state: State<'_, AppState>) -> Result<Vec<SavedSearch>, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| list_user_saved_searches(conn, user_id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_saved_search(
+    state: State<'_, AppState>,
+    id: i64,
+    request: UpdateSavedSearchRequest,
+) -> Result<SavedSearch, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| modify_saved_search(conn, user_id, id, request))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_saved_search(state: State<'_, AppState>, id: i64) -> Result<bool, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| remove_saved_search(conn, user_id, id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn run_saved_search(state: State<'_, AppState>, id: i64) -> Result<SavedSearchRun, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| {
+            // Seules les recherches du profil actif peuvent être lancées à la main
+            load_saved_search(conn, user_id, id)?;
+            execute_saved_search(conn, id, &now_timestamp())
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
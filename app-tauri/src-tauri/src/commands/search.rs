@@ -1,9 +1,20 @@
-use crate::AppState;
-use rusqlite::params;
+use crate::{
+    database::fts::parse_keywords,
+    error::AppError,
+    geo::home_coordinates,
+    models::Coordinates,
+    skills::SkillTaxonomy,
+    AppState,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rusqlite::{params, types::Value, Connection, OptionalExtension, Row, ToSql};
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
-#[derive(Debug, Serialize, Deserialize)]
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+pub const MAX_PAGE_SIZE: u32 = 200;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SearchCriteria {
     pub keywords: String,
     pub location: String,
@@ -15,6 +26,25 @@ pub struct SearchCriteria {
     pub skills: Vec<String>,
     pub date_posted: Option<String>,
     pub sort_by: Option<String>,
+    // Pagination : `cursor` (défilement infini) prime sur `offset`, lui-même
+    // prioritaire sur `page` (numérotée à partir de 1, héritée du bridge Python)
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: Option<u32>,
+    #[serde(default)]
+    pub page: Option<u32>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    // Recherche géographique autour du domicile "primary" (défaut) ou "secondary"
+    #[serde(default)]
+    pub radius_km: Option<f64>,
+    #[serde(default)]
+    pub home: Option<String>,
+    // Garde dans une recherche par rayon les offres dont le lieu n'a pas pu
+    // être géolocalisé (distance inconnue) ; écartées par défaut
+    #[serde(default)]
+    pub include_unknown_distance: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,136 +63,465 @@ pub struct JobResult {
     pub skills: Vec<String>,
     pub remote: bool,
     pub source: String,
+    // Renseignés uniquement pour une recherche par mots-clés : termes trouvés
+    // entourés de <mark>, score BM25 (plus élevé = plus pertinent)
+    pub title_snippet: Option<String>,
+    pub description_snippet: Option<String>,
+    pub relevance: Option<f64>,
+    // Distance à vol d'oiseau depuis le domicile de référence, si connue
+    pub distance_km: Option<f64>,
 }
 
-#[tauri::command]
-pub async fn search_jobs(
-    state: State<'_, AppState>,
-    criteria: SearchCriteria,
-) -> Result<Vec<JobResult>, String> {
-    let conn = state.db.lock().await;
-    let conn = conn.as_ref().ok_or("Database connection not initialized")?;
+// Filtre effectivement appliqué, renvoyé pour l'affichage des filtres actifs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedFacet {
+    pub field: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchResponse {
+    pub jobs: Vec<JobResult>,
+    pub total: i64,
+    pub limit: u32,
+    pub offset: u32,
+    pub sort_by: String,
+    pub next_cursor: Option<String>,
+    pub applied_facets: Vec<AppliedFacet>,
+    // Recherche géographique : offres sans coordonnées (distance inconnue) qui
+    // répondent aux autres filtres. Hors rayon, et sauf `include_unknown_distance`,
+    // elles ne sont pas dans `total` ; sinon elles sont listées en dernier
+    pub unknown_distance: Option<i64>,
+}
+
+const JOB_RESULT_COLUMNS: &str = "jobs.id, jobs.title, jobs.company, jobs.location, jobs.job_type,
+    jobs.salary_min, jobs.salary_max, jobs.description, jobs.url, jobs.posted_at, jobs.experience_level,
+    jobs.skills, jobs.remote, jobs.source";
+
+// Colonnes FTS ajoutées aux résultats : le titre et l'entreprise pèsent plus
+// que la description dans le classement
+const FTS_COLUMNS: &str = "highlight(jobs_fts, 0, '<mark>', '</mark>') AS title_snippet,
+    snippet(jobs_fts, 2, '<mark>', '</mark>', '…', 16) AS description_snippet,
+    -bm25(jobs_fts, 10.0, 5.0, 1.0, 1.0, 3.0) AS relevance";
+
+const NO_FTS_COLUMNS: &str = "NULL AS title_snippet, NULL AS description_snippet, NULL AS relevance";
+
+// Offres citant la compétence (nom canonique) ou l'une de ses descendantes
+// (React pour JavaScript…). Paramètres : identifiant puis nom canonique
+const SKILL_TREE_FILTER: &str = "EXISTS (
+        WITH RECURSIVE tree(id) AS (
+            SELECT ? UNION SELECT skills.id FROM skills JOIN tree ON skills.parent_id = tree.id
+        )
+        SELECT 1 FROM json_each(CASE WHEN json_valid(jobs.skills) THEN jobs.skills ELSE '[]' END) AS job_skill
+        WHERE job_skill.value = ?
+           OR job_skill.value IN (SELECT skills.name FROM skills JOIN tree ON skills.id = tree.id)
+    )";
+
+fn job_result_from_row(row: &Row) -> rusqlite::Result<JobResult> {
+    Ok(JobResult {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        company: row.get(2)?,
+        location: row.get(3)?,
+        job_type: row.get(4)?,
+        salary_min: row.get(5)?,
+        salary_max: row.get(6)?,
+        description: row.get(7)?,
+        url: row.get(8)?,
+        posted_at: row.get(9)?,
+        experience_level: row.get(10)?,
+        skills: serde_json::from_str(&row.get::<_, String>(11)?).unwrap_or_default(),
+        remote: row.get(12)?,
+        source: row.get(13)?,
+        title_snippet: row.get(14)?,
+        description_snippet: row.get(15)?,
+        relevance: row.get(16)?,
+        distance_km: row.get(17)?,
+    })
+}
+
+// Requête filtrée commune au comptage, à la pagination et aux facettes
+pub(crate) struct FilteredQuery {
+    pub sql: String,
+    pub params: Vec<Box<dyn ToSql>>,
+    pub applied: Vec<AppliedFacet>,
+}
+
+fn push_in_clause(query: &mut String, params: &mut Vec<Box<dyn ToSql>>, column: &str, values: &[String]) {
+    query.push_str(&format!(" AND {} IN (", column));
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            query.push(',');
+        }
+        query.push('?');
+        params.push(Box::new(value.clone()));
+    }
+    query.push(')');
+}
 
-    let mut query = String::from(
-        "SELECT id, title, company, location, job_type, salary_min, salary_max, 
-        description, url, posted_at, experience_level, skills, remote, source 
-        FROM jobs WHERE 1=1",
-    );
+// Expression SQL de la distance au point d'origine ; les coordonnées sont des
+// flottants validés, jamais du texte saisi
+fn distance_expression(origin: Option<&Coordinates>) -> String {
+    match origin {
+        Some(origin) => format!(
+            "haversine_km(jobs.latitude, jobs.longitude, {:?}, {:?})",
+            origin.lat, origin.lng
+        ),
+        None => "NULL".to_string(),
+    }
+}
+
+// Point d'origine de la recherche géographique, requis dès qu'un rayon ou
+// un tri par distance est demandé
+pub(crate) fn resolve_origin(
+    conn: &Connection,
+    user_id: i64,
+    criteria: &SearchCriteria,
+) -> Result<Option<Coordinates>, AppError> {
+    let wants_distance = criteria.radius_km.is_some()
+        || criteria.home.is_some()
+        || criteria.sort_by.as_deref() == Some("distance");
+    if !wants_distance {
+        return Ok(None);
+    }
 
-    let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+    let secondary = match criteria.home.as_deref() {
+        None | Some("primary") => false,
+        Some("secondary") => true,
+        Some(other) => {
+            return Err(AppError::Validation(format!("Domicile de référence inconnu : {}", other)));
+        }
+    };
+    if let Some(radius) = criteria.radius_km {
+        if !radius.is_finite() || radius <= 0.0 {
+            return Err(AppError::Validation("Le rayon de recherche doit être positif".to_string()));
+        }
+    }
 
-    if !criteria.keywords.is_empty() {
-        query.push_str(" AND (title LIKE ? OR description LIKE ? OR company LIKE ?)");
-        let keyword = format!("%{}%", criteria.keywords);
-        params.push(Box::new(keyword.clone()));
-        params.push(Box::new(keyword.clone()));
-        params.push(Box::new(keyword));
+    let origin = home_coordinates(conn, user_id, secondary)?
+        .filter(|origin| origin.lat.is_finite() && origin.lng.is_finite());
+    if origin.is_none() && criteria.radius_km.is_some() {
+        return Err(AppError::Validation(
+            "Impossible de localiser le domicile pour la recherche par rayon".to_string(),
+        ));
+    }
+    Ok(origin)
+}
+
+// Construit `SELECT <colonnes>, <clé de tri> AS sort_key FROM jobs ... WHERE <filtres>`
+pub(crate) fn build_filtered_query(
+    criteria: &SearchCriteria,
+    sort_key: SortKey,
+    origin: Option<&Coordinates>,
+) -> FilteredQuery {
+    let fts = parse_keywords(&criteria.keywords);
+    let distance = distance_expression(origin);
+    let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+    let mut applied = Vec::new();
+    let mut applied_facet = |field: &str, value: String| {
+        applied.push(AppliedFacet { field: field.to_string(), value });
+    };
+
+    let mut query = match fts.matching {
+        Some(matching) => {
+            params.push(Box::new(matching));
+            format!(
+                "SELECT {}, {}, {} AS distance_km, {} AS sort_key
+                 FROM jobs JOIN jobs_fts ON jobs_fts.rowid = jobs.id WHERE jobs_fts MATCH ?",
+                JOB_RESULT_COLUMNS,
+                FTS_COLUMNS,
+                distance,
+                sort_key.expression(&distance)
+            )
+        }
+        None => format!(
+            "SELECT {}, {}, {} AS distance_km, {} AS sort_key FROM jobs WHERE 1=1",
+            JOB_RESULT_COLUMNS,
+            NO_FTS_COLUMNS,
+            distance,
+            sort_key.expression(&distance)
+        ),
+    };
+
+    if let Some(excluded) = fts.excluded {
+        query.push_str(" AND jobs.id NOT IN (SELECT rowid FROM jobs_fts WHERE jobs_fts MATCH ?)");
+        params.push(Box::new(excluded));
+    }
+    if !criteria.keywords.trim().is_empty() {
+        applied_facet("keywords", criteria.keywords.trim().to_string());
+    }
+
+    if let Some(radius) = criteria.radius_km.filter(|_| origin.is_some()) {
+        // Une offre sans coordonnées a une distance NULL : écartée, sauf si
+        // l'appelant demande à la voir faute de pouvoir dire si elle est dans le rayon
+        let include_unknown = criteria.include_unknown_distance.unwrap_or(false);
+        query.push_str(&format!(" AND ({0} <= ? OR ({0} IS NULL AND ?))", distance));
+        params.push(Box::new(radius));
+        params.push(Box::new(include_unknown));
+        applied_facet("radius_km", radius.to_string());
+        applied_facet("home", criteria.home.clone().unwrap_or_else(|| "primary".to_string()));
+        if include_unknown {
+            applied_facet("include_unknown_distance", "true".to_string());
+        }
     }
 
     if !criteria.location.is_empty() {
-        query.push_str(" AND location LIKE ?");
+        query.push_str(" AND jobs.location LIKE ?");
         params.push(Box::new(format!("%{}%", criteria.location)));
+        applied_facet("location", criteria.location.clone());
     }
 
     if let Some(salary_min) = criteria.salary_min {
-        query.push_str(" AND salary_min >= ?");
+        query.push_str(" AND jobs.salary_min >= ?");
         params.push(Box::new(salary_min));
+        applied_facet("salary_min", salary_min.to_string());
     }
 
     if let Some(salary_max) = criteria.salary_max {
-        query.push_str(" AND salary_max <= ?");
+        query.push_str(" AND jobs.salary_max <= ?");
         params.push(Box::new(salary_max));
+        applied_facet("salary_max", salary_max.to_string());
     }
 
     if !criteria.contract_types.is_empty() {
-        query.push_str(" AND job_type IN (");
-        for (i, _) in criteria.contract_types.iter().enumerate() {
-            if i > 0 {
-                query.push(',');
-            }
-            query.push('?');
-        }
-        query.push(')');
-        for contract_type in criteria.contract_types {
-            params.push(Box::new(contract_type));
+        push_in_clause(&mut query, &mut params, "jobs.job_type", &criteria.contract_types);
+        for contract_type in &criteria.contract_types {
+            applied_facet("job_type", contract_type.clone());
         }
     }
 
     if !criteria.experience_levels.is_empty() {
-        query.push_str(" AND experience_level IN (");
-        for (i, _) in criteria.experience_levels.iter().enumerate() {
-            if i > 0 {
-                query.push(',');
-            }
-            query.push('?');
-        }
-        query.push(')');
-        for level in criteria.experience_levels {
-            params.push(Box::new(level));
+        push_in_clause(&mut query, &mut params, "jobs.experience_level", &criteria.experience_levels);
+        for level in &criteria.experience_levels {
+            applied_facet("experience_level", level.clone());
         }
     }
 
     if let Some(remote) = criteria.remote {
-        query.push_str(" AND remote = ?");
+        query.push_str(" AND jobs.remote = ?");
         params.push(Box::new(remote));
+        applied_facet("remote", remote.to_string());
     }
 
     if !criteria.skills.is_empty() {
+        let taxonomy = SkillTaxonomy::bundled();
         query.push_str(" AND (");
         for (i, skill) in criteria.skills.iter().enumerate() {
             if i > 0 {
                 query.push_str(" OR ");
             }
-            query.push_str("skills LIKE ?");
-            params.push(Box::new(format!("%{}%", skill)));
+            // Compétence du référentiel (identifiant, nom ou synonyme) : ses
+            // sous-compétences satisfont aussi le filtre. Sinon, simple recherche textuelle
+            match taxonomy.resolve(skill) {
+                Some(known) => {
+                    query.push_str(SKILL_TREE_FILTER);
+                    params.push(Box::new(known.id.clone()));
+                    params.push(Box::new(known.name.clone()));
+                    applied_facet("skill", known.id.clone());
+                }
+                None => {
+                    query.push_str("jobs.skills LIKE ?");
+                    params.push(Box::new(format!("%{}%", skill)));
+                    applied_facet("skill", skill.clone());
+                }
+            }
         }
         query.push(')');
     }
 
-    if let Some(date_posted) = criteria.date_posted {
-        query.push_str(" AND posted_at >= ?");
-        params.push(Box::new(date_posted));
+    if let Some(date_posted) = &criteria.date_posted {
+        query.push_str(" AND jobs.posted_at >= ?");
+        params.push(Box::new(date_posted.clone()));
+        applied_facet("date_posted", date_posted.clone());
+    }
+
+    FilteredQuery { sql: query, params, applied }
+}
+
+// Clé de tri principale ; l'id départage toujours les égalités pour que
+// deux pages successives ne se chevauchent ni ne se sautent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SortKey {
+    Relevance,
+    Date,
+    Salary,
+    Distance,
+}
+
+impl SortKey {
+    fn resolve(sort_by: Option<&str>, ranked: bool, located: bool) -> Self {
+        match sort_by {
+            Some("relevance") | None if ranked => SortKey::Relevance,
+            Some("salary") => SortKey::Salary,
+            Some("distance") if located => SortKey::Distance,
+            _ => SortKey::Date,
+        }
     }
 
-    if let Some(sort_by) = criteria.sort_by {
-        match sort_by.as_str() {
-            "date" => query.push_str(" ORDER BY posted_at DESC"),
-            "salary" => query.push_str(" ORDER BY salary_max DESC"),
-            _ => query.push_str(" ORDER BY posted_at DESC"),
+    fn name(self) -> &'static str {
+        match self {
+            SortKey::Relevance => "relevance",
+            SortKey::Date => "date",
+            SortKey::Salary => "salary",
+            SortKey::Distance => "distance",
         }
-    } else {
-        query.push_str(" ORDER BY posted_at DESC");
     }
 
-    let mut stmt = conn.prepare(&query).map_err(|e| e.to_string())?;
+    // Toujours trié par ordre décroissant : la distance est donc négative
+    fn expression(self, distance: &str) -> String {
+        match self {
+            SortKey::Relevance => "-bm25(jobs_fts, 10.0, 5.0, 1.0, 1.0, 3.0)".to_string(),
+            SortKey::Date => "jobs.posted_at".to_string(),
+            // Les offres sans salaire passent en dernier
+            SortKey::Salary => "COALESCE(jobs.salary_max, -1)".to_string(),
+            // Les offres non géolocalisées passent en dernier
+            SortKey::Distance => format!("COALESCE(-{}, -1e9)", distance),
+        }
+    }
+}
+
+// Position opaque transmise au frontend : dernière clé de tri et dernier id vus
+#[derive(Debug, Serialize, Deserialize)]
+struct Cursor {
+    sort: String,
+    key: serde_json::Value,
+    id: i64,
+}
+
+fn encode_cursor(sort_key: SortKey, key: &Value, id: i64) -> String {
+    let key = match key {
+        Value::Integer(i) => serde_json::json!(i),
+        Value::Real(f) => serde_json::json!(f),
+        Value::Text(s) => serde_json::json!(s),
+        _ => serde_json::Value::Null,
+    };
+    let cursor = Cursor { sort: sort_key.name().to_string(), key, id };
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(&cursor).unwrap_or_default())
+}
+
+fn decode_cursor(cursor: &str, sort_key: SortKey) -> Result<(Value, i64), AppError> {
+    let invalid = || AppError::Validation("Curseur de pagination invalide".to_string());
+    let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+    let cursor: Cursor = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+    if cursor.sort != sort_key.name() {
+        return Err(AppError::Validation(
+            "Le curseur ne correspond pas au tri demandé".to_string(),
+        ));
+    }
+
+    let key = match cursor.key {
+        serde_json::Value::Number(n) if n.is_i64() => Value::Integer(n.as_i64().unwrap_or_default()),
+        serde_json::Value::Number(n) => Value::Real(n.as_f64().ok_or_else(invalid)?),
+        serde_json::Value::String(s) => Value::Text(s),
+        _ => return Err(invalid()),
+    };
+    Ok((key, cursor.id))
+}
 
-    let jobs = stmt
+// Les offres sont communes à tous les profils ; seul le domicile de référence
+// de la recherche géographique dépend de l'utilisateur
+pub fn find_jobs(conn: &Connection, user_id: i64, criteria: SearchCriteria) -> Result<SearchResponse, AppError> {
+    let ranked = parse_keywords(&criteria.keywords).matching.is_some();
+    let origin = resolve_origin(conn, user_id, &criteria)?;
+    let sort_key = SortKey::resolve(criteria.sort_by.as_deref(), ranked, origin.is_some());
+    let FilteredQuery { sql, mut params, applied } = build_filtered_query(&criteria, sort_key, origin.as_ref());
+
+    let limit = criteria.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    // Un numéro de page démesuré donne une page vide plutôt qu'un débordement
+    let offset = criteria
+        .offset
+        .or_else(|| criteria.page.map(|page| page.saturating_sub(1).saturating_mul(limit)))
+        .unwrap_or(0);
+
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM ({})", sql),
+        rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+        |row| row.get(0),
+    )?;
+    // Les offres sans coordonnées sont comptées même quand le rayon les écarte
+    let unknown_distance = match origin.as_ref() {
+        Some(origin) => {
+            let with_unknown = SearchCriteria { include_unknown_distance: Some(true), ..criteria.clone() };
+            let counted = build_filtered_query(&with_unknown, sort_key, Some(origin));
+            Some(conn.query_row(
+                &format!("SELECT COUNT(*) - COUNT(distance_km) FROM ({})", counted.sql),
+                rusqlite::params_from_iter(counted.params.iter().map(|p| p.as_ref())),
+                |row| row.get::<_, i64>(0),
+            )?)
+        }
+        None => None,
+    };
+
+    let mut query = format!("SELECT * FROM ({})", sql);
+    let offset = match &criteria.cursor {
+        Some(cursor) => {
+            let (key, id) = decode_cursor(cursor, sort_key)?;
+            query.push_str(" WHERE (sort_key < ? OR (sort_key = ? AND id < ?))");
+            params.push(Box::new(key.clone()));
+            params.push(Box::new(key));
+            params.push(Box::new(id));
+            0
+        }
+        None => offset,
+    };
+    // Une ligne de plus que la page pour savoir s'il reste des résultats
+    query.push_str(" ORDER BY sort_key DESC, id DESC LIMIT ? OFFSET ?");
+    params.push(Box::new(limit + 1));
+    params.push(Box::new(offset));
+
+    let mut stmt = conn.prepare(&query)?;
+    let mut rows = stmt
         .query_map(
             rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
-            |row| {
-                Ok(JobResult {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    company: row.get(2)?,
-                    location: row.get(3)?,
-                    job_type: row.get(4)?,
-                    salary_min: row.get(5)?,
-                    salary_max: row.get(6)?,
-                    description: row.get(7)?,
-                    url: row.get(8)?,
-                    posted_at: row.get(9)?,
-                    experience_level: row.get(10)?,
-                    skills: serde_json::from_str(&row.get::<_, String>(11)?).unwrap_or_default(),
-                    remote: row.get(12)?,
-                    source: row.get(13)?,
-                })
-            },
+            |row| Ok((job_result_from_row(row)?, row.get::<_, Value>(18)?)),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let next_cursor = if rows.len() > limit as usize {
+        rows.truncate(limit as usize);
+        rows.last().map(|(job, key)| encode_cursor(sort_key, key, job.id))
+    } else {
+        None
+    };
+
+    Ok(SearchResponse {
+        jobs: rows.into_iter().map(|(job, _)| job).collect(),
+        total,
+        limit,
+        offset,
+        sort_by: sort_key.name().to_string(),
+        next_cursor,
+        applied_facets: applied,
+        unknown_distance,
+    })
+}
+
+pub fn find_job(conn: &Connection, job_id: i64) -> Result<Option<JobResult>, AppError> {
+    let job = conn
+        .query_row(
+            &format!(
+                "SELECT {}, {}, NULL AS distance_km FROM jobs WHERE id = ?",
+                JOB_RESULT_COLUMNS, NO_FTS_COLUMNS
+            ),
+            params![job_id],
+            job_result_from_row,
         )
-        .map_err(|e| e.to_string())?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.to_string())?;
+        .optional()?;
 
-    Ok(jobs)
+    Ok(job)
+}
+
+#[tauri::command]
+pub async fn search_jobs(
+    state: State<'_, AppState>,
+    criteria: SearchCriteria,
+) -> Result<SearchResponse, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| find_jobs(conn, user_id, criteria))
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -170,37 +529,9 @@ pub async fn get_job_details(
     state: State<'_, AppState>,
     job_id: i64,
 ) -> Result<Option<JobResult>, String> {
-    let conn = state.db.lock().await;
-    let conn = conn.as_ref().ok_or("Database connection not initialized")?;
-
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, title, company, location, job_type, salary_min, salary_max, 
-            description, url, posted_at, experience_level, skills, remote, source 
-            FROM jobs WHERE id = ?",
-        )
-        .map_err(|e| e.to_string())?;
-
-    let job = stmt
-        .query_row(params![job_id], |row| {
-            Ok(JobResult {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                company: row.get(2)?,
-                location: row.get(3)?,
-                job_type: row.get(4)?,
-                salary_min: row.get(5)?,
-                salary_max: row.get(6)?,
-                description: row.get(7)?,
-                url: row.get(8)?,
-                posted_at: row.get(9)?,
-                experience_level: row.get(10)?,
-                skills: serde_json::from_str(&row.get::<_, String>(11)?).unwrap_or_default(),
-                remote: row.get(12)?,
-                source: row.get(13)?,
-            })
-        })
-        .ok();
-
-    Ok(job)
-} 
\ No newline at end of file
+    state
+        .db
+        .read(move |conn| find_job(conn, job_id))
+        .await
+        .map_err(|e| e.to_string())
+}
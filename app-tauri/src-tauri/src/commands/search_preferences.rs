@@ -0,0 +1,193 @@
+use crate::{
+    error::AppError,
+    models::{KeywordWeight, SearchCategory, SearchPreference},
+    AppState,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use tauri::State;
+
+fn validate_preference(preference: &SearchPreference) -> Result<(), AppError> {
+    if preference.name.trim().is_empty() {
+        return Err(AppError::Validation("Le nom de la préférence de recherche est requis".to_string()));
+    }
+    for category in &preference.categories {
+        if category.name.trim().is_empty() {
+            return Err(AppError::Validation("Le nom d'une catégorie est requis".to_string()));
+        }
+        if let Some(priority) = category.priority.filter(|priority| !(1..=10).contains(priority)) {
+            return Err(AppError::Validation(format!(
+                "Priorité invalide pour « {} » : {} (entre 1 et 10)",
+                category.name.trim(),
+                priority
+            )));
+        }
+        for keyword in &category.keywords {
+            if keyword.keyword.trim().is_empty() {
+                return Err(AppError::Validation("Un mot-clé ne peut pas être vide".to_string()));
+            }
+            // Poids nul : mot-clé seulement requis, sans effet sur le score
+            if keyword.weight < 0 {
+                return Err(AppError::Validation(format!(
+                    "Le poids du mot-clé « {} » doit être positif",
+                    keyword.keyword.trim()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Préférences du profil avec leurs catégories et mots-clés pondérés
+pub fn load_search_preferences(conn: &Connection, user_id: i64) -> Result<Vec<SearchPreference>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT k.category_id, k.keyword, k.weight, k.required
+         FROM keywords k
+         JOIN search_categories c ON c.id = k.category_id
+         JOIN search_preferences p ON p.id = c.preference_id
+         WHERE p.user_id = ?
+         ORDER BY k.id",
+    )?;
+    let mut keywords: HashMap<i64, Vec<KeywordWeight>> = HashMap::new();
+    for row in stmt.query_map([user_id], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            KeywordWeight {
+                keyword: row.get(1)?,
+                weight: row.get(2)?,
+                required: row.get(3)?,
+            },
+        ))
+    })? {
+        let (category_id, keyword) = row?;
+        keywords.entry(category_id).or_default().push(keyword);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT c.preference_id, c.id, c.name, c.priority
+         FROM search_categories c
+         JOIN search_preferences p ON p.id = c.preference_id
+         WHERE p.user_id = ?
+         ORDER BY c.id",
+    )?;
+    let mut categories: HashMap<i64, Vec<SearchCategory>> = HashMap::new();
+    for row in stmt.query_map([user_id], |row| {
+        let id: i64 = row.get(1)?;
+        Ok((
+            row.get::<_, i64>(0)?,
+            SearchCategory {
+                id,
+                name: row.get(2)?,
+                keywords: Vec::new(),
+                priority: row.get(3)?,
+            },
+        ))
+    })? {
+        let (preference_id, mut category) = row?;
+        category.keywords = keywords.remove(&category.id).unwrap_or_default();
+        categories.entry(preference_id).or_default().push(category);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, is_active, last_used FROM search_preferences WHERE user_id = ? ORDER BY id",
+    )?;
+    let preferences = stmt
+        .query_map([user_id], |row| {
+            let id: i64 = row.get(0)?;
+            Ok(SearchPreference {
+                id,
+                name: row.get(1)?,
+                is_active: row.get(2)?,
+                categories: categories.remove(&id).unwrap_or_default(),
+                last_used: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(preferences)
+}
+
+// Crée la préférence (`id` à 0) ou remplace celle du profil, catégories et
+// mots-clés compris. Renvoie l'identifiant enregistré
+pub fn save_search_preference(
+    conn: &Connection,
+    user_id: i64,
+    preference: &SearchPreference,
+) -> Result<i64, AppError> {
+    validate_preference(preference)?;
+
+    let tx = conn.unchecked_transaction()?;
+    let id = if preference.id > 0 {
+        let owned = tx
+            .query_row(
+                "SELECT 1 FROM search_preferences WHERE id = ? AND user_id = ?",
+                [preference.id, user_id],
+                |_| Ok(()),
+            )
+            .optional()?;
+        if owned.is_none() {
+            return Err(AppError::NotFound(format!("Préférence de recherche {} introuvable", preference.id)));
+        }
+        tx.execute(
+            "UPDATE search_preferences SET name = ?, is_active = ?, last_used = ? WHERE id = ?",
+            params![preference.name.trim(), preference.is_active, preference.last_used, preference.id],
+        )?;
+        tx.execute(
+            "DELETE FROM keywords WHERE category_id IN (SELECT id FROM search_categories WHERE preference_id = ?)",
+            [preference.id],
+        )?;
+        tx.execute("DELETE FROM search_categories WHERE preference_id = ?", [preference.id])?;
+        preference.id
+    } else {
+        tx.execute(
+            "INSERT INTO search_preferences (user_id, name, is_active, last_used) VALUES (?, ?, ?, ?)",
+            params![user_id, preference.name.trim(), preference.is_active, preference.last_used],
+        )?;
+        tx.last_insert_rowid()
+    };
+
+    for category in &preference.categories {
+        tx.execute(
+            "INSERT INTO search_categories (preference_id, name, priority) VALUES (?, ?, ?)",
+            params![id, category.name.trim(), category.priority],
+        )?;
+        let category_id = tx.last_insert_rowid();
+        for keyword in &category.keywords {
+            tx.execute(
+                "INSERT INTO keywords (category_id, keyword, weight, required) VALUES (?, ?, ?, ?)",
+                params![category_id, keyword.keyword.trim(), keyword.weight, keyword.required.unwrap_or(false)],
+            )?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn get_search_preferences(state: State<'_, AppState>) -> Result<Vec<SearchPreference>, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .read(move |conn| load_search_preferences(conn, user_id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_search_preferences(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    preferences: SearchPreference,
+) -> Result<bool, String> {
+    let user_id = state.user_id();
+    state
+        .db
+        .call(move |conn| save_search_preference(conn, user_id, &preferences))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Les mots-clés pondérés entrent dans les scores de matching : seules les
+    // offres dont le score est périmé sont recalculées
+    state.rescorer.start(app, state.db.clone(), state.operations.clone(), user_id);
+    Ok(true)
+}
@@ -0,0 +1,13 @@
+use crate::skills::{Skill, SkillTaxonomy};
+
+// Référentiel complet, pour l'autocomplétion et les filtres par identifiant
+#[tauri::command]
+pub async fn list_skills() -> Result<Vec<Skill>, String> {
+    Ok(SkillTaxonomy::bundled().skills().to_vec())
+}
+
+// Noms canoniques des compétences saisies, sans doublons
+#[tauri::command]
+pub async fn normalize_skills(skills: Vec<String>) -> Result<Vec<String>, String> {
+    Ok(SkillTaxonomy::bundled().canonicalize(&skills))
+}
@@ -0,0 +1,146 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use crate::error::AppError;
+
+// Au-delà, le fichier n'est vraisemblablement pas un CV
+pub const MAX_CV_SIZE: usize = 20 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CvFormat {
+    Pdf,
+    Docx,
+    Text,
+}
+
+impl CvFormat {
+    pub fn from_path(path: &Path) -> Result<Self, AppError> {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "pdf" => Ok(CvFormat::Pdf),
+            "docx" => Ok(CvFormat::Docx),
+            "txt" | "md" => Ok(CvFormat::Text),
+            "doc" => Err(AppError::Validation(
+                "Format .doc non pris en charge : enregistrez le CV au format .docx ou PDF".to_string(),
+            )),
+            _ => Err(AppError::Validation(format!("Format de CV non pris en charge : {}", path.display()))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CvFormat::Pdf => "pdf",
+            CvFormat::Docx => "docx",
+            CvFormat::Text => "text",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pdf" => Some(CvFormat::Pdf),
+            "docx" => Some(CvFormat::Docx),
+            "text" => Some(CvFormat::Text),
+            _ => None,
+        }
+    }
+}
+
+// Texte brut du CV, une ligne par paragraphe
+pub fn extract_text(bytes: &[u8], format: CvFormat) -> Result<String, AppError> {
+    if bytes.len() > MAX_CV_SIZE {
+        return Err(AppError::Validation("Le fichier du CV est trop volumineux".to_string()));
+    }
+
+    let text = match format {
+        CvFormat::Pdf => extract_pdf_text(bytes)?,
+        CvFormat::Docx => extract_docx_text(bytes)?,
+        CvFormat::Text => String::from_utf8_lossy(bytes).into_owned(),
+    };
+
+    let text = clean_text(&text);
+    if text.is_empty() {
+        // Typiquement un PDF scanné, sans couche texte
+        return Err(AppError::Validation("Aucun texte n'a pu être extrait du CV".to_string()));
+    }
+    Ok(text)
+}
+
+fn extract_pdf_text(bytes: &[u8]) -> Result<String, AppError> {
+    // pdf-extract panique sur certains PDF mal formés plutôt que de renvoyer une erreur
+    std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(bytes))
+        .map_err(|_| AppError::Validation("PDF illisible".to_string()))?
+        .map_err(|e| AppError::Validation(format!("PDF illisible : {}", e)))
+}
+
+fn extract_docx_text(bytes: &[u8]) -> Result<String, AppError> {
+    let invalid = |e: zip::result::ZipError| AppError::Validation(format!("Document DOCX invalide : {}", e));
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(invalid)?;
+    let mut document = String::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(invalid)?
+        .read_to_string(&mut document)?;
+    docx_document_text(&document)
+}
+
+// Parcourt word/document.xml : les paragraphes (w:p) deviennent des lignes,
+// les tabulations et sauts de ligne explicites sont conservés
+fn docx_document_text(document: &str) -> Result<String, AppError> {
+    let invalid = |e: quick_xml::Error| AppError::Validation(format!("Document DOCX invalide : {}", e));
+    let mut reader = Reader::from_str(document);
+
+    let mut text = String::new();
+    let mut in_text = false;
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(start) if start.local_name().as_ref() == b"t" => in_text = true,
+            Event::End(end) => match end.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => text.push('\n'),
+                _ => {}
+            },
+            Event::Empty(empty) => match empty.local_name().as_ref() {
+                b"tab" => text.push('\t'),
+                b"br" | b"cr" | b"p" => text.push('\n'),
+                _ => {}
+            },
+            Event::Text(content) if in_text => {
+                let content = content
+                    .unescape()
+                    .map(|content| content.into_owned())
+                    .unwrap_or_else(|_| String::from_utf8_lossy(&content).into_owned());
+                text.push_str(&content);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(text)
+}
+
+// Espaces normalisés ligne par ligne, au plus une ligne vide consécutive
+fn clean_text(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line
+            .replace(['\u{a0}', '\u{202f}'], " ")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
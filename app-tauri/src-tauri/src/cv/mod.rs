@@ -0,0 +1,11 @@
+// Import natif des CV : extraction du texte (PDF, DOCX) puis analyse des
+// sections pour remplir `CVInfo`
+pub mod extract;
+pub mod parser;
+
+pub use extract::{extract_text, CvFormat};
+pub use parser::{parse_cv, CvSection, CvSectionKind, ParsedCv};
+
+// À incrémenter à chaque amélioration de l'analyse : les CV importés avec une
+// version antérieure peuvent être réanalysés depuis leur texte brut
+pub const CV_PARSER_VERSION: i32 = 1;
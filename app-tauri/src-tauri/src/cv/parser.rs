@@ -0,0 +1,440 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::geo::normalize;
+use crate::models::{CVInfo, Education};
+use crate::skills::SkillTaxonomy;
+
+const MAX_EXPERIENCE_YEARS: i32 = 60;
+const MAX_SKILL_LENGTH: usize = 50;
+const MAX_SKILL_WORDS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CvSectionKind {
+    Summary,
+    Experience,
+    Education,
+    Skills,
+    Certifications,
+    Languages,
+    Projects,
+    Interests,
+    Contact,
+}
+
+// Titres de section reconnus, déjà normalisés (minuscules, sans accents)
+const HEADINGS: &[(CvSectionKind, &[&str])] = &[
+    (
+        CvSectionKind::Summary,
+        &["profil", "profil professionnel", "resume", "a propos", "a propos de moi", "presentation",
+          "summary", "profile", "professional summary", "about me", "objective"],
+    ),
+    (
+        CvSectionKind::Experience,
+        &["experience", "experiences", "experience professionnelle", "experiences professionnelles",
+          "parcours professionnel", "emplois", "work experience", "professional experience", "experience history",
+          "employment", "employment history", "work history"],
+    ),
+    (
+        CvSectionKind::Education,
+        &["formation", "formations", "education", "etudes", "diplomes", "cursus", "parcours academique",
+          "formation et diplomes", "academic background", "education and training"],
+    ),
+    (
+        CvSectionKind::Skills,
+        &["competences", "competences techniques", "competences cles", "savoir faire", "expertises",
+          "technologies", "outils", "skills", "technical skills", "key skills", "core competencies", "tools"],
+    ),
+    (
+        CvSectionKind::Certifications,
+        &["certifications", "certification", "certificats", "accreditations", "licences et certifications",
+          "certificates", "licenses and certifications", "licenses & certifications"],
+    ),
+    (
+        CvSectionKind::Languages,
+        &["langues", "competences linguistiques", "languages", "language skills"],
+    ),
+    (CvSectionKind::Projects, &["projets", "projets personnels", "realisations", "portfolio", "projects"]),
+    (
+        CvSectionKind::Interests,
+        &["centres d interet", "loisirs", "interets", "hobbies", "interests"],
+    ),
+    (
+        CvSectionKind::Contact,
+        &["contact", "coordonnees", "informations personnelles", "personal information", "personal details"],
+    ),
+];
+
+const FRENCH_WORDS: &[&str] = &["et", "de", "des", "le", "la", "les", "du", "au", "aux", "pour", "avec", "chez", "ans"];
+const ENGLISH_WORDS: &[&str] = &["and", "the", "of", "for", "with", "at", "to", "years", "in"];
+
+const DEGREE_WORDS: &[&str] = &[
+    "master", "mastere", "masters", "msc", "mba", "licence", "bachelor", "bachelors", "bsc", "bts", "dut", "deug",
+    "dea", "dess", "doctorat", "doctorate", "phd", "bac", "baccalaureat", "diplome", "degree", "ingenieur",
+];
+const INSTITUTION_WORDS: &[&str] = &[
+    "universite", "university", "ecole", "school", "institut", "institute", "insa", "iut", "lycee", "college",
+    "polytechnique", "faculte", "faculty", "academie", "academy", "campus", "conservatoire",
+];
+const PRESENT_WORDS: &[&str] = &[
+    "present", "presente", "aujourd", "actuel", "actuellement", "now", "current", "currently", "ongoing", "today",
+];
+// Mots qui entourent une période sans rien apprendre d'autre
+const DATE_FILLER_WORDS: &[&str] = &["de", "a", "au", "du", "depuis", "en", "cours", "ce", "jour", "hui", "from", "to", "since"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CvSection {
+    pub kind: CvSectionKind,
+    pub heading: String,
+    pub lines: Vec<String>,
+}
+
+// Résultat de l'analyse du texte d'un CV
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParsedCv {
+    pub language: Option<String>,
+    pub sections: Vec<CvSection>,
+    pub skills: Vec<String>,
+    pub experience_years: Option<i32>,
+    pub education: Vec<Education>,
+    pub certifications: Vec<String>,
+}
+
+impl ParsedCv {
+    pub fn cv_info(&self, path: PathBuf, last_updated: String) -> CVInfo {
+        let non_empty = |values: &Vec<String>| (!values.is_empty()).then(|| values.clone());
+        CVInfo {
+            path,
+            last_updated,
+            skills: non_empty(&self.skills),
+            experience_years: self.experience_years,
+            education: (!self.education.is_empty()).then(|| self.education.clone()),
+            certifications: non_empty(&self.certifications),
+        }
+    }
+
+    fn section_lines(&self, kind: CvSectionKind) -> impl Iterator<Item = &String> {
+        self.sections.iter().filter(move |section| section.kind == kind).flat_map(|section| &section.lines)
+    }
+}
+
+// Analyse le texte brut d'un CV, en français ou en anglais. `today` borne
+// les périodes encore en cours (« depuis 2021 », « 2020 - présent »)
+pub fn parse_cv(text: &str, today: NaiveDate) -> ParsedCv {
+    let mut parsed = ParsedCv {
+        language: detect_language(text),
+        sections: split_sections(text),
+        ..Default::default()
+    };
+    parsed.skills = SkillTaxonomy::bundled().canonicalize(&extract_skills(parsed.section_lines(CvSectionKind::Skills)));
+    parsed.certifications = extract_certifications(parsed.section_lines(CvSectionKind::Certifications));
+    parsed.experience_years = experience_years(parsed.section_lines(CvSectionKind::Experience), today);
+    parsed.education = extract_education(parsed.section_lines(CvSectionKind::Education));
+    parsed
+}
+
+fn detect_language(text: &str) -> Option<String> {
+    let normalized = normalize(text);
+    let (mut french, mut english) = (0, 0);
+    for word in normalized.split(|c: char| !c.is_alphanumeric()) {
+        french += FRENCH_WORDS.contains(&word) as usize;
+        english += ENGLISH_WORDS.contains(&word) as usize;
+    }
+    match french.cmp(&english) {
+        std::cmp::Ordering::Greater => Some("fr".to_string()),
+        std::cmp::Ordering::Less => Some("en".to_string()),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+fn strip_bullet(line: &str) -> &str {
+    line.trim_start_matches(['-', '•', '*', '–', '—', '·', '▪', '◦', '●', '>', '#']).trim()
+}
+
+// Un titre occupe toute la ligne (« EXPÉRIENCE », « Skills: ») ou précède
+// son contenu après deux-points (« Compétences : Rust, SQL »)
+fn heading(line: &str) -> Option<(CvSectionKind, Option<String>)> {
+    let line = strip_bullet(line);
+    let (title, rest) = match line.split_once(':') {
+        Some((title, rest)) => (title, Some(rest.trim()).filter(|rest| !rest.is_empty())),
+        None => (line, None),
+    };
+    let normalized = normalize(title.trim_end_matches(|c: char| !c.is_alphanumeric()));
+    HEADINGS
+        .iter()
+        .find(|(_, titles)| titles.contains(&normalized.as_str()))
+        .map(|(kind, _)| (*kind, rest.map(str::to_string)))
+}
+
+// Les lignes précédant le premier titre (nom, coordonnées) ne forment pas de section
+fn split_sections(text: &str) -> Vec<CvSection> {
+    let mut sections: Vec<CvSection> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        // Dans les compétences, « Languages: Rust, Go » est une catégorie et non un titre
+        let in_skills = sections.last().is_some_and(|section| section.kind == CvSectionKind::Skills);
+        if let Some((kind, rest)) = heading(line).filter(|(_, rest)| !(in_skills && rest.is_some())) {
+            sections.push(CvSection {
+                kind,
+                heading: line.to_string(),
+                lines: rest.into_iter().collect(),
+            });
+        } else if let Some(section) = sections.last_mut() {
+            section.lines.push(line.to_string());
+        }
+    }
+    for section in &mut sections {
+        while section.lines.last().is_some_and(|line| line.is_empty()) {
+            section.lines.pop();
+        }
+    }
+    sections
+}
+
+fn extract_skills<'a>(lines: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut skills = Vec::new();
+    for line in lines {
+        let line = strip_bullet(line);
+        // « Langages : Rust, Go » : la catégorie n'est pas une compétence
+        let line = line.split_once(':').map_or(line, |(_, rest)| rest);
+        for skill in line.split([',', ';', '•', '|', '·']).flat_map(|part| part.split(" - ")).flat_map(|part| part.split(" – ")) {
+            // Le niveau entre parenthèses (« Rust (avancé) ») n'est pas conservé
+            let skill = skill.split('(').next().unwrap_or_default();
+            let skill = strip_bullet(skill).trim_end_matches('.').trim();
+            if skill.is_empty() || skill.len() > MAX_SKILL_LENGTH || skill.split_whitespace().count() > MAX_SKILL_WORDS {
+                continue;
+            }
+            if seen.insert(skill.to_lowercase()) {
+                skills.push(skill.to_string());
+            }
+        }
+    }
+    skills
+}
+
+fn extract_certifications<'a>(lines: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    lines
+        .map(|line| strip_bullet(line).to_string())
+        .filter(|line| line.chars().count() > 1)
+        .filter(|line| seen.insert(line.to_lowercase()))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DateToken {
+    Date { year: i32, month: Option<u32> },
+    Present,
+}
+
+fn month_number(word: &str) -> Option<u32> {
+    let month = match word {
+        "janvier" | "janv" | "jan" | "january" => 1,
+        "fevrier" | "fevr" | "fev" | "feb" | "february" => 2,
+        "mars" | "mar" | "march" => 3,
+        "avril" | "avr" | "apr" | "april" => 4,
+        "mai" | "may" => 5,
+        "juin" | "jun" | "june" => 6,
+        "juillet" | "juil" | "jul" | "july" => 7,
+        "aout" | "aug" | "august" => 8,
+        "septembre" | "sept" | "sep" | "september" => 9,
+        "octobre" | "oct" | "october" => 10,
+        "novembre" | "nov" | "november" => 11,
+        "decembre" | "dec" | "december" => 12,
+        word if word.len() <= 2 => word.parse().ok().filter(|month| (1..=12).contains(month))?,
+        _ => return None,
+    };
+    Some(month)
+}
+
+fn year(word: &str) -> Option<i32> {
+    (word.len() == 4)
+        .then(|| word.parse().ok())
+        .flatten()
+        .filter(|year| (1950..=2100).contains(year))
+}
+
+fn words(line: &str) -> Vec<String> {
+    normalize(line)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// Dates d'une ligne dans l'ordre : « 03/2019 », « mars 2019 », « 2019 », « présent »
+fn date_tokens(line: &str) -> Vec<DateToken> {
+    let words = words(line);
+    let mut tokens = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| words[i].as_str());
+        if let Some(year) = year(word) {
+            tokens.push(DateToken::Date { year, month: previous.and_then(month_number) });
+            // « depuis 2021 », « since March 2021 » : période toujours en cours
+            let opener = i.checked_sub(if previous.and_then(month_number).is_some() { 2 } else { 1 });
+            if opener.is_some_and(|opener| matches!(words[opener].as_str(), "depuis" | "since")) {
+                tokens.push(DateToken::Present);
+            }
+        } else if PRESENT_WORDS.contains(&word.as_str())
+            || (word == "cours" && previous == Some("en"))
+            || (word == "jour" && previous == Some("ce"))
+        {
+            tokens.push(DateToken::Present);
+        }
+    }
+    tokens
+}
+
+fn month_index(year: i32, month: u32) -> i32 {
+    year * 12 + month as i32 - 1
+}
+
+// Périodes [début, fin[ en mois. Une fin au mois près est incluse, une année
+// seule ne l'est pas : « 2019 - 2021 » fait deux ans, « 01/2020 - 12/2020 » un an
+fn periods<'a>(lines: impl Iterator<Item = &'a String>, today: NaiveDate) -> Vec<(i32, i32)> {
+    let now = month_index(today.year(), today.month()) + 1;
+    let mut periods = Vec::new();
+    for line in lines {
+        let tokens = date_tokens(line);
+        let mut i = 0;
+        while i + 1 < tokens.len() {
+            let DateToken::Date { year, month } = tokens[i] else {
+                i += 1;
+                continue;
+            };
+            let start = month_index(year, month.unwrap_or(1));
+            let end = match tokens[i + 1] {
+                DateToken::Present => now,
+                DateToken::Date { year: end_year, month: Some(end_month) } => month_index(end_year, end_month) + 1,
+                DateToken::Date { year: end_year, month: None } if end_year == year => month_index(end_year, 12) + 1,
+                DateToken::Date { year: end_year, month: None } => month_index(end_year, 1),
+            };
+            if start < end && start < now {
+                periods.push((start, end.min(now)));
+            }
+            i += 2;
+        }
+    }
+    periods
+}
+
+// Les périodes qui se chevauchent (deux postes simultanés) ne comptent qu'une fois
+fn experience_years<'a>(lines: impl Iterator<Item = &'a String>, today: NaiveDate) -> Option<i32> {
+    let mut periods = periods(lines, today);
+    if periods.is_empty() {
+        return None;
+    }
+    periods.sort_unstable();
+
+    let mut months = 0;
+    let (mut current_start, mut current_end) = periods[0];
+    for &(start, end) in &periods[1..] {
+        if start <= current_end {
+            current_end = current_end.max(end);
+        } else {
+            months += current_end - current_start;
+            (current_start, current_end) = (start, end);
+        }
+    }
+    months += current_end - current_start;
+    Some((months / 12).min(MAX_EXPERIENCE_YEARS))
+}
+
+fn has_word(text: &str, vocabulary: &[&str]) -> bool {
+    words(text).iter().any(|word| vocabulary.contains(&word.as_str()))
+}
+
+fn is_date_only(part: &str) -> bool {
+    words(part).iter().all(|word| {
+        year(word).is_some()
+            || month_number(word).is_some()
+            || PRESENT_WORDS.contains(&word.as_str())
+            || DATE_FILLER_WORDS.contains(&word.as_str())
+    })
+}
+
+// Une ligne vide, une nouvelle période ou un nouveau diplôme ouvrent une
+// nouvelle entrée
+fn education_entries<'a>(lines: impl Iterator<Item = &'a String>) -> Vec<Vec<&'a str>> {
+    let mut entries: Vec<Vec<&str>> = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let (mut has_dates, mut has_degree) = (false, false);
+    for line in lines.map(|line| strip_bullet(line)) {
+        if line.is_empty() {
+            entries.push(std::mem::take(&mut current));
+            (has_dates, has_degree) = (false, false);
+            continue;
+        }
+        let dates = !date_tokens(line).is_empty();
+        let degree = has_word(line, DEGREE_WORDS);
+        if (dates && has_dates) || (degree && has_degree) {
+            entries.push(std::mem::take(&mut current));
+            (has_dates, has_degree) = (false, false);
+        }
+        current.push(line);
+        has_dates |= dates;
+        has_degree |= degree;
+    }
+    entries.push(current);
+    entries.retain(|entry| !entry.is_empty());
+    entries
+}
+
+// « Diplôme d'ingénieur en informatique » → (« Diplôme d'ingénieur », « informatique »)
+fn split_field(degree: &str) -> (String, String) {
+    for separator in [" en ", " in ", " of ", " de "] {
+        if let Some((degree, field)) = degree.split_once(separator) {
+            return (degree.trim().to_string(), field.trim().to_string());
+        }
+    }
+    (degree.trim().to_string(), String::new())
+}
+
+fn parse_education(lines: &[&str]) -> Option<Education> {
+    let mut years = Vec::new();
+    let mut ongoing = false;
+    for line in lines {
+        for token in date_tokens(line) {
+            match token {
+                DateToken::Date { year, .. } => years.push(year),
+                DateToken::Present => ongoing = true,
+            }
+        }
+    }
+    let start_year = *years.iter().min()?;
+    let end_year = (!ongoing).then(|| *years.iter().max().unwrap_or(&start_year));
+
+    let parts: Vec<&str> = lines
+        .iter()
+        .flat_map(|line| line.split([',', '|', '\t']))
+        .flat_map(|part| part.split(" - "))
+        .flat_map(|part| part.split(" – "))
+        .flat_map(|part| part.split(" — "))
+        .map(|part| part.trim_matches(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | '.')))
+        .filter(|part| !part.is_empty() && !is_date_only(part))
+        .collect();
+
+    let degree = parts.iter().find(|part| has_word(part, DEGREE_WORDS))?;
+    let institution = parts
+        .iter()
+        .find(|part| *part != degree && has_word(part, INSTITUTION_WORDS))
+        .or_else(|| parts.iter().find(|part| *part != degree))?;
+    let (degree, field) = split_field(degree);
+
+    Some(Education {
+        institution: institution.to_string(),
+        degree,
+        field,
+        start_year,
+        end_year,
+    })
+}
+
+// Les entrées sans diplôme, sans établissement ou sans année sont ignorées :
+// le texte brut du CV reste disponible pour les compléter
+fn extract_education<'a>(lines: impl Iterator<Item = &'a String>) -> Vec<Education> {
+    education_entries(lines).iter().filter_map(|entry| parse_education(entry)).collect()
+}
@@ -0,0 +1,154 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_CACHE_CAPACITY: usize = 128;
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(300); // 5 minutes
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CacheStats {
+    pub capacity: usize,
+    pub size: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub invalidations: u64,
+    pub hit_rate: f64,
+}
+
+struct CacheEntry<V> {
+    value: V,
+    inserted_at: Instant,
+    last_used: u64,
+}
+
+struct CacheState<V> {
+    entries: HashMap<String, CacheEntry<V>>,
+    // Ordre d'utilisation : la plus petite clé est la moins récemment utilisée
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+    // Incrémentée à chaque invalidation
+    generation: u64,
+    stats: CacheStats,
+}
+
+// Cache LRU borné avec expiration : au-delà de `capacity` entrées, la moins
+// récemment consultée est évincée
+pub struct LruCache<V> {
+    ttl: Duration,
+    state: Mutex<CacheState<V>>,
+}
+
+impl<V: Clone> LruCache<V> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            ttl,
+            state: Mutex::new(CacheState {
+                entries: HashMap::with_capacity(capacity),
+                recency: BTreeMap::new(),
+                tick: 0,
+                generation: 0,
+                stats: CacheStats {
+                    capacity,
+                    ..CacheStats::default()
+                },
+            }),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<V> {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+
+        let expired = match state.entries.get(key) {
+            Some(entry) => entry.inserted_at.elapsed() >= self.ttl,
+            None => {
+                state.stats.misses += 1;
+                return None;
+            }
+        };
+        if expired {
+            if let Some(entry) = state.entries.remove(key) {
+                state.recency.remove(&entry.last_used);
+            }
+            state.stats.misses += 1;
+            return None;
+        }
+
+        state.tick += 1;
+        let tick = state.tick;
+        let entry = state.entries.get_mut(key)?;
+        state.recency.remove(&entry.last_used);
+        state.recency.insert(tick, key.to_string());
+        entry.last_used = tick;
+        state.stats.hits += 1;
+        Some(entry.value.clone())
+    }
+
+    // Génération courante, à relever avant de lire la base puis à passer à
+    // `insert`
+    pub fn generation(&self) -> u64 {
+        self.state.lock().unwrap().generation
+    }
+
+    // Une valeur lue avant la dernière invalidation est périmée : elle n'est
+    // pas mise en cache
+    pub fn insert(&self, key: String, value: V, generation: u64) {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        if generation != state.generation {
+            return;
+        }
+
+        state.tick += 1;
+        let tick = state.tick;
+        if let Some(previous) = state.entries.remove(&key) {
+            state.recency.remove(&previous.last_used);
+        }
+
+        while state.entries.len() >= state.stats.capacity {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+            state.stats.evictions += 1;
+        }
+
+        state.recency.insert(tick, key.clone());
+        state.entries.insert(
+            key,
+            CacheEntry {
+                value,
+                inserted_at: Instant::now(),
+                last_used: tick,
+            },
+        );
+    }
+
+    // Vide le cache ; appelé après chaque écriture susceptible de changer un résultat
+    pub fn invalidate_all(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.generation += 1;
+        if !state.entries.is_empty() {
+            state.stats.invalidations += 1;
+        }
+        state.entries.clear();
+        state.recency.clear();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let state = self.state.lock().unwrap();
+        let lookups = state.stats.hits + state.stats.misses;
+        CacheStats {
+            size: state.entries.len(),
+            hit_rate: if lookups == 0 {
+                0.0
+            } else {
+                state.stats.hits as f64 / lookups as f64
+            },
+            ..state.stats.clone()
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::database::pool::{ConnectionPool, PoolMetrics, PooledConnection};
+use crate::error::AppError;
+
+// En WAL, un seul écrivain mais des lecteurs concurrents
+const READ_CONNECTIONS: usize = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DbMetrics {
+    pub reader: PoolMetrics,
+    pub writer: PoolMetrics,
+}
+
+// Exécuteur des requêtes rusqlite : chaque closure tourne sur le pool de
+// threads bloquants de Tokio, jamais sur les workers asynchrones
+#[derive(Clone)]
+pub struct Db {
+    reader: ConnectionPool,
+    writer: ConnectionPool,
+}
+
+impl Db {
+    pub fn open(path: &str) -> Result<Self, AppError> {
+        Ok(Self {
+            writer: ConnectionPool::new(path, 1)?,
+            reader: ConnectionPool::new_read_only(path, READ_CONNECTIONS)?,
+        })
+    }
+
+    // Exécute une closure en écriture (connexion unique, sérialisée)
+    pub async fn call<F, T>(&self, f: F) -> Result<T, AppError>
+    where
+        F: FnOnce(&mut Connection) -> Result<T, AppError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let conn = self.writer.get().await?;
+        run_blocking(conn, f).await
+    }
+
+    // Exécute une closure en lecture seule, en parallèle des autres lectures
+    pub async fn read<F, T>(&self, f: F) -> Result<T, AppError>
+    where
+        F: FnOnce(&Connection) -> Result<T, AppError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let conn = self.reader.get().await?;
+        run_blocking(conn, move |conn| f(conn)).await
+    }
+
+    pub fn metrics(&self) -> DbMetrics {
+        DbMetrics {
+            reader: self.reader.metrics(),
+            writer: self.writer.metrics(),
+        }
+    }
+}
+
+async fn run_blocking<F, T>(mut conn: PooledConnection, f: F) -> Result<T, AppError>
+where
+    F: FnOnce(&mut Connection) -> Result<T, AppError> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(move || f(&mut conn))
+        .await
+        .map_err(|e| AppError::Internal(format!("Database task failed: {}", e)))?
+}
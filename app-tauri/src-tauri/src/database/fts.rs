@@ -0,0 +1,115 @@
+// Traduction des mots-clés saisis par l'utilisateur en requêtes FTS5.
+//
+// Syntaxe acceptée :
+//   rust tokio        -> les deux termes doivent apparaître
+//   "chef de projet"  -> expression exacte
+//   dév*              -> préfixe
+//   -java, -"full stack" -> terme ou expression exclus
+//
+// Chaque terme est entouré de guillemets avant d'être transmis à FTS5 : la
+// ponctuation saisie (c++, node.js, AND, NEAR...) ne peut donc jamais être
+// interprétée comme un opérateur ni provoquer d'erreur de syntaxe.
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FtsQuery {
+    // Expression à passer à `MATCH` pour les termes requis
+    pub matching: Option<String>,
+    // Expression `MATCH` des termes exclus, combinés par OR
+    pub excluded: Option<String>,
+}
+
+impl FtsQuery {
+    pub fn is_empty(&self) -> bool {
+        self.matching.is_none() && self.excluded.is_none()
+    }
+}
+
+struct Term {
+    text: String,
+    prefix: bool,
+    negated: bool,
+}
+
+pub fn parse_keywords(input: &str) -> FtsQuery {
+    let terms = tokenize(input);
+
+    let render = |terms: Vec<&Term>, separator: &str| -> Option<String> {
+        if terms.is_empty() {
+            return None;
+        }
+        Some(
+            terms
+                .iter()
+                .map(|term| {
+                    let quoted = format!("\"{}\"", term.text.replace('"', "\"\""));
+                    if term.prefix {
+                        format!("{}*", quoted)
+                    } else {
+                        quoted
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(separator),
+        )
+    };
+
+    FtsQuery {
+        matching: render(terms.iter().filter(|t| !t.negated).collect(), " "),
+        excluded: render(terms.iter().filter(|t| t.negated).collect(), " OR "),
+    }
+}
+
+fn tokenize(input: &str) -> Vec<Term> {
+    let mut terms = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let negated = c == '-';
+        if negated {
+            chars.next();
+        }
+
+        let mut text = String::new();
+        if chars.peek() == Some(&'"') {
+            // Expression entre guillemets, fermante optionnelle
+            chars.next();
+            for c in chars.by_ref() {
+                if c == '"' {
+                    break;
+                }
+                text.push(c);
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+        }
+
+        let prefix = if text.ends_with('*') {
+            text.truncate(text.trim_end_matches('*').len());
+            true
+        } else if chars.peek() == Some(&'*') {
+            chars.next();
+            true
+        } else {
+            false
+        };
+
+        // Un terme sans lettre ni chiffre ("-", "*"...) ne produit aucun jeton FTS5
+        let text = text.trim().to_string();
+        if text.chars().any(char::is_alphanumeric) {
+            terms.push(Term { text, prefix, negated });
+        }
+    }
+
+    terms
+}
@@ -1,4 +1,8 @@
-use rusqlite::{Connection, Result};
+use rusqlite::Connection;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
 
 pub struct Migration {
     pub version: i32,
@@ -7,14 +11,44 @@ pub struct Migration {
     pub down: &'static str,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum MigrationDirection {
+    Up,
+    Down,
+}
+
+// Étape d'un plan de migration, appliquée ou simplement prévue (dry-run)
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStep {
+    pub version: i32,
+    pub description: String,
+    pub direction: MigrationDirection,
+}
+
 impl Migration {
-    pub fn execute(&self, conn: &Connection) -> Result<()> {
-        conn.execute(self.up, [])?;
+    pub fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.up.as_bytes()))
+    }
+
+    pub fn execute(&self, conn: &Connection) -> Result<(), AppError> {
+        // `execute` n'exécute que la première instruction : les migrations
+        // contiennent plusieurs instructions, d'où `execute_batch`.
+        // La migration et son enregistrement sont atomiques.
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(self.up)?;
+        tx.execute(
+            "INSERT INTO schema_version (version, description, checksum) VALUES (?, ?, ?)",
+            rusqlite::params![self.version, self.description, self.checksum()],
+        )?;
+        tx.commit()?;
         Ok(())
     }
 
-    pub fn rollback(&self, conn: &Connection) -> Result<()> {
-        conn.execute(self.down, [])?;
+    pub fn rollback(&self, conn: &Connection) -> Result<(), AppError> {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(self.down)?;
+        tx.execute("DELETE FROM schema_version WHERE version = ?", [self.version])?;
+        tx.commit()?;
         Ok(())
     }
 }
@@ -24,16 +58,23 @@ pub fn get_migrations() -> Vec<Migration> {
         Migration {
             version: 1,
             description: "Création des tables initiales",
-            up: "
-                CREATE TABLE IF NOT EXISTS schema_version (
-                    version INTEGER PRIMARY KEY,
-                    applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-                );
-                
-                INSERT INTO schema_version (version) VALUES (1);
-            ",
+            up: include_str!("../../migrations/001_initial.sql"),
             down: "
-                DROP TABLE IF EXISTS schema_version;
+                DROP TABLE IF EXISTS llm_models;
+                DROP TABLE IF EXISTS llm_providers;
+                DROP TABLE IF EXISTS keywords;
+                DROP TABLE IF EXISTS search_categories;
+                DROP TABLE IF EXISTS search_preferences;
+                DROP TABLE IF EXISTS interviews;
+                DROP TABLE IF EXISTS kanban_cards;
+                DROP TABLE IF EXISTS kanban_columns;
+                DROP TABLE IF EXISTS generated_documents;
+                DROP TABLE IF EXISTS document_templates;
+                DROP TABLE IF EXISTS documents;
+                DROP TABLE IF EXISTS commute_times;
+                DROP TABLE IF EXISTS jobs;
+                DROP TABLE IF EXISTS user_profiles;
+                DROP TABLE IF EXISTS users;
             ",
         },
         Migration {
@@ -41,11 +82,9 @@ pub fn get_migrations() -> Vec<Migration> {
             description: "Ajout de l'index sur l'email des utilisateurs",
             up: "
                 CREATE INDEX IF NOT EXISTS idx_users_email ON users(email);
-                INSERT INTO schema_version (version) VALUES (2);
             ",
             down: "
                 DROP INDEX IF EXISTS idx_users_email;
-                DELETE FROM schema_version WHERE version = 2;
             ",
         },
         Migration {
@@ -53,11 +92,9 @@ pub fn get_migrations() -> Vec<Migration> {
             description: "Ajout de l'index sur le statut des jobs",
             up: "
                 CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status);
-                INSERT INTO schema_version (version) VALUES (3);
             ",
             down: "
                 DROP INDEX IF EXISTS idx_jobs_status;
-                DELETE FROM schema_version WHERE version = 3;
             ",
         },
         Migration {
@@ -145,8 +182,6 @@ pub fn get_migrations() -> Vec<Migration> {
                 BEGIN
                     UPDATE application_notes SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
                 END;
-
-                INSERT INTO schema_version (version) VALUES (4);
             ",
             down: "
                 DROP TRIGGER IF EXISTS update_applications_timestamp;
@@ -163,8 +198,6 @@ pub fn get_migrations() -> Vec<Migration> {
                 DROP TABLE IF EXISTS application_documents;
                 DROP TABLE IF EXISTS application_stages;
                 DROP TABLE IF EXISTS applications;
-                
-                DELETE FROM schema_version WHERE version = 4;
             ",
         },
         Migration {
@@ -176,7 +209,6 @@ pub fn get_migrations() -> Vec<Migration> {
                 CREATE INDEX IF NOT EXISTS idx_jobs_location ON jobs(location);
                 CREATE INDEX IF NOT EXISTS idx_documents_user_id ON documents(user_id);
                 CREATE INDEX IF NOT EXISTS idx_documents_type ON documents(document_type);
-                INSERT INTO schema_version (version) VALUES (5);
             ",
             down: "
                 DROP INDEX IF EXISTS idx_jobs_title;
@@ -184,7 +216,6 @@ pub fn get_migrations() -> Vec<Migration> {
                 DROP INDEX IF EXISTS idx_jobs_location;
                 DROP INDEX IF EXISTS idx_documents_user_id;
                 DROP INDEX IF EXISTS idx_documents_type;
-                DELETE FROM schema_version WHERE version = 5;
             ",
         },
         Migration {
@@ -194,45 +225,891 @@ pub fn get_migrations() -> Vec<Migration> {
                 CREATE INDEX IF NOT EXISTS idx_jobs_user_id ON jobs(user_id);
                 CREATE INDEX IF NOT EXISTS idx_kanban_cards_job_id ON kanban_cards(job_id);
                 CREATE INDEX IF NOT EXISTS idx_kanban_cards_column_id ON kanban_cards(column_id);
-                INSERT INTO schema_version (version) VALUES (6);
             ",
             down: "
                 DROP INDEX IF EXISTS idx_jobs_user_id;
                 DROP INDEX IF EXISTS idx_kanban_cards_job_id;
                 DROP INDEX IF EXISTS idx_kanban_cards_column_id;
-                DELETE FROM schema_version WHERE version = 6;
             ",
-        }
+        },
+        Migration {
+            version: 7,
+            description: "Reprise des index de l'ancienne initialisation de DatabaseOperations",
+            up: "
+                CREATE INDEX IF NOT EXISTS idx_jobs_matching_score ON jobs(matching_score);
+                CREATE INDEX IF NOT EXISTS idx_templates_type ON document_templates(template_type);
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_jobs_matching_score;
+                DROP INDEX IF EXISTS idx_templates_type;
+            ",
+        },
+        Migration {
+            version: 8,
+            description: "Index plein texte FTS5 sur les offres",
+            up: "
+                CREATE VIRTUAL TABLE IF NOT EXISTS jobs_fts USING fts5(
+                    title, company, description, requirements, skills,
+                    content='jobs',
+                    content_rowid='id',
+                    tokenize='unicode61 remove_diacritics 2',
+                    prefix='2 3'
+                );
+
+                -- Les triggers maintiennent l'index à jour pour toute écriture sur jobs
+                CREATE TRIGGER IF NOT EXISTS jobs_fts_ai AFTER INSERT ON jobs BEGIN
+                    INSERT INTO jobs_fts(rowid, title, company, description, requirements, skills)
+                    VALUES (new.id, new.title, new.company, new.description, new.requirements, new.skills);
+                END;
+
+                CREATE TRIGGER IF NOT EXISTS jobs_fts_ad AFTER DELETE ON jobs BEGIN
+                    INSERT INTO jobs_fts(jobs_fts, rowid, title, company, description, requirements, skills)
+                    VALUES ('delete', old.id, old.title, old.company, old.description, old.requirements, old.skills);
+                END;
+
+                CREATE TRIGGER IF NOT EXISTS jobs_fts_au
+                AFTER UPDATE OF title, company, description, requirements, skills ON jobs BEGIN
+                    INSERT INTO jobs_fts(jobs_fts, rowid, title, company, description, requirements, skills)
+                    VALUES ('delete', old.id, old.title, old.company, old.description, old.requirements, old.skills);
+                    INSERT INTO jobs_fts(rowid, title, company, description, requirements, skills)
+                    VALUES (new.id, new.title, new.company, new.description, new.requirements, new.skills);
+                END;
+
+                -- Indexer les offres déjà présentes
+                INSERT INTO jobs_fts(jobs_fts) VALUES ('rebuild');
+            ",
+            down: "
+                DROP TRIGGER IF EXISTS jobs_fts_ai;
+                DROP TRIGGER IF EXISTS jobs_fts_ad;
+                DROP TRIGGER IF EXISTS jobs_fts_au;
+                DROP TABLE IF EXISTS jobs_fts;
+            ",
+        },
+        Migration {
+            version: 9,
+            description: "Coordonnées géographiques des offres et des domiciles",
+            up: "
+                ALTER TABLE jobs ADD COLUMN latitude REAL;
+                ALTER TABLE jobs ADD COLUMN longitude REAL;
+                CREATE INDEX IF NOT EXISTS idx_jobs_coordinates ON jobs(latitude, longitude);
+
+                ALTER TABLE user_profiles ADD COLUMN primary_latitude REAL;
+                ALTER TABLE user_profiles ADD COLUMN primary_longitude REAL;
+                ALTER TABLE user_profiles ADD COLUMN secondary_latitude REAL;
+                ALTER TABLE user_profiles ADD COLUMN secondary_longitude REAL;
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_jobs_coordinates;
+                ALTER TABLE jobs DROP COLUMN latitude;
+                ALTER TABLE jobs DROP COLUMN longitude;
+
+                ALTER TABLE user_profiles DROP COLUMN primary_latitude;
+                ALTER TABLE user_profiles DROP COLUMN primary_longitude;
+                ALTER TABLE user_profiles DROP COLUMN secondary_latitude;
+                ALTER TABLE user_profiles DROP COLUMN secondary_longitude;
+            ",
+        },
+        Migration {
+            version: 10,
+            description: "Recherches sauvegardées et résultats de leur dernière exécution",
+            up: "
+                CREATE TABLE IF NOT EXISTS saved_searches (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    user_id INTEGER NOT NULL,
+                    name TEXT NOT NULL,
+                    criteria TEXT NOT NULL,
+                    is_active BOOLEAN NOT NULL DEFAULT 1,
+                    interval_minutes INTEGER NOT NULL DEFAULT 60,
+                    last_run_at TEXT,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    FOREIGN KEY (user_id) REFERENCES users(id)
+                );
+
+                -- Empreinte de chaque offre trouvée lors de la dernière exécution
+                CREATE TABLE IF NOT EXISTS saved_search_results (
+                    saved_search_id INTEGER NOT NULL,
+                    job_id INTEGER NOT NULL,
+                    fingerprint TEXT NOT NULL,
+                    first_seen_at TEXT NOT NULL,
+                    PRIMARY KEY (saved_search_id, job_id),
+                    FOREIGN KEY (saved_search_id) REFERENCES saved_searches(id) ON DELETE CASCADE
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_saved_searches_user_id ON saved_searches(user_id);
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_saved_searches_user_id;
+                DROP TABLE IF EXISTS saved_search_results;
+                DROP TABLE IF EXISTS saved_searches;
+            ",
+        },
+        Migration {
+            version: 11,
+            description: "Identifiant des offres chez leur source pour la collecte",
+            up: "
+                ALTER TABLE jobs ADD COLUMN external_id TEXT;
+                CREATE INDEX IF NOT EXISTS idx_jobs_source_external_id ON jobs(source, external_id);
+                CREATE INDEX IF NOT EXISTS idx_jobs_url ON jobs(url);
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_jobs_url;
+                DROP INDEX IF EXISTS idx_jobs_source_external_id;
+                ALTER TABLE jobs DROP COLUMN external_id;
+            ",
+        },
+        Migration {
+            version: 12,
+            description: "Date limite de candidature des offres",
+            up: "
+                ALTER TABLE jobs ADD COLUMN valid_through TEXT;
+            ",
+            down: "
+                ALTER TABLE jobs DROP COLUMN valid_through;
+            ",
+        },
+        Migration {
+            version: 13,
+            description: "Paires d'offres candidates au dédoublonnage",
+            up: "
+                -- job_id < duplicate_job_id ; une paire ignorée ou fusionnée
+                -- n'est plus proposée lors des détections suivantes
+                CREATE TABLE IF NOT EXISTS duplicate_candidates (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    job_id INTEGER NOT NULL,
+                    duplicate_job_id INTEGER NOT NULL,
+                    score REAL NOT NULL,
+                    title_similarity REAL NOT NULL,
+                    description_similarity REAL,
+                    reason TEXT NOT NULL,
+                    status TEXT NOT NULL DEFAULT 'pending',
+                    merged_into INTEGER,
+                    detected_at TEXT NOT NULL,
+                    resolved_at TEXT,
+                    UNIQUE (job_id, duplicate_job_id)
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_duplicate_candidates_status ON duplicate_candidates(status);
+                CREATE INDEX IF NOT EXISTS idx_duplicate_candidates_duplicate ON duplicate_candidates(duplicate_job_id);
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_duplicate_candidates_duplicate;
+                DROP INDEX IF EXISTS idx_duplicate_candidates_status;
+                DROP TABLE IF EXISTS duplicate_candidates;
+            ",
+        },
+        Migration {
+            version: 14,
+            description: "Critères de matching du profil et détail des scores par offre",
+            up: "
+                ALTER TABLE user_profiles ADD COLUMN skills TEXT NOT NULL DEFAULT '[]';
+                ALTER TABLE user_profiles ADD COLUMN experience_years INTEGER;
+                ALTER TABLE user_profiles ADD COLUMN job_preferences TEXT;
+
+                -- Détail du dernier calcul de `jobs.matching_score`, facteur par facteur
+                CREATE TABLE IF NOT EXISTS job_match_scores (
+                    user_id INTEGER NOT NULL,
+                    job_id INTEGER NOT NULL,
+                    score REAL NOT NULL,
+                    breakdown TEXT NOT NULL,
+                    computed_at TEXT NOT NULL,
+                    PRIMARY KEY (user_id, job_id),
+                    FOREIGN KEY (user_id) REFERENCES users(id),
+                    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+                );
+            ",
+            down: "
+                DROP TABLE IF EXISTS job_match_scores;
+                ALTER TABLE user_profiles DROP COLUMN job_preferences;
+                ALTER TABLE user_profiles DROP COLUMN experience_years;
+                ALTER TABLE user_profiles DROP COLUMN skills;
+            ",
+        },
+        Migration {
+            version: 15,
+            description: "Version du profil utilisée pour chaque score de matching",
+            up: "
+                ALTER TABLE job_match_scores ADD COLUMN profile_version TEXT;
+            ",
+            down: "
+                ALTER TABLE job_match_scores DROP COLUMN profile_version;
+            ",
+        },
+        Migration {
+            version: 16,
+            description: "Formations, certifications et préférences du profil",
+            up: "
+                -- Listes et préférences stockées en JSON, comme `skills` et `job_preferences`
+                ALTER TABLE user_profiles ADD COLUMN education TEXT;
+                ALTER TABLE user_profiles ADD COLUMN certifications TEXT;
+                ALTER TABLE user_profiles ADD COLUMN preferences TEXT;
+            ",
+            down: "
+                ALTER TABLE user_profiles DROP COLUMN preferences;
+                ALTER TABLE user_profiles DROP COLUMN certifications;
+                ALTER TABLE user_profiles DROP COLUMN education;
+            ",
+        },
+        Migration {
+            version: 17,
+            description: "CV importés avec leur texte brut",
+            up: "
+                -- Le texte brut est conservé pour réanalyser le CV quand l'analyse s'améliore
+                CREATE TABLE IF NOT EXISTS cv_documents (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    user_id INTEGER NOT NULL,
+                    file_path TEXT NOT NULL,
+                    format TEXT NOT NULL,
+                    raw_text TEXT NOT NULL,
+                    parsed TEXT NOT NULL,
+                    parser_version INTEGER NOT NULL,
+                    imported_at TEXT NOT NULL,
+                    parsed_at TEXT NOT NULL,
+                    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+                );
+                CREATE INDEX IF NOT EXISTS idx_cv_documents_user ON cv_documents(user_id, imported_at);
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_cv_documents_user;
+                DROP TABLE IF EXISTS cv_documents;
+            ",
+        },
+        Migration {
+            version: 18,
+            description: "Référentiel de compétences et synonymes",
+            up: "
+                -- Rempli au démarrage depuis le référentiel embarqué (data/skills.csv)
+                CREATE TABLE IF NOT EXISTS skills (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL UNIQUE,
+                    category TEXT NOT NULL,
+                    parent_id TEXT,
+                    FOREIGN KEY (parent_id) REFERENCES skills(id) ON DELETE SET NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_skills_parent ON skills(parent_id);
+
+                -- Clés normalisées (voir `skill_key`) du nom, de l'identifiant et des synonymes
+                CREATE TABLE IF NOT EXISTS skill_aliases (
+                    alias TEXT PRIMARY KEY,
+                    skill_id TEXT NOT NULL,
+                    FOREIGN KEY (skill_id) REFERENCES skills(id) ON DELETE CASCADE
+                );
+            ",
+            down: "
+                DROP TABLE IF EXISTS skill_aliases;
+                DROP INDEX IF EXISTS idx_skills_parent;
+                DROP TABLE IF EXISTS skills;
+            ",
+        },
+        Migration {
+            version: 19,
+            description: "Profils multiples : dernier profil actif",
+            up: "
+                -- Le profil utilisé le plus récemment redevient actif au démarrage
+                ALTER TABLE users ADD COLUMN last_active_at TIMESTAMP;
+            ",
+            down: "
+                ALTER TABLE users DROP COLUMN last_active_at;
+            ",
+        },
+        Migration {
+            version: 20,
+            description: "Statuts de candidature contrôlés et historique des changements",
+            up: "
+                -- Anciens statuts libres ramenés aux statuts reconnus
+                UPDATE applications SET status = CASE
+                    WHEN lower(trim(status)) IN ('draft', 'applied', 'screening', 'interview', 'offer',
+                        'accepted', 'rejected', 'withdrawn', 'ghosted') THEN lower(trim(status))
+                    WHEN lower(trim(status)) IN ('interviewing', 'interview_scheduled') THEN 'interview'
+                    WHEN lower(trim(status)) IN ('offered', 'offer_received') THEN 'offer'
+                    WHEN applied_at IS NOT NULL THEN 'applied'
+                    ELSE 'draft'
+                END
+                WHERE status NOT IN ('draft', 'applied', 'screening', 'interview', 'offer',
+                    'accepted', 'rejected', 'withdrawn', 'ghosted');
+
+                CREATE TRIGGER IF NOT EXISTS check_applications_status_insert
+                BEFORE INSERT ON applications
+                WHEN NEW.status NOT IN ('draft', 'applied', 'screening', 'interview', 'offer',
+                    'accepted', 'rejected', 'withdrawn', 'ghosted')
+                BEGIN
+                    SELECT RAISE(ABORT, 'Statut de candidature inconnu');
+                END;
+
+                CREATE TRIGGER IF NOT EXISTS check_applications_status_update
+                BEFORE UPDATE OF status ON applications
+                WHEN NEW.status NOT IN ('draft', 'applied', 'screening', 'interview', 'offer',
+                    'accepted', 'rejected', 'withdrawn', 'ghosted')
+                BEGIN
+                    SELECT RAISE(ABORT, 'Statut de candidature inconnu');
+                END;
+
+                CREATE TABLE IF NOT EXISTS application_status_history (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    application_id INTEGER NOT NULL,
+                    from_status TEXT,
+                    to_status TEXT NOT NULL,
+                    reason TEXT,
+                    changed_at TIMESTAMP NOT NULL,
+                    FOREIGN KEY (application_id) REFERENCES applications(id) ON DELETE CASCADE
+                );
+                CREATE INDEX IF NOT EXISTS idx_application_status_history_application
+                    ON application_status_history(application_id, changed_at);
+
+                -- Statut actuel des candidatures existantes comme point de départ
+                INSERT INTO application_status_history (application_id, from_status, to_status, changed_at)
+                SELECT id, NULL, status, COALESCE(updated_at, created_at, CURRENT_TIMESTAMP) FROM applications;
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_application_status_history_application;
+                DROP TABLE IF EXISTS application_status_history;
+                DROP TRIGGER IF EXISTS check_applications_status_update;
+                DROP TRIGGER IF EXISTS check_applications_status_insert;
+            ",
+        },
+        Migration {
+            version: 21,
+            description: "Suppression en cascade des étapes, documents et notes de candidature",
+            up: "
+                -- Étapes, documents et notes suivent la suppression de leur candidature.
+                -- SQLite ne modifie pas une clé étrangère existante : tables reconstruites
+                CREATE TABLE application_stages_rebuild (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    application_id INTEGER NOT NULL,
+                    stage_type TEXT NOT NULL,
+                    scheduled_at TIMESTAMP,
+                    completed_at TIMESTAMP,
+                    notes TEXT,
+                    outcome TEXT,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    FOREIGN KEY (application_id) REFERENCES applications(id) ON DELETE CASCADE
+                );
+                INSERT INTO application_stages_rebuild SELECT * FROM application_stages;
+                DROP TABLE application_stages;
+                ALTER TABLE application_stages_rebuild RENAME TO application_stages;
+
+                CREATE TABLE application_documents_rebuild (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    application_id INTEGER NOT NULL,
+                    document_type TEXT NOT NULL,
+                    file_path TEXT,
+                    content TEXT,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    FOREIGN KEY (application_id) REFERENCES applications(id) ON DELETE CASCADE
+                );
+                INSERT INTO application_documents_rebuild SELECT * FROM application_documents;
+                DROP TABLE application_documents;
+                ALTER TABLE application_documents_rebuild RENAME TO application_documents;
+
+                CREATE TABLE application_notes_rebuild (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    application_id INTEGER NOT NULL,
+                    content TEXT NOT NULL,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    FOREIGN KEY (application_id) REFERENCES applications(id) ON DELETE CASCADE
+                );
+                INSERT INTO application_notes_rebuild SELECT * FROM application_notes;
+                DROP TABLE application_notes;
+                ALTER TABLE application_notes_rebuild RENAME TO application_notes;
+
+                -- Index et triggers disparus avec les anciennes tables
+                CREATE INDEX IF NOT EXISTS idx_application_stages_type ON application_stages(stage_type);
+                CREATE INDEX IF NOT EXISTS idx_application_documents_type ON application_documents(document_type);
+                CREATE INDEX IF NOT EXISTS idx_application_stages_application
+                    ON application_stages(application_id, scheduled_at);
+                CREATE INDEX IF NOT EXISTS idx_application_documents_application
+                    ON application_documents(application_id);
+                CREATE INDEX IF NOT EXISTS idx_application_notes_application
+                    ON application_notes(application_id);
+
+                CREATE TRIGGER IF NOT EXISTS update_application_stages_timestamp
+                AFTER UPDATE ON application_stages
+                BEGIN
+                    UPDATE application_stages SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+                END;
+
+                CREATE TRIGGER IF NOT EXISTS update_application_documents_timestamp
+                AFTER UPDATE ON application_documents
+                BEGIN
+                    UPDATE application_documents SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+                END;
+
+                CREATE TRIGGER IF NOT EXISTS update_application_notes_timestamp
+                AFTER UPDATE ON application_notes
+                BEGIN
+                    UPDATE application_notes SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+                END;
+            ",
+            down: "
+                CREATE TABLE application_stages_rebuild (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    application_id INTEGER NOT NULL,
+                    stage_type TEXT NOT NULL,
+                    scheduled_at TIMESTAMP,
+                    completed_at TIMESTAMP,
+                    notes TEXT,
+                    outcome TEXT,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    FOREIGN KEY (application_id) REFERENCES applications(id)
+                );
+                INSERT INTO application_stages_rebuild SELECT * FROM application_stages;
+                DROP TABLE application_stages;
+                ALTER TABLE application_stages_rebuild RENAME TO application_stages;
+
+                CREATE TABLE application_documents_rebuild (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    application_id INTEGER NOT NULL,
+                    document_type TEXT NOT NULL,
+                    file_path TEXT,
+                    content TEXT,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    FOREIGN KEY (application_id) REFERENCES applications(id)
+                );
+                INSERT INTO application_documents_rebuild SELECT * FROM application_documents;
+                DROP TABLE application_documents;
+                ALTER TABLE application_documents_rebuild RENAME TO application_documents;
+
+                CREATE TABLE application_notes_rebuild (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    application_id INTEGER NOT NULL,
+                    content TEXT NOT NULL,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    FOREIGN KEY (application_id) REFERENCES applications(id)
+                );
+                INSERT INTO application_notes_rebuild SELECT * FROM application_notes;
+                DROP TABLE application_notes;
+                ALTER TABLE application_notes_rebuild RENAME TO application_notes;
+
+                -- Index et triggers disparus avec les anciennes tables
+                CREATE INDEX IF NOT EXISTS idx_application_stages_type ON application_stages(stage_type);
+                CREATE INDEX IF NOT EXISTS idx_application_documents_type ON application_documents(document_type);
+
+                CREATE TRIGGER IF NOT EXISTS update_application_stages_timestamp
+                AFTER UPDATE ON application_stages
+                BEGIN
+                    UPDATE application_stages SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+                END;
+
+                CREATE TRIGGER IF NOT EXISTS update_application_documents_timestamp
+                AFTER UPDATE ON application_documents
+                BEGIN
+                    UPDATE application_documents SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+                END;
+
+                CREATE TRIGGER IF NOT EXISTS update_application_notes_timestamp
+                AFTER UPDATE ON application_notes
+                BEGIN
+                    UPDATE application_notes SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+                END;
+            ",
+        },
+        Migration {
+            version: 22,
+            description: "Rappels de suivi des candidatures et des cartes du Kanban",
+            up: "
+                -- Les colonnes du Kanban appartiennent à un profil : les relances
+                -- d'une carte sont adressées au propriétaire de sa colonne
+                ALTER TABLE kanban_columns ADD COLUMN user_id INTEGER NOT NULL DEFAULT 1;
+                CREATE INDEX IF NOT EXISTS idx_kanban_columns_user ON kanban_columns(user_id, position);
+
+                -- Un rappel par échéance d'une source (étape, carte ou candidature) ;
+                -- l'état (reporté, écarté) survit au redémarrage de l'application
+                CREATE TABLE IF NOT EXISTS reminders (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    user_id INTEGER NOT NULL,
+                    kind TEXT NOT NULL,
+                    source_id INTEGER NOT NULL,
+                    job_id INTEGER NOT NULL,
+                    title TEXT NOT NULL,
+                    due_at TIMESTAMP NOT NULL,
+                    status TEXT NOT NULL DEFAULT 'pending',
+                    snoozed_until TIMESTAMP,
+                    notified_at TIMESTAMP,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    UNIQUE (kind, source_id, due_at),
+                    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+                );
+                CREATE INDEX IF NOT EXISTS idx_reminders_user ON reminders(user_id, due_at);
+                CREATE INDEX IF NOT EXISTS idx_reminders_status ON reminders(status, due_at);
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_reminders_status;
+                DROP INDEX IF EXISTS idx_reminders_user;
+                DROP TABLE IF EXISTS reminders;
+                DROP INDEX IF EXISTS idx_kanban_columns_user;
+                ALTER TABLE kanban_columns DROP COLUMN user_id;
+            ",
+        },
+        Migration {
+            version: 23,
+            description: "Identifiant d'agenda des étapes importées depuis un fichier iCalendar",
+            up: "
+                -- UID de l'invitation d'origine : un nouvel import la met à jour
+                -- et l'export le réutilise pour que l'agenda ne la duplique pas
+                ALTER TABLE application_stages ADD COLUMN calendar_uid TEXT;
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_application_stages_calendar_uid
+                    ON application_stages(calendar_uid) WHERE calendar_uid IS NOT NULL;
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_application_stages_calendar_uid;
+                ALTER TABLE application_stages DROP COLUMN calendar_uid;
+            ",
+        },
+        Migration {
+            version: 24,
+            description: "Préférences de recherche propres à chaque profil",
+            up: "
+                -- Les préférences existantes reviennent au profil local (id 1) ;
+                -- leurs mots-clés pondérés n'entrent que dans ses scores
+                ALTER TABLE search_preferences ADD COLUMN user_id INTEGER NOT NULL DEFAULT 1;
+                CREATE INDEX IF NOT EXISTS idx_search_preferences_user ON search_preferences(user_id, is_active);
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_search_preferences_user;
+                ALTER TABLE search_preferences DROP COLUMN user_id;
+            ",
+        },
+        Migration {
+            version: 25,
+            description: "UID d'invitation unique par candidature",
+            up: "
+                -- Deux profils peuvent importer la même invitation : l'unicité
+                -- du UID ne vaut qu'au sein d'une candidature
+                DROP INDEX IF EXISTS idx_application_stages_calendar_uid;
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_application_stages_calendar_uid
+                    ON application_stages(application_id, calendar_uid) WHERE calendar_uid IS NOT NULL;
+            ",
+            down: "
+                DROP INDEX IF EXISTS idx_application_stages_calendar_uid;
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_application_stages_calendar_uid
+                    ON application_stages(calendar_uid) WHERE calendar_uid IS NOT NULL;
+            ",
+        },
     ]
 }
 
-pub fn run_migrations(conn: &Connection) -> Result<()> {
-    // Vérifier si la table schema_version existe
-    let table_exists: bool = conn
-        .query_row(
-            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type='table' AND name='schema_version')",
-            [],
-            |row| row.get(0),
-        )
-        .unwrap_or(false);
+pub fn run_migrations(conn: &Connection) -> Result<(), AppError> {
+    set_aside_legacy_tables(conn)?;
+    let migrations = get_migrations();
+    let latest = migrations.last().map(|m| m.version).unwrap_or(0);
+    apply_migrations(conn, &migrations, latest, false)?;
+    restore_legacy_rows(conn)?;
+    Ok(())
+}
+
+// Préfixe des tables d'une base créée avant le runner de migrations
+const LEGACY_PREFIX: &str = "legacy_";
+
+// Anciens noms de tables ou de colonnes : (table, ancien nom, nom canonique)
+const LEGACY_TABLE_RENAMES: &[(&str, &str)] = &[("user_profile", "user_profiles")];
+const LEGACY_COLUMN_RENAMES: &[(&str, &str, &str)] = &[
+    ("user_profiles", "location", "primary_home"),
+    ("kanban_columns", "limit", "card_limit"),
+    ("documents", "name", "title"),
+    ("documents", "type", "document_type"),
+    ("documents", "path", "file_path"),
+];
 
-    if !table_exists {
-        // Exécuter la première migration qui crée la table schema_version
-        let first_migration = get_migrations().first().unwrap();
-        first_migration.execute(conn)?;
+// Une base sans `schema_version` mais avec des tables a été créée par l'un des
+// anciens chemins d'initialisation, avec un schéma divergent (`user_profiles`
+// sans `user_id`...). Ses tables sont renommées `legacy_*` pour laisser les
+// migrations créer le schéma canonique ; leurs lignes sont reprises ensuite
+fn set_aside_legacy_tables(conn: &Connection) -> Result<(), AppError> {
+    if schema_version_exists(conn)? {
+        return Ok(());
+    }
+    let tables = user_tables(conn)?
+        .into_iter()
+        .filter(|table| !table.starts_with(LEGACY_PREFIX))
+        .collect::<Vec<_>>();
+    if tables.is_empty() {
+        return Ok(());
     }
+    log::warn!("Base antérieure aux migrations : {} table(s) converties au schéma canonique", tables.len());
 
-    // Récupérer la version actuelle
-    let current_version: i32 = conn
-        .query_row("SELECT MAX(version) FROM schema_version", [], |row| row.get(0))
-        .unwrap_or(0);
+    let tx = conn.unchecked_transaction()?;
+    // Les index, vues et triggers gardent leur nom après un renommage et
+    // empêcheraient les migrations de créer les leurs
+    let objects = {
+        let mut stmt = tx.prepare(
+            "SELECT type, name FROM sqlite_master
+             WHERE type IN ('index', 'view', 'trigger') AND sql IS NOT NULL",
+        )?;
+        let objects = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        objects
+    };
+    for (kind, name) in objects {
+        tx.execute_batch(&format!("DROP {} IF EXISTS \"{}\"", kind.to_uppercase(), name))?;
+    }
+    for table in &tables {
+        tx.execute_batch(&format!("ALTER TABLE \"{0}\" RENAME TO \"{1}{0}\"", table, LEGACY_PREFIX))?;
+    }
+    tx.commit()?;
+    Ok(())
+}
 
-    // Exécuter les migrations en attente
-    for migration in get_migrations() {
-        if migration.version > current_version {
-            migration.execute(conn)?;
+// Recopie les lignes des tables `legacy_*` dans les tables canoniques, par
+// colonnes communes. Une colonne devenue obligatoire reçoit sa valeur par
+// défaut (l'utilisateur local pour `user_id`). Une table reprise est
+// supprimée ; une table sans équivalent ou en échec reste pour une reprise
+// manuelle
+fn restore_legacy_rows(conn: &Connection) -> Result<(), AppError> {
+    let tables = user_tables(conn)?;
+    let legacy: Vec<&String> = tables.iter().filter(|table| table.starts_with(LEGACY_PREFIX)).collect();
+    if legacy.is_empty() {
+        return Ok(());
+    }
+
+    // Les anciennes bases contiennent des références orphelines
+    let foreign_keys: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+    for table in legacy {
+        let name = &table[LEGACY_PREFIX.len()..];
+        let target = LEGACY_TABLE_RENAMES
+            .iter()
+            .find(|(old, _)| *old == name)
+            .map_or(name, |(_, new)| *new);
+        if !tables.iter().any(|t| t == target) {
+            continue;
+        }
+        if let Err(e) = copy_legacy_table(conn, table, target) {
+            log::warn!("Table {} non reprise, conservée pour une reprise manuelle : {}", table, e);
+        }
+    }
+    if foreign_keys {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    }
+    Ok(())
+}
+
+fn copy_legacy_table(conn: &Connection, legacy: &str, target: &str) -> Result<(), AppError> {
+    let source_columns: Vec<String> = table_columns(conn, legacy)?.into_iter().map(|column| column.name).collect();
+    let mut columns = Vec::new();
+    let mut values = Vec::new();
+    for column in table_columns(conn, target)? {
+        let source = LEGACY_COLUMN_RENAMES
+            .iter()
+            .find(|(table, _, new)| *table == target && *new == column.name)
+            .map(|(_, old, _)| old.to_string())
+            .filter(|old| source_columns.contains(old))
+            .or_else(|| source_columns.iter().find(|c| **c == column.name).cloned());
+        let fallback = column.default.clone().unwrap_or_else(|| {
+            if column.name == "user_id" {
+                // Utilisateur local, seul utilisateur des anciennes bases
+                "1".to_string()
+            } else if ["INT", "REAL", "NUM", "BOOL"].iter().any(|t| column.sql_type.to_uppercase().contains(t)) {
+                "0".to_string()
+            } else {
+                "''".to_string()
+            }
+        });
+        match source {
+            // Listes JSON (compétences...) : une ancienne valeur en texte libre est abandonnée
+            Some(source) if fallback == "'[]'" => {
+                values.push(format!("CASE WHEN json_valid(\"{0}\") THEN \"{0}\" ELSE '[]' END", source))
+            }
+            Some(source) if column.not_null => values.push(format!("COALESCE(\"{}\", {})", source, fallback)),
+            Some(source) => values.push(format!("\"{}\"", source)),
+            None if column.not_null && column.default.is_none() => values.push(fallback),
+            None => continue,
         }
+        columns.push(format!("\"{}\"", column.name));
     }
 
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(&format!(
+        "INSERT OR IGNORE INTO \"{}\" ({}) SELECT {} FROM \"{}\";
+         DROP TABLE \"{}\";",
+        target,
+        columns.join(", "),
+        values.join(", "),
+        legacy,
+        legacy
+    ))?;
+    tx.commit()?;
     Ok(())
-} 
\ No newline at end of file
+}
+
+struct TableColumn {
+    name: String,
+    sql_type: String,
+    not_null: bool,
+    default: Option<String>,
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<TableColumn>, AppError> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info(\"{}\")", table))?;
+    let columns = stmt
+        .query_map([], |row| {
+            Ok(TableColumn {
+                name: row.get(1)?,
+                sql_type: row.get(2)?,
+                not_null: row.get(3)?,
+                default: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(columns)
+}
+
+// Tables ordinaires, hors tables internes de SQLite et tables virtuelles (FTS)
+fn user_tables(conn: &Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM sqlite_master
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND sql NOT LIKE 'CREATE VIRTUAL%'
+         ORDER BY rowid",
+    )?;
+    let tables = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(tables)
+}
+
+// Amène le schéma à `target` en appliquant ou en annulant les migrations
+pub fn migrate_to(conn: &Connection, target: i32, dry_run: bool) -> Result<Vec<MigrationStep>, AppError> {
+    apply_migrations(conn, &get_migrations(), target, dry_run)
+}
+
+// Liste les migrations en attente sans rien modifier
+pub fn pending_migrations(conn: &Connection) -> Result<Vec<MigrationStep>, AppError> {
+    let migrations = get_migrations();
+    let latest = migrations.last().map(|m| m.version).unwrap_or(0);
+    apply_migrations(conn, &migrations, latest, true)
+}
+
+pub fn apply_migrations(
+    conn: &Connection,
+    migrations: &[Migration],
+    target: i32,
+    dry_run: bool,
+) -> Result<Vec<MigrationStep>, AppError> {
+    if target < 0 || (target > 0 && !migrations.iter().any(|m| m.version == target)) {
+        return Err(AppError::Validation(format!("Version de schéma inconnue : {}", target)));
+    }
+
+    if !dry_run {
+        ensure_schema_version_table(conn)?;
+    }
+    verify_checksums(conn, migrations, dry_run)?;
+
+    let current = current_version(conn)?;
+    let mut steps = Vec::new();
+
+    if target >= current {
+        for migration in migrations.iter().filter(|m| m.version > current && m.version <= target) {
+            if !dry_run {
+                migration.execute(conn)?;
+            }
+            steps.push(MigrationStep {
+                version: migration.version,
+                description: migration.description.to_string(),
+                direction: MigrationDirection::Up,
+            });
+        }
+    } else {
+        for migration in migrations.iter().rev().filter(|m| m.version <= current && m.version > target) {
+            if !dry_run {
+                migration.rollback(conn)?;
+            }
+            steps.push(MigrationStep {
+                version: migration.version,
+                description: migration.description.to_string(),
+                direction: MigrationDirection::Down,
+            });
+        }
+    }
+
+    Ok(steps)
+}
+
+pub fn current_version(conn: &Connection) -> Result<i32, AppError> {
+    if !schema_version_exists(conn)? {
+        return Ok(0);
+    }
+
+    let version = conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(version)
+}
+
+fn schema_version_exists(conn: &Connection) -> Result<bool, AppError> {
+    let exists = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type='table' AND name='schema_version')",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(exists)
+}
+
+fn ensure_schema_version_table(conn: &Connection) -> Result<(), AppError> {
+    // La table de suivi appartient au runner, pas aux migrations
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            description TEXT,
+            checksum TEXT,
+            applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+    )?;
+
+    // Les bases créées avant le suivi des checksums n'ont pas ces colonnes
+    let mut stmt = conn.prepare("PRAGMA table_info(schema_version)")?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+    for column in ["description", "checksum"] {
+        if !columns.iter().any(|c| c == column) {
+            conn.execute_batch(&format!("ALTER TABLE schema_version ADD COLUMN {} TEXT", column))?;
+        }
+    }
+
+    Ok(())
+}
+
+// Détecte les migrations modifiées après avoir été appliquées
+fn verify_checksums(conn: &Connection, migrations: &[Migration], dry_run: bool) -> Result<(), AppError> {
+    if !schema_version_exists(conn)? {
+        return Ok(());
+    }
+
+    let mut stmt = conn.prepare("SELECT version, checksum FROM schema_version")?;
+    let applied = stmt
+        .query_map([], |row| Ok((row.get::<_, i32>(0)?, row.get::<_, Option<String>>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (version, checksum) in applied {
+        let Some(migration) = migrations.iter().find(|m| m.version == version) else {
+            continue;
+        };
+        match checksum {
+            Some(checksum) if checksum != migration.checksum() => {
+                return Err(AppError::Database(format!(
+                    "La migration {} a été modifiée après son application (checksum différent)",
+                    version
+                )));
+            }
+            Some(_) => {}
+            // Version enregistrée sans checksum : on complète l'historique
+            None if !dry_run => {
+                conn.execute(
+                    "UPDATE schema_version SET description = ?, checksum = ? WHERE version = ?",
+                    rusqlite::params![migration.description, migration.checksum(), version],
+                )?;
+            }
+            None => {}
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod cache;
+pub mod config;
+pub mod executor;
+pub mod fts;
+pub mod migrations;
+pub mod operations;
+pub mod pool;
@@ -1,162 +1,130 @@
-use rusqlite::{Connection, Result, params};
-use crate::models::{User, Job, JobStats, Document, DocumentTemplate, SearchPreferences, JobApplication};
-use std::path::Path;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use rusqlite::{params, params_from_iter, types::Value, Result, Row};
+use crate::models::{
+    CommuteTimes, DistributionData, Document, DocumentTemplate, Job, JobStats, SalaryRange, TrendData, User,
+};
 use crate::error::AppError;
-use std::collections::VecDeque;
-use std::time::Duration;
-use std::collections::HashMap;
-use std::time::Instant;
-
-const MAX_CONNECTIONS: usize = 10;
-const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
-const CACHE_TTL: Duration = Duration::from_secs(300); // 5 minutes
-
-struct CacheEntry<T> {
-    value: T,
-    timestamp: Instant,
+use crate::geo::Gazetteer;
+use crate::skills::SkillTaxonomy;
+use crate::database::cache::{CacheStats, LruCache, DEFAULT_CACHE_CAPACITY, DEFAULT_CACHE_TTL};
+use crate::database::migrations::run_migrations;
+use crate::database::pool::{ConnectionPool, PoolMetrics, PooledConnection, MAX_CONNECTIONS};
+
+// Lu uniquement via `scored_job_columns` : `jobs.matching_score` n'est pas le score du profil
+const JOB_COLUMNS: &str = "id, title, company, location, description, url, source, posted_at, \
+     salary_min, salary_max, salary_currency, salary_period, matching_score, skills, experience_level, status, \
+     valid_through";
+
+// Score de matching du profil plutôt que celui stocké sur l'offre, partagée
+// entre tous les profils. Requiert `LEFT JOIN job_match_scores scores`
+pub(crate) const SCORED_JOBS_FROM: &str = "jobs LEFT JOIN job_match_scores scores ON scores.job_id = jobs.id AND scores.user_id = ?";
+
+pub(crate) fn scored_job_columns() -> String {
+    JOB_COLUMNS.replace("matching_score", "COALESCE(scores.score, 0) AS matching_score")
 }
 
 pub struct DatabaseOperations {
-    pool: Arc<Mutex<VecDeque<Connection>>>,
-    path: String,
-    job_cache: Arc<Mutex<HashMap<String, CacheEntry<Vec<Job>>>>>,
+    pool: ConnectionPool,
+    job_cache: LruCache<Vec<Job>>,
+}
+
+// Construit un `Job` à partir d'une ligne sélectionnée avec `JOB_COLUMNS`
+pub(crate) fn job_from_row(row: &Row) -> Result<Job> {
+    let salary_min: Option<f64> = row.get(8)?;
+    let salary_max: Option<f64> = row.get(9)?;
+    let salary_currency: Option<String> = row.get(10)?;
+    let salary_period: Option<String> = row.get(11)?;
+    let salary = if salary_min.is_some() || salary_max.is_some() {
+        Some(SalaryRange {
+            min: salary_min,
+            max: salary_max,
+            currency: salary_currency,
+            period: salary_period,
+        })
+    } else {
+        None
+    };
+    let skills: String = row.get(13)?;
+    let experience_level: String = row.get(14)?;
+
+    Ok(Job {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        company: row.get(2)?,
+        location: row.get(3)?,
+        description: row.get(4)?,
+        url: row.get(5)?,
+        source: row.get(6)?,
+        published_at: row.get(7)?,
+        valid_through: row.get(16)?,
+        salary,
+        matching_score: row.get(12)?,
+        commute_times: CommuteTimes::default(),
+        skills: serde_json::from_str(&skills).ok(),
+        experience_level: if experience_level.is_empty() { None } else { Some(experience_level) },
+        applied_at: None,
+        status: row.get(15)?,
+    })
+}
+
+fn template_from_row(row: &Row) -> Result<DocumentTemplate> {
+    let variables: Option<String> = row.get(4)?;
+    Ok(DocumentTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        template_type: row.get(2)?,
+        content: row.get(3)?,
+        variables: variables.and_then(|v| serde_json::from_str(&v).ok()),
+        is_default: row.get(5)?,
+        language: row.get(6)?,
+    })
+}
+
+fn document_from_row(row: &Row) -> Result<Document> {
+    Ok(Document {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        title: row.get(2)?,
+        document_type: row.get(3)?,
+        file_path: row.get(4)?,
+        content: row.get(5)?,
+        created_at: row.get(6)?,
+    })
 }
 
 impl DatabaseOperations {
-    pub fn new(path: &str) -> Result<Self> {
-        let mut pool = VecDeque::with_capacity(MAX_CONNECTIONS);
-        
-        // Créer les connexions initiales
-        for _ in 0..MAX_CONNECTIONS {
-            let conn = Connection::open(path)?;
-            pool.push_back(conn);
-        }
+    pub fn new(path: &str) -> Result<Self, AppError> {
+        Self::with_cache_capacity(path, DEFAULT_CACHE_CAPACITY)
+    }
 
+    pub fn with_cache_capacity(path: &str, capacity: usize) -> Result<Self, AppError> {
         Ok(Self {
-            pool: Arc::new(Mutex::new(pool)),
-            path: path.to_string(),
-            job_cache: Arc::new(Mutex::new(HashMap::new())),
+            pool: ConnectionPool::new(path, MAX_CONNECTIONS)?,
+            job_cache: LruCache::new(capacity, DEFAULT_CACHE_TTL),
         })
     }
 
-    async fn get_connection(&self) -> Result<Connection, AppError> {
-        let mut pool = self.pool.lock().await;
-        
-        // Attendre jusqu'à ce qu'une connexion soit disponible
-        let start_time = std::time::Instant::now();
-        while pool.is_empty() {
-            if start_time.elapsed() > CONNECTION_TIMEOUT {
-                return Err(AppError::Internal("Timeout waiting for database connection".to_string()));
-            }
-            tokio::time::sleep(Duration::from_millis(100)).await;
-        }
-
-        // Récupérer une connexion du pool
-        let conn = pool.pop_front().unwrap();
-        Ok(conn)
+    // La connexion retourne d'elle-même dans le pool à la fin de la portée
+    async fn get_connection(&self) -> Result<PooledConnection, AppError> {
+        self.pool.get().await
     }
 
-    async fn return_connection(&self, conn: Connection) {
-        let mut pool = self.pool.lock().await;
-        pool.push_back(conn);
+    pub fn pool_metrics(&self) -> PoolMetrics {
+        self.pool.metrics()
     }
 
-    pub async fn init(&self) -> Result<()> {
-        let conn = self.get_connection().await?;
-        
-        // Création des tables avec optimisations
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS users (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                email TEXT UNIQUE NOT NULL,
-                password_hash TEXT NOT NULL,
-                created_at DATETIME NOT NULL
-            )",
-            [],
-        )?;
-
-        // Création de l'index sur l'email
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_users_email ON users(email)",
-            [],
-        )?;
-
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS jobs (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                title TEXT NOT NULL,
-                company TEXT NOT NULL,
-                location TEXT,
-                description TEXT,
-                salary_range TEXT,
-                matching_score REAL,
-                status TEXT NOT NULL,
-                source TEXT NOT NULL,
-                created_at DATETIME NOT NULL,
-                updated_at DATETIME NOT NULL
-            )",
-            [],
-        )?;
-
-        // Création des index pour les requêtes fréquentes
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status)",
-            [],
-        )?;
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_jobs_matching_score ON jobs(matching_score)",
-            [],
-        )?;
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_jobs_company ON jobs(company)",
-            [],
-        )?;
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_jobs_location ON jobs(location)",
-            [],
-        )?;
-
-        // Table des documents avec index
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS documents (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                user_id INTEGER NOT NULL,
-                title TEXT NOT NULL,
-                file_path TEXT NOT NULL,
-                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-                FOREIGN KEY (user_id) REFERENCES users (id)
-            )",
-            [],
-        )?;
-
-        // Index pour les documents
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_documents_user_id ON documents(user_id)",
-            [],
-        )?;
+    pub fn cache_stats(&self) -> CacheStats {
+        self.job_cache.stats()
+    }
 
-        // Table des modèles de documents avec index
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS document_templates (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                template_type TEXT NOT NULL,
-                content TEXT NOT NULL,
-                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-            )",
-            [],
-        )?;
+    pub fn flush_cache(&self) {
+        self.job_cache.invalidate_all();
+    }
 
-        // Index pour les templates
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_templates_type ON document_templates(template_type)",
-            [],
-        )?;
+    pub async fn init(&self) -> Result<(), AppError> {
+        let conn = self.get_connection().await?;
 
-        self.return_connection(conn).await;
-        Ok(())
+        // Le schéma est entièrement géré par les migrations versionnées
+        run_migrations(&conn)
     }
 
     pub async fn get_user_by_email(&self, email: &str) -> Result<User, AppError> {
@@ -169,7 +137,6 @@ impl DatabaseOperations {
                 password_hash: row.get(2)?,
             })
         })?;
-        self.return_connection(conn).await;
         Ok(user)
     }
 
@@ -187,150 +154,135 @@ impl DatabaseOperations {
         })
     }
 
-    pub fn create_job(&self, job: Job) -> Result<Job> {
+    pub async fn create_job(&self, mut job: Job) -> Result<Job, AppError> {
         let conn = self.get_connection().await?;
+        let coordinates = Gazetteer::bundled().geocode(&job.location);
+        job.skills = job.skills.map(|skills| SkillTaxonomy::bundled().canonicalize(&skills));
         conn.execute(
-            "INSERT INTO jobs (title, company, location, description, salary_range, matching_score, status, source) 
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO jobs (title, company, location, description, url, source, posted_at,
+                salary_min, salary_max, salary_currency, salary_period, skills, experience_level,
+                matching_score, status, latitude, longitude, valid_through)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, COALESCE(?, 'new'), ?, ?, ?)",
             params![
                 job.title,
                 job.company,
                 job.location,
                 job.description,
-                job.salary_range,
+                job.url,
+                job.source,
+                job.published_at,
+                job.salary.as_ref().and_then(|s| s.min),
+                job.salary.as_ref().and_then(|s| s.max),
+                job.salary.as_ref().and_then(|s| s.currency.clone()),
+                job.salary.as_ref().and_then(|s| s.period.clone()),
+                serde_json::to_string(&job.skills.clone().unwrap_or_default()).unwrap_or_else(|_| "[]".to_string()),
+                job.experience_level.clone().unwrap_or_default(),
                 job.matching_score,
                 job.status,
-                job.source,
+                coordinates.as_ref().map(|c| c.lat),
+                coordinates.as_ref().map(|c| c.lng),
+                job.valid_through,
             ],
         )?;
         let id = conn.last_insert_rowid();
+        self.job_cache.invalidate_all();
         Ok(Job {
             id,
             ..job
         })
     }
 
-    pub fn update_job_status(&self, job_id: i64, status: &str) -> Result<bool> {
+    pub async fn update_job_status(&self, job_id: i64, status: &str) -> Result<bool, AppError> {
         let conn = self.get_connection().await?;
         let rows_affected = conn.execute(
             "UPDATE jobs SET status = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
             params![status, job_id],
         )?;
+        if rows_affected > 0 {
+            self.job_cache.invalidate_all();
+        }
         Ok(rows_affected > 0)
     }
 
-    pub fn get_user_documents(&self, user_id: i64) -> Result<Vec<Document>> {
+    pub async fn get_user_documents(&self, user_id: i64) -> Result<Vec<Document>, AppError> {
         let conn = self.get_connection().await?;
-        let mut stmt = conn.prepare("SELECT * FROM documents WHERE user_id = ?")?;
+        let mut stmt = conn.prepare(
+            "SELECT id, user_id, title, document_type, file_path, content, created_at
+             FROM documents WHERE user_id = ?",
+        )?;
         let documents = stmt
-            .query_map([user_id], |row| {
-                Ok(Document {
-                    id: row.get(0)?,
-                    user_id: row.get(1)?,
-                    title: row.get(2)?,
-                    file_path: row.get(3)?,
-                    document_type: row.get(4)?,
-                    created_at: row.get(5)?,
-                })
-            })?
+            .query_map([user_id], document_from_row)?
             .collect::<Result<Vec<_>>>()?;
         Ok(documents)
     }
 
-    pub fn get_template_by_type(&self, template_type: &str) -> Result<Option<DocumentTemplate>> {
+    pub async fn get_template_by_type(&self, template_type: &str) -> Result<Option<DocumentTemplate>, AppError> {
         let conn = self.get_connection().await?;
-        let mut stmt = conn.prepare("SELECT * FROM document_templates WHERE template_type = ?")?;
-        let template = stmt
-            .query_row([template_type], |row| {
-                Ok(DocumentTemplate {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    template_type: row.get(2)?,
-                    content: row.get(3)?,
-                    created_at: row.get(4)?,
-                })
-            })
-            .ok();
+        let mut stmt = conn.prepare(
+            "SELECT id, name, template_type, content, variables, is_default, language
+             FROM document_templates WHERE template_type = ?",
+        )?;
+        let template = stmt.query_row([template_type], template_from_row).ok();
         Ok(template)
     }
 
-    pub async fn get_jobs(&self) -> Result<Vec<Job>, AppError> {
+    pub async fn get_jobs(&self, user_id: i64) -> Result<Vec<Job>, AppError> {
         // Vérifier le cache
-        let cache_key = "all_jobs".to_string();
-        let mut cache = self.job_cache.lock().await;
-        
-        if let Some(entry) = cache.get(&cache_key) {
-            if entry.timestamp.elapsed() < CACHE_TTL {
-                return Ok(entry.value.clone());
-            }
+        let cache_key = format!("all_jobs:{}", user_id);
+        let generation = self.job_cache.generation();
+        if let Some(cached_jobs) = self.job_cache.get(&cache_key) {
+            return Ok(cached_jobs);
         }
 
         let conn = self.get_connection().await?;
-        let mut stmt = conn.prepare(
-            "SELECT id, title, company, location, description, salary_range, matching_score, status, source 
-             FROM jobs 
-             ORDER BY matching_score DESC, created_at DESC"
-        )?;
-        
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM {} ORDER BY COALESCE(scores.score, 0) DESC, jobs.created_at DESC, jobs.id DESC",
+            scored_job_columns(),
+            SCORED_JOBS_FROM
+        ))?;
+
         let jobs = stmt
-            .query_map([], |row| {
-                Ok(Job {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    company: row.get(2)?,
-                    location: row.get(3)?,
-                    description: row.get(4)?,
-                    salary_range: row.get(5)?,
-                    matching_score: row.get(6)?,
-                    status: row.get(7)?,
-                    source: row.get(8)?,
-                })
-            })?
+            .query_map([user_id], job_from_row)?
             .collect::<Result<Vec<_>>>()?;
 
         // Mettre à jour le cache
-        cache.insert(
-            cache_key,
-            CacheEntry {
-                value: jobs.clone(),
-                timestamp: Instant::now(),
-            },
-        );
+        self.job_cache.insert(cache_key, jobs.clone(), generation);
 
-        self.return_connection(conn).await;
         Ok(jobs)
     }
 
     pub async fn get_job_stats(&self) -> Result<JobStats, AppError> {
         let conn = self.get_connection().await?;
-        
-        // Requête optimisée pour les statistiques
+
+        let total_jobs: i64 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0))?;
+
+        // Nombre d'offres ajoutées par jour
         let mut stmt = conn.prepare(
-            "SELECT 
-                COUNT(*) as total_jobs,
-                SUM(CASE WHEN status = 'new' THEN 1 ELSE 0 END) as new_jobs,
-                SUM(CASE WHEN status = 'applied' THEN 1 ELSE 0 END) as applied_jobs,
-                SUM(CASE WHEN status = 'interview' THEN 1 ELSE 0 END) as interview_jobs,
-                SUM(CASE WHEN status = 'offer' THEN 1 ELSE 0 END) as offer_jobs,
-                AVG(matching_score) as avg_score
-             FROM jobs"
+            "SELECT date(created_at) AS day, COUNT(*) FROM jobs GROUP BY day ORDER BY day",
         )?;
+        let trend = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<Result<Vec<_>>>()?;
 
-        let stats = stmt.query_row([], |row| {
-            Ok(JobStats {
-                total_jobs: row.get(0)?,
-                status_distribution: vec![
-                    ("new".to_string(), row.get(1)?),
-                    ("applied".to_string(), row.get(2)?),
-                    ("interview".to_string(), row.get(3)?),
-                    ("offer".to_string(), row.get(4)?),
-                ],
-                source_distribution: vec![], // À implémenter si nécessaire
-            })
-        })?;
+        // Répartition par source
+        let mut stmt = conn.prepare(
+            "SELECT source, COUNT(*) FROM jobs GROUP BY source ORDER BY COUNT(*) DESC",
+        )?;
+        let sources = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<Result<Vec<_>>>()?;
 
-        self.return_connection(conn).await;
-        Ok(stats)
+        Ok(JobStats {
+            total_jobs,
+            trend_data: TrendData {
+                labels: trend.iter().map(|(label, _)| label.clone()).collect(),
+                values: trend.iter().map(|(_, value)| *value).collect(),
+            },
+            source_distribution: DistributionData {
+                labels: sources.iter().map(|(label, _)| label.clone()).collect(),
+                values: sources.iter().map(|(_, value)| *value).collect(),
+            },
+        })
     }
 
     pub async fn create_document(
@@ -341,71 +293,55 @@ impl DatabaseOperations {
         document_type: &str,
     ) -> Result<Document, AppError> {
         let conn = self.get_connection().await?;
-        conn.execute(
-            "INSERT INTO documents (user_id, title, content, document_type) VALUES (?, ?, ?, ?)",
+        let document = conn.query_row(
+            "INSERT INTO documents (user_id, title, content, document_type) VALUES (?, ?, ?, ?)
+             RETURNING id, user_id, title, document_type, file_path, content, created_at",
             params![user_id, title, content, document_type],
+            document_from_row,
         )?;
-        let id = conn.last_insert_rowid();
-        Ok(Document {
-            id,
-            user_id,
-            title: title.to_string(),
-            content: content.to_string(),
-            document_type: document_type.to_string(),
-        })
+        Ok(document)
     }
 
     pub async fn get_document_templates(&self) -> Result<Vec<DocumentTemplate>, AppError> {
         let conn = self.get_connection().await?;
-        let mut stmt = conn.prepare("SELECT id, template_type, content FROM document_templates")?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, template_type, content, variables, is_default, language FROM document_templates",
+        )?;
         let templates = stmt
-            .query_map([], |row| {
-                Ok(DocumentTemplate {
-                    id: row.get(0)?,
-                    template_type: row.get(1)?,
-                    content: row.get(2)?,
-                })
-            })?
+            .query_map([], template_from_row)?
             .collect::<Result<Vec<_>>>()?;
         Ok(templates)
     }
 
-    async fn get_cached_jobs(&self, cache_key: &str) -> Option<Vec<Job>> {
-        let cache = self.job_cache.lock().await;
-        if let Some(entry) = cache.get(cache_key) {
-            if entry.timestamp.elapsed() < CACHE_TTL {
-                return Some(entry.value.clone());
-            }
-        }
-        None
-    }
-
-    async fn cache_jobs(&self, cache_key: String, jobs: Vec<Job>) {
-        let mut cache = self.job_cache.lock().await;
-        cache.insert(cache_key, CacheEntry {
-            value: jobs,
-            timestamp: Instant::now(),
-        });
-    }
-
-    pub async fn search_jobs(&self, keywords: Option<&str>, location: Option<&str>) -> Result<Vec<Job>, AppError> {
-        let cache_key = format!("search:{}:{}", 
-            keywords.unwrap_or(""), 
-            location.unwrap_or("")
+    pub async fn search_jobs(
+        &self,
+        user_id: i64,
+        keywords: Option<&str>,
+        location: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Job>, AppError> {
+        let cache_key = format!("search:{}:{}:{}:{}:{}",
+            user_id,
+            keywords.unwrap_or(""),
+            location.unwrap_or(""),
+            limit,
+            offset
         );
 
         // Vérifier le cache
-        if let Some(cached_jobs) = self.get_cached_jobs(&cache_key).await {
+        let generation = self.job_cache.generation();
+        if let Some(cached_jobs) = self.job_cache.get(&cache_key) {
             return Ok(cached_jobs);
         }
 
         let conn = self.get_connection().await?;
-        let mut query = "SELECT id, title, company, location, description, matching_score FROM jobs WHERE 1=1".to_string();
-        let mut params = Vec::new();
+        let mut query = format!("SELECT {} FROM {} WHERE 1=1", scored_job_columns(), SCORED_JOBS_FROM);
+        let mut params = vec![Value::Integer(user_id)];
 
         if let Some(kw) = keywords {
             query.push_str(" AND (title LIKE ? OR company LIKE ? OR description LIKE ?)");
-            let search_term = format!("%{}%", kw);
+            let search_term = Value::Text(format!("%{}%", kw));
             params.push(search_term.clone());
             params.push(search_term.clone());
             params.push(search_term);
@@ -413,30 +349,23 @@ impl DatabaseOperations {
 
         if let Some(loc) = location {
             query.push_str(" AND location LIKE ?");
-            params.push(format!("%{}%", loc));
+            params.push(Value::Text(format!("%{}%", loc)));
         }
 
-        query.push_str(" ORDER BY matching_score DESC LIMIT 100");
+        // L'id départage les scores égaux pour une pagination stable
+        query.push_str(&format!(
+            " ORDER BY COALESCE(scores.score, 0) DESC, jobs.id DESC LIMIT {} OFFSET {}",
+            limit, offset
+        ));
 
         let mut stmt = conn.prepare(&query)?;
         let jobs = stmt
-            .query_map(params_from_iter(params.iter()), |row| {
-                Ok(Job {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    company: row.get(2)?,
-                    location: row.get(3)?,
-                    description: row.get(4)?,
-                    matching_score: row.get(5)?,
-                })
-            })?
+            .query_map(params_from_iter(params.iter()), job_from_row)?
             .collect::<Result<Vec<_>>>()?;
 
-        self.return_connection(conn).await;
-        
         // Mettre en cache les résultats
-        self.cache_jobs(cache_key, jobs.clone()).await;
-        
+        self.job_cache.insert(cache_key, jobs.clone(), generation);
+
         Ok(jobs)
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,217 @@
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::error::AppError;
+use crate::geo::register_sql_functions;
+
+pub const MAX_CONNECTIONS: usize = 10;
+pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolMetrics {
+    pub max_size: usize,
+    pub open: usize,
+    pub in_use: usize,
+    pub idle: usize,
+    pub waiting: usize,
+    pub total_acquired: u64,
+    pub timeouts: u64,
+    pub avg_wait_ms: f64,
+    pub max_wait_ms: u64,
+}
+
+struct PoolInner {
+    path: String,
+    max_size: usize,
+    read_only: bool,
+    idle: Mutex<VecDeque<Connection>>,
+    semaphore: Arc<Semaphore>,
+    open: AtomicUsize,
+    waiting: AtomicUsize,
+    total_acquired: AtomicU64,
+    timeouts: AtomicU64,
+    total_wait_us: AtomicU64,
+    max_wait_us: AtomicU64,
+}
+
+// Pool de connexions SQLite : l'attente se fait sur un sémaphore, jamais
+// en tenant le verrou de la file des connexions libres
+#[derive(Clone)]
+pub struct ConnectionPool {
+    inner: Arc<PoolInner>,
+}
+
+// Connexion empruntée au pool, restituée automatiquement à la destruction
+pub struct PooledConnection {
+    conn: Option<Connection>,
+    pool: Arc<PoolInner>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl ConnectionPool {
+    pub fn new(path: &str, max_size: usize) -> Result<Self, AppError> {
+        Self::with_mode(path, max_size, false)
+    }
+
+    // Pool dont les connexions refusent toute écriture (`query_only`)
+    pub fn new_read_only(path: &str, max_size: usize) -> Result<Self, AppError> {
+        Self::with_mode(path, max_size, true)
+    }
+
+    fn with_mode(path: &str, max_size: usize, read_only: bool) -> Result<Self, AppError> {
+        let max_size = max_size.max(1);
+        let pool = Self {
+            inner: Arc::new(PoolInner {
+                path: path.to_string(),
+                max_size,
+                read_only,
+                idle: Mutex::new(VecDeque::with_capacity(max_size)),
+                semaphore: Arc::new(Semaphore::new(max_size)),
+                open: AtomicUsize::new(0),
+                waiting: AtomicUsize::new(0),
+                total_acquired: AtomicU64::new(0),
+                timeouts: AtomicU64::new(0),
+                total_wait_us: AtomicU64::new(0),
+                max_wait_us: AtomicU64::new(0),
+            }),
+        };
+
+        // Ouvrir une première connexion pour détecter un chemin invalide au démarrage
+        let conn = pool.inner.open_connection()?;
+        pool.inner.open.fetch_add(1, Ordering::SeqCst);
+        pool.inner.idle.lock().unwrap().push_back(conn);
+
+        Ok(pool)
+    }
+
+    pub async fn get(&self) -> Result<PooledConnection, AppError> {
+        let start = Instant::now();
+        self.inner.waiting.fetch_add(1, Ordering::SeqCst);
+        let permit = tokio::time::timeout(
+            CONNECTION_TIMEOUT,
+            self.inner.semaphore.clone().acquire_owned(),
+        )
+        .await;
+        self.inner.waiting.fetch_sub(1, Ordering::SeqCst);
+
+        let permit = match permit {
+            Ok(Ok(permit)) => permit,
+            Ok(Err(_)) => return Err(AppError::Internal("Database pool closed".to_string())),
+            Err(_) => {
+                self.inner.timeouts.fetch_add(1, Ordering::SeqCst);
+                return Err(AppError::Internal("Timeout waiting for database connection".to_string()));
+            }
+        };
+        self.record_wait(start.elapsed());
+
+        // Le permis garantit qu'une connexion libre existe ou peut être ouverte
+        let idle = self.inner.idle.lock().unwrap().pop_front();
+        let conn = match idle {
+            Some(conn) => conn,
+            None => {
+                let conn = self.inner.open_connection()?;
+                self.inner.open.fetch_add(1, Ordering::SeqCst);
+                conn
+            }
+        };
+
+        Ok(PooledConnection {
+            conn: Some(conn),
+            pool: self.inner.clone(),
+            _permit: permit,
+        })
+    }
+
+    pub fn metrics(&self) -> PoolMetrics {
+        let idle = self.inner.idle.lock().unwrap().len();
+        let total_acquired = self.inner.total_acquired.load(Ordering::SeqCst);
+        let total_wait_us = self.inner.total_wait_us.load(Ordering::SeqCst);
+
+        PoolMetrics {
+            max_size: self.inner.max_size,
+            open: self.inner.open.load(Ordering::SeqCst),
+            in_use: self.inner.max_size - self.inner.semaphore.available_permits(),
+            idle,
+            waiting: self.inner.waiting.load(Ordering::SeqCst),
+            total_acquired,
+            timeouts: self.inner.timeouts.load(Ordering::SeqCst),
+            avg_wait_ms: if total_acquired == 0 {
+                0.0
+            } else {
+                total_wait_us as f64 / total_acquired as f64 / 1000.0
+            },
+            max_wait_ms: self.inner.max_wait_us.load(Ordering::SeqCst) / 1000,
+        }
+    }
+
+    fn record_wait(&self, wait: Duration) {
+        let wait_us = wait.as_micros() as u64;
+        self.inner.total_acquired.fetch_add(1, Ordering::SeqCst);
+        self.inner.total_wait_us.fetch_add(wait_us, Ordering::SeqCst);
+        self.inner.max_wait_us.fetch_max(wait_us, Ordering::SeqCst);
+    }
+}
+
+impl PoolInner {
+    fn open_connection(&self) -> Result<Connection, AppError> {
+        let conn = open_connection(&self.path)?;
+        if self.read_only {
+            conn.execute_batch("PRAGMA query_only = ON;")?;
+        }
+        Ok(conn)
+    }
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection already returned to the pool")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection already returned to the pool")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            // Une transaction laissée ouverte (erreur, panique) ne doit pas
+            // contaminer le prochain emprunteur
+            if !conn.is_autocommit() && conn.execute_batch("ROLLBACK").is_err() {
+                self.pool.open.fetch_sub(1, Ordering::SeqCst);
+                return;
+            }
+            self.pool.idle.lock().unwrap().push_back(conn);
+        }
+        // Le permis est libéré après la remise en file de la connexion
+    }
+}
+
+// Ouvre une connexion et applique les PRAGMAs communs à toute l'application
+pub fn open_connection(path: &str) -> Result<Connection, AppError> {
+    let conn = Connection::open(path)?;
+    configure_connection(&conn)?;
+    Ok(conn)
+}
+
+pub fn configure_connection(conn: &Connection) -> Result<(), AppError> {
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         PRAGMA foreign_keys = ON;
+         PRAGMA synchronous = NORMAL;",
+    )?;
+    register_sql_functions(conn)?;
+    Ok(())
+}
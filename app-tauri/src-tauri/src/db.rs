@@ -2,12 +2,14 @@ use rusqlite::{Connection, Result as SqlResult};
 use std::path::PathBuf;
 use std::env;
 
-pub struct DbState {
-    #[allow(dead_code)]
-    pub conn: Connection,
-}
+use crate::database::executor::Db;
+use crate::database::migrations::run_migrations;
+use crate::database::pool::open_connection;
+use crate::error::AppError;
+use crate::geo::geocode_missing_jobs;
+use crate::skills::{normalize_stored_skills, sync_skill_taxonomy, SkillTaxonomy};
 
-pub fn init_db() -> SqlResult<DbState> {
+pub fn init_db() -> Result<Db, AppError> {
     let db_path = get_db_path();
     
     // Créer le dossier data s'il n'existe pas
@@ -19,15 +21,20 @@ pub fn init_db() -> SqlResult<DbState> {
         }
     }
 
-    let conn = Connection::open(&db_path)?;
+    let db_path = db_path.to_string_lossy();
 
-    // Appliquer les migrations
-    conn.execute(
-        include_str!("../migrations/001_initial.sql"),
-        [],
-    )?;
+    // Appliquer les migrations avant d'ouvrir les pools
+    {
+        let conn = open_connection(&db_path)?;
+        run_migrations(&conn)?;
+        // Géolocaliser les offres importées avant l'ajout des coordonnées
+        geocode_missing_jobs(&conn)?;
+        // Référentiel de compétences à jour, puis compétences déjà enregistrées ramenées aux noms canoniques
+        sync_skill_taxonomy(&conn, SkillTaxonomy::bundled())?;
+        normalize_stored_skills(&conn, SkillTaxonomy::bundled())?;
+    }
 
-    Ok(DbState { conn })
+    Db::open(&db_path)
 }
 
 #[allow(dead_code)]
@@ -36,9 +43,9 @@ pub fn get_db_connection() -> SqlResult<Connection> {
     Connection::open(&db_path)
 }
 
-fn get_db_path() -> PathBuf {
+pub fn get_db_path() -> PathBuf {
     let mut path = env::current_dir().unwrap();
     path.push("data");
     path.push("jobs.db");
     path
-} 
\ No newline at end of file
+}
@@ -0,0 +1,227 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::geo::{normalize, Gazetteer};
+
+// Taille de la signature MinHash et découpage en bandes pour le LSH : deux
+// descriptions partageant une bande entière deviennent candidates
+pub const MINHASH_SIZE: usize = 64;
+const LSH_BANDS: usize = 16;
+const LSH_ROWS: usize = MINHASH_SIZE / LSH_BANDS;
+
+// Mots consécutifs par bardeau de description
+const SHINGLE_WORDS: usize = 3;
+
+// Au-delà, un bloc (une entreprise qui publie beaucoup, un texte type repris
+// par toutes les offres d'un site) est trop grand pour comparer ses offres deux
+// à deux : seule l'empreinte, ou un bloc plus petit, les rapproche
+const MAX_BLOCK_SIZE: usize = 200;
+
+// Poids de chaque critère dans le score ; sans description d'un côté, le
+// score est calculé sur les trois autres
+const TITLE_WEIGHT: f64 = 0.4;
+const COMPANY_WEIGHT: f64 = 0.25;
+const LOCATION_WEIGHT: f64 = 0.1;
+const DESCRIPTION_WEIGHT: f64 = 0.25;
+
+// Mentions sans valeur pour comparer deux intitulés ou deux entreprises
+const TITLE_NOISE: &[&str] = &["h", "f", "hf", "fh", "x", "cdi", "cdd", "stage", "alternance", "freelance"];
+const LEGAL_FORMS: &[&str] = &["sa", "sas", "sasu", "sarl", "eurl", "sca", "group", "groupe", "inc", "ltd", "gmbh", "france"];
+
+// Offre réduite à ce qui sert à la comparer
+#[derive(Debug, Clone)]
+pub struct JobFingerprint {
+    pub job_id: i64,
+    // « titre|entreprise|lieu » normalisés : deux offres de même empreinte
+    // sont candidates quel que soit leur score
+    pub key: String,
+    pub company: String,
+    title_tokens: HashSet<String>,
+    company_tokens: HashSet<String>,
+    location: String,
+    signature: Option<[u64; MINHASH_SIZE]>,
+}
+
+impl JobFingerprint {
+    pub fn new(job_id: i64, title: &str, company: &str, location: &str, description: &str) -> Self {
+        let title_tokens = title_tokens(title);
+        let company_tokens = company_tokens(company);
+        let location = location_key(location);
+        let company = sorted_join(&company_tokens);
+
+        Self {
+            job_id,
+            key: format!("{}|{}|{}", sorted_join(&title_tokens), company, location),
+            company,
+            title_tokens,
+            company_tokens,
+            location,
+            signature: minhash(&shingles(description)),
+        }
+    }
+}
+
+// Détail du rapprochement de deux offres
+#[derive(Debug, Clone, PartialEq)]
+pub struct Similarity {
+    pub score: f64,
+    pub title: f64,
+    pub company: f64,
+    pub location: f64,
+    pub description: Option<f64>,
+    pub same_fingerprint: bool,
+}
+
+pub fn similarity(a: &JobFingerprint, b: &JobFingerprint) -> Similarity {
+    let title = jaccard(&a.title_tokens, &b.title_tokens);
+    let company = if !a.company.is_empty() && a.company == b.company {
+        1.0
+    } else {
+        jaccard(&a.company_tokens, &b.company_tokens)
+    };
+    let location = if a.location == b.location { 1.0 } else { 0.0 };
+    let description = match (&a.signature, &b.signature) {
+        (Some(a), Some(b)) => Some(a.iter().zip(b.iter()).filter(|(x, y)| x == y).count() as f64 / MINHASH_SIZE as f64),
+        _ => None,
+    };
+
+    let fields = TITLE_WEIGHT * title + COMPANY_WEIGHT * company + LOCATION_WEIGHT * location;
+    let score = match description {
+        Some(description) => fields + DESCRIPTION_WEIGHT * description,
+        None => fields / (TITLE_WEIGHT + COMPANY_WEIGHT + LOCATION_WEIGHT),
+    };
+
+    Similarity {
+        score,
+        title,
+        company,
+        location,
+        description,
+        same_fingerprint: a.key == b.key,
+    }
+}
+
+// Paires d'indices (i < j) à comparer : même empreinte, même entreprise, ou
+// descriptions proches d'après le LSH. Évite de comparer toutes les offres
+pub fn candidate_pairs(jobs: &[JobFingerprint]) -> Vec<(usize, usize)> {
+    let mut buckets: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, job) in jobs.iter().enumerate() {
+        buckets.entry(format!("key:{}", job.key)).or_default().push(index);
+        if !job.company.is_empty() {
+            buckets.entry(format!("company:{}", job.company)).or_default().push(index);
+        }
+        if let Some(signature) = &job.signature {
+            for (band, rows) in signature.chunks(LSH_ROWS).enumerate() {
+                let rows: Vec<String> = rows.iter().map(|row| format!("{:x}", row)).collect();
+                buckets.entry(format!("band{}:{}", band, rows.join(","))).or_default().push(index);
+            }
+        }
+    }
+
+    let mut pairs = HashSet::new();
+    for (bucket, members) in &buckets {
+        if !bucket.starts_with("key:") && members.len() > MAX_BLOCK_SIZE {
+            continue;
+        }
+        for (n, &i) in members.iter().enumerate() {
+            for &j in &members[n + 1..] {
+                pairs.insert((i.min(j), i.max(j)));
+            }
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> = pairs.into_iter().collect();
+    pairs.sort_unstable();
+    pairs
+}
+
+// Mots normalisés (sans accents ni ponctuation), hors mentions sans valeur
+pub(crate) fn tokens(text: &str) -> Vec<String> {
+    normalize(text)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn significant_tokens(text: &str, noise: &[&str]) -> HashSet<String> {
+    tokens(text).into_iter().filter(|word| !noise.contains(&word.as_str())).collect()
+}
+
+// Mots significatifs d'une entreprise (sans forme juridique) ou d'un intitulé
+pub(crate) fn company_tokens(company: &str) -> HashSet<String> {
+    significant_tokens(company, LEGAL_FORMS)
+}
+
+pub(crate) fn title_tokens(title: &str) -> HashSet<String> {
+    significant_tokens(title, TITLE_NOISE)
+}
+
+fn sorted_join(tokens: &HashSet<String>) -> String {
+    let mut tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+    tokens.sort_unstable();
+    tokens.join(" ")
+}
+
+// Commune reconnue par le gazetteer (« 69003 Lyon » et « Lyon 3e » se
+// rejoignent), à défaut le libellé sans chiffres
+fn location_key(location: &str) -> String {
+    match Gazetteer::bundled().locate(location) {
+        Some(commune) => normalize(&commune.name),
+        None => tokens(location)
+            .into_iter()
+            .filter(|word| !word.chars().all(|c| c.is_ascii_digit()))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let union = a.union(b).count();
+    a.intersection(b).count() as f64 / union as f64
+}
+
+// Bardeaux de mots d'une description ; un texte trop court forme un seul bardeau
+pub fn shingles(description: &str) -> HashSet<String> {
+    let words = tokens(description);
+    if words.is_empty() {
+        return HashSet::new();
+    }
+    if words.len() < SHINGLE_WORDS {
+        return HashSet::from([words.join(" ")]);
+    }
+    words.windows(SHINGLE_WORDS).map(|window| window.join(" ")).collect()
+}
+
+// Signature MinHash : pour chaque fonction de hachage, le plus petit hachage
+// des bardeaux. La part de valeurs égales estime l'indice de Jaccard
+pub fn minhash(shingles: &HashSet<String>) -> Option<[u64; MINHASH_SIZE]> {
+    if shingles.is_empty() {
+        return None;
+    }
+    let mut signature = [u64::MAX; MINHASH_SIZE];
+    for shingle in shingles {
+        let base = fnv1a(shingle.as_bytes());
+        for (seed, slot) in signature.iter_mut().enumerate() {
+            let hash = splitmix64(base ^ (seed as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+            if hash < *slot {
+                *slot = hash;
+            }
+        }
+    }
+    Some(signature)
+}
+
+// Hachages stables d'une version de Rust à l'autre, contrairement à `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -61,6 +61,26 @@ pub fn validate_password(password: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+// Chiffres, espaces, points, tirets et parenthèses, éventuellement précédés de « + »
+pub fn validate_phone(phone: &str) -> Result<(), AppError> {
+    let digits = phone.chars().filter(|c| c.is_ascii_digit()).count();
+    let allowed = phone
+        .trim_start_matches('+')
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '.' | '-' | '(' | ')'));
+    if !allowed || !(6..=15).contains(&digits) {
+        return Err(AppError::Validation("Numéro de téléphone invalide".to_string()));
+    }
+    Ok(())
+}
+
+pub fn validate_coordinates(lat: f64, lng: f64) -> Result<(), AppError> {
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) {
+        return Err(AppError::Validation("Coordonnées géographiques invalides".to_string()));
+    }
+    Ok(())
+}
+
 pub fn validate_job_title(title: &str) -> Result<(), AppError> {
     if title.is_empty() {
         return Err(AppError::Validation("Le titre ne peut pas être vide".to_string()));
@@ -0,0 +1,268 @@
+use rusqlite::functions::FunctionFlags;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::error::AppError;
+use crate::models::Coordinates;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+// Gazetteer hors ligne (code postal, commune, département, coordonnées),
+// embarqué dans le binaire : aucun appel réseau pour géolocaliser une offre.
+// Généré par `scripts/build_communes.py` depuis la base des codes postaux
+// La Poste / INSEE ; le fichier versionné ne couvre encore que les principales
+// communes tant qu'il n'a pas été régénéré
+const BUNDLED_COMMUNES: &str = include_str!("../data/communes_fr.csv");
+
+// Communes découpées en arrondissements : tous leurs codes postaux
+// renvoient au centre de la commune
+const MUNICIPAL_ARRONDISSEMENTS: &[(&str, &str)] = &[
+    ("75", "paris"),
+    ("6900", "lyon"),
+    ("1300", "marseille"),
+    ("1301", "marseille"),
+];
+
+#[derive(Debug, Clone)]
+pub struct Commune {
+    pub postcode: String,
+    pub name: String,
+    pub department: String,
+    pub coordinates: Coordinates,
+}
+
+pub struct Gazetteer {
+    communes: Vec<Commune>,
+    by_postcode: HashMap<String, usize>,
+    by_name: HashMap<String, Vec<usize>>,
+}
+
+impl Gazetteer {
+    pub fn bundled() -> &'static Gazetteer {
+        static GAZETTEER: OnceLock<Gazetteer> = OnceLock::new();
+        GAZETTEER.get_or_init(|| Gazetteer::from_csv(BUNDLED_COMMUNES))
+    }
+
+    // Format : `code_postal;nom;departement;latitude;longitude`, avec en-tête
+    pub fn from_csv(csv: &str) -> Self {
+        let mut gazetteer = Gazetteer {
+            communes: Vec::new(),
+            by_postcode: HashMap::new(),
+            by_name: HashMap::new(),
+        };
+
+        for line in csv.lines().skip(1) {
+            let fields: Vec<&str> = line.split(';').map(str::trim).collect();
+            let [postcode, name, department, lat, lng] = fields[..] else {
+                continue;
+            };
+            let (Ok(lat), Ok(lng)) = (lat.parse(), lng.parse()) else {
+                continue;
+            };
+
+            let index = gazetteer.communes.len();
+            gazetteer.by_postcode.entry(postcode.to_string()).or_insert(index);
+            gazetteer.by_name.entry(normalize(name)).or_default().push(index);
+            gazetteer.communes.push(Commune {
+                postcode: postcode.to_string(),
+                name: name.to_string(),
+                department: department.to_string(),
+                coordinates: Coordinates { lat, lng },
+            });
+        }
+
+        gazetteer
+    }
+
+    // Géolocalise un libellé d'offre : « 75008 Paris », « Lyon (69) »,
+    // « Saint-Denis, Île-de-France », « Paris 15e »...
+    pub fn locate(&self, location: &str) -> Option<&Commune> {
+        let postcodes: Vec<&str> = location
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|token| token.len() == 5)
+            .collect();
+        for postcode in &postcodes {
+            if let Some(commune) = self.by_postcode(postcode) {
+                return Some(commune);
+            }
+        }
+
+        // Un numéro de département isolé permet de départager les homonymes
+        let department = location
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .find(|token| {
+                token.len() == 2
+                    && (token.chars().all(|c| c.is_ascii_digit())
+                        || token.eq_ignore_ascii_case("2a")
+                        || token.eq_ignore_ascii_case("2b"))
+            })
+            .map(str::to_uppercase);
+
+        let segments = location
+            .split([',', '(', ')', '/'])
+            .flat_map(|segment| segment.split(" - "))
+            .map(normalize)
+            .map(|segment| strip_qualifiers(&segment))
+            .filter(|segment| !segment.is_empty());
+
+        for segment in segments {
+            if let Some(indexes) = self.by_name.get(&segment) {
+                let preferred = indexes
+                    .iter()
+                    .find(|&&i| Some(&self.communes[i].department) == department.as_ref())
+                    .or_else(|| indexes.first());
+                if let Some(&i) = preferred {
+                    return Some(&self.communes[i]);
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn geocode(&self, location: &str) -> Option<Coordinates> {
+        self.locate(location).map(|commune| commune.coordinates.clone())
+    }
+
+    fn by_postcode(&self, postcode: &str) -> Option<&Commune> {
+        if let Some(&i) = self.by_postcode.get(postcode) {
+            return Some(&self.communes[i]);
+        }
+        MUNICIPAL_ARRONDISSEMENTS
+            .iter()
+            .find(|(prefix, _)| postcode.starts_with(prefix))
+            .and_then(|(_, name)| self.by_name.get(*name))
+            .and_then(|indexes| indexes.first())
+            .map(|&i| &self.communes[i])
+    }
+}
+
+// Minuscules, sans accents ni tirets : « Saint-Étienne » -> « saint etienne »
+pub(crate) fn normalize(value: &str) -> String {
+    let folded: String = value
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'à' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'î' | 'ï' => 'i',
+            'ô' | 'ö' => 'o',
+            'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            'ÿ' => 'y',
+            '-' | '\'' | '’' | '.' => ' ',
+            c => c,
+        })
+        .collect();
+
+    let words: Vec<&str> = folded.split_whitespace().collect();
+    words
+        .iter()
+        .map(|word| match *word {
+            "st" => "saint",
+            "ste" => "sainte",
+            word => word,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Retire codes postaux, arrondissements et « cedex » d'un libellé normalisé
+fn strip_qualifiers(segment: &str) -> String {
+    let words: Vec<&str> = segment
+        .split_whitespace()
+        .filter(|word| !word.chars().all(|c| c.is_ascii_digit()))
+        .filter(|word| !matches!(*word, "cedex" | "arrondissement" | "france"))
+        .filter(|word| {
+            // 15e, 1er, 8eme
+            let digits = word.trim_end_matches(|c: char| c.is_alphabetic());
+            digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit())
+        })
+        .collect();
+    words.join(" ")
+}
+
+pub fn haversine_km(from: &Coordinates, to: &Coordinates) -> f64 {
+    let (lat1, lat2) = (from.lat.to_radians(), to.lat.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lng = (to.lng - from.lng).to_radians();
+
+    let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lng / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+// Expose `haversine_km(lat1, lng1, lat2, lng2)` aux requêtes SQL ; renvoie
+// NULL si l'une des coordonnées est absente
+pub fn register_sql_functions(conn: &Connection) -> Result<(), AppError> {
+    conn.create_scalar_function(
+        "haversine_km",
+        4,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| {
+            let values = (0..4)
+                .map(|i| ctx.get::<Option<f64>>(i))
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(match values[..] {
+                [Some(lat1), Some(lng1), Some(lat2), Some(lng2)] => Some(haversine_km(
+                    &Coordinates { lat: lat1, lng: lng1 },
+                    &Coordinates { lat: lat2, lng: lng2 },
+                )),
+                _ => None,
+            })
+        },
+    )?;
+    Ok(())
+}
+
+// Renseigne latitude/longitude des offres qui n'en ont pas encore ; renvoie
+// le nombre d'offres géolocalisées
+pub fn geocode_missing_jobs(conn: &Connection) -> Result<usize, AppError> {
+    let gazetteer = Gazetteer::bundled();
+    let mut stmt = conn.prepare("SELECT id, location FROM jobs WHERE latitude IS NULL AND location <> ''")?;
+    let pending = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut geocoded = 0;
+    for (id, location) in pending {
+        if let Some(coordinates) = gazetteer.geocode(&location) {
+            conn.execute(
+                "UPDATE jobs SET latitude = ?, longitude = ? WHERE id = ?",
+                params![coordinates.lat, coordinates.lng, id],
+            )?;
+            geocoded += 1;
+        }
+    }
+    Ok(geocoded)
+}
+
+// Domicile principal (`secondary = false`) ou secondaire de l'utilisateur :
+// coordonnées enregistrées, ou à défaut géolocalisation de l'adresse saisie
+pub fn home_coordinates(
+    conn: &Connection,
+    user_id: i64,
+    secondary: bool,
+) -> Result<Option<Coordinates>, AppError> {
+    let query = if secondary {
+        "SELECT secondary_latitude, secondary_longitude, secondary_home FROM user_profiles WHERE user_id = ?"
+    } else {
+        "SELECT primary_latitude, primary_longitude, primary_home FROM user_profiles WHERE user_id = ?"
+    };
+
+    let home = conn
+        .query_row(query, [user_id], |row| {
+            Ok((
+                row.get::<_, Option<f64>>(0)?,
+                row.get::<_, Option<f64>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })
+        .optional()?;
+
+    Ok(match home {
+        Some((Some(lat), Some(lng), _)) => Some(Coordinates { lat, lng }),
+        Some((_, _, Some(address))) => Gazetteer::bundled().geocode(&address),
+        _ => None,
+    })
+}
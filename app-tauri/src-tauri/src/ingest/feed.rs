@@ -0,0 +1,352 @@
+use async_trait::async_trait;
+use chrono::DateTime;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::Deserialize;
+
+use crate::error::AppError;
+use crate::ingest::normalize::{clean_text, html_to_text};
+use crate::ingest::{fetch_text, http_client, IngestQuery, JobSource, RawJob};
+
+// Flux d'offres d'un site d'emploi : RSS 2.0, Atom ou JSON Feed. `location`
+// est une URL http(s) ou un chemin local (éventuellement préfixé par file://)
+pub struct FeedSource {
+    name: String,
+    location: String,
+}
+
+impl FeedSource {
+    // `name` est enregistré comme source des offres importées
+    pub fn new(name: &str, location: &str) -> Result<Self, AppError> {
+        let name = name.trim();
+        let location = location.trim();
+        if name.is_empty() {
+            return Err(AppError::Validation("Le nom du flux ne peut pas être vide".to_string()));
+        }
+        if location.is_empty() {
+            return Err(AppError::Validation("L'adresse du flux ne peut pas être vide".to_string()));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            location: location.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl JobSource for FeedSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Un flux publie toujours les mêmes offres : la requête est ignorée
+    async fn fetch(&self, _query: &IngestQuery) -> Result<String, AppError> {
+        if self.location.starts_with("http://") || self.location.starts_with("https://") {
+            let client = http_client()?;
+            return fetch_text(client.get(&self.location)).await;
+        }
+        let path = self.location.strip_prefix("file://").unwrap_or(&self.location);
+        Ok(tokio::fs::read_to_string(path).await?)
+    }
+
+    fn parse(&self, document: &str) -> Result<Vec<RawJob>, AppError> {
+        parse_feed(document)
+    }
+}
+
+// Détecte le format du flux et en extrait les offres
+pub fn parse_feed(document: &str) -> Result<Vec<RawJob>, AppError> {
+    let document = document.trim_start_matches('\u{feff}').trim();
+    if document.starts_with('{') {
+        return parse_json_feed(document);
+    }
+
+    let root = parse_xml(document)?;
+    match root.name.as_str() {
+        "rss" => Ok(root.children_named("channel").flat_map(|channel| channel.children_named("item")).map(rss_item).collect()),
+        // RSS 1.0 : les items sont frères du canal
+        "rdf" => Ok(root.children_named("item").map(rss_item).collect()),
+        "feed" => Ok(root.children_named("entry").map(atom_entry).collect()),
+        other => Err(AppError::Validation(format!("Format de flux non reconnu : <{}>", other))),
+    }
+}
+
+fn rss_item(item: &XmlElement) -> RawJob {
+    let guid = item.child_text(&["guid"]);
+    let url = item.child_text(&["link"]).or_else(|| guid.clone().filter(|guid| guid.starts_with("http")));
+    let description = item.child_text(&["encoded", "description"]).unwrap_or_default();
+    board_job(
+        item.child_text(&["title"]).unwrap_or_default(),
+        guid.or_else(|| url.clone()),
+        url.unwrap_or_default(),
+        item.child_text(&["pubdate", "date", "published"]),
+        &description,
+        item,
+    )
+}
+
+fn atom_entry(entry: &XmlElement) -> RawJob {
+    let url = entry
+        .children_named("link")
+        .find(|link| matches!(link.attr("rel"), None | Some("alternate")))
+        .and_then(|link| link.attr("href"))
+        .map(str::to_string);
+    let description = entry.child_text(&["content", "summary"]).unwrap_or_default();
+    let mut job = board_job(
+        entry.child_text(&["title"]).unwrap_or_default(),
+        entry.child_text(&["id"]).or_else(|| url.clone()),
+        url.unwrap_or_default(),
+        entry.child_text(&["published", "updated"]),
+        &description,
+        entry,
+    );
+    if job.company.is_empty() {
+        if let Some(author) = entry.children_named("author").find_map(|author| author.child_text(&["name"])) {
+            job.company = author;
+        }
+    }
+    job
+}
+
+// Champs communs à RSS et Atom, y compris les éléments propres aux sites
+// d'emploi (<company>, <location>, <salary>…) quel que soit leur espace de noms
+fn board_job(
+    title: String,
+    guid: Option<String>,
+    url: String,
+    published: Option<String>,
+    description: &str,
+    element: &XmlElement,
+) -> RawJob {
+    let mut job = RawJob {
+        external_id: guid,
+        title,
+        company: element.child_text(&["company", "companyname", "hiringorganization", "creator"]).unwrap_or_default(),
+        location: element.child_text(&["location", "joblocation", "region", "city"]).unwrap_or_default(),
+        description: html_to_text(description),
+        url,
+        posted_at: published.map(|date| feed_date(&date)),
+        salary: element.child_text(&["salary"]),
+        job_type: element.child_text(&["jobtype", "contracttype", "employmenttype"]),
+        ..Default::default()
+    };
+    split_board_title(&mut job);
+    job
+}
+
+// Les sites sans champ dédié mettent l'entreprise dans le titre :
+// « Acme: Développeur Rust », « Développeur Rust at Acme (Lyon) »,
+// « Développeur Rust chez Acme »
+fn split_board_title(job: &mut RawJob) {
+    if !job.company.is_empty() {
+        return;
+    }
+    let title = clean_text(&job.title);
+
+    if let Some((company, rest)) = title.split_once(": ") {
+        job.company = company.to_string();
+        job.title = rest.to_string();
+        return;
+    }
+
+    for separator in [" at ", " chez "] {
+        if let Some((rest, company)) = title.rsplit_once(separator) {
+            let (company, location) = match company.strip_suffix(')').and_then(|c| c.rsplit_once(" (")) {
+                Some((company, location)) => (company, location),
+                None => (company, ""),
+            };
+            job.title = rest.to_string();
+            job.company = company.to_string();
+            if job.location.is_empty() {
+                job.location = location.to_string();
+            }
+            return;
+        }
+    }
+}
+
+// Dates RFC 2822 (RSS) ramenées au format ISO ; les autres sont déjà ISO
+fn feed_date(raw: &str) -> String {
+    DateTime::parse_from_rfc2822(raw.trim())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|_| raw.trim().to_string())
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFeed {
+    version: String,
+    #[serde(default)]
+    items: Vec<JsonFeedItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFeedItem {
+    // Chaîne d'après la spécification, parfois un nombre en pratique
+    id: serde_json::Value,
+    url: Option<String>,
+    external_url: Option<String>,
+    #[serde(default)]
+    title: String,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    summary: Option<String>,
+    date_published: Option<String>,
+    author: Option<JsonFeedAuthor>,
+    #[serde(default)]
+    authors: Vec<JsonFeedAuthor>,
+    // Extension propre aux sites d'emploi
+    #[serde(rename = "_job")]
+    job: Option<JsonFeedJob>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFeedAuthor {
+    name: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct JsonFeedJob {
+    company: Option<String>,
+    location: Option<String>,
+    salary: Option<String>,
+    employment_type: Option<String>,
+}
+
+fn parse_json_feed(document: &str) -> Result<Vec<RawJob>, AppError> {
+    let feed: JsonFeed = serde_json::from_str(document)
+        .map_err(|e| AppError::Validation(format!("JSON Feed invalide : {}", e)))?;
+    if !feed.version.starts_with("https://jsonfeed.org/version/") {
+        return Err(AppError::Validation(format!("Version de JSON Feed inconnue : {}", feed.version)));
+    }
+
+    Ok(feed
+        .items
+        .into_iter()
+        .map(|item| {
+            let extension = item.job.unwrap_or_default();
+            let guid = match item.id {
+                serde_json::Value::String(id) => Some(id),
+                serde_json::Value::Null => None,
+                other => Some(other.to_string()),
+            };
+            let description = match (item.content_html, item.content_text, item.summary) {
+                (Some(html), _, _) => html_to_text(&html),
+                (None, Some(text), _) | (None, None, Some(text)) => text,
+                (None, None, None) => String::new(),
+            };
+            let author = item.authors.into_iter().chain(item.author).find_map(|author| author.name);
+
+            let mut job = RawJob {
+                external_id: guid,
+                title: item.title,
+                company: extension.company.or(author).unwrap_or_default(),
+                location: extension.location.unwrap_or_default(),
+                description,
+                url: item.url.or(item.external_url).unwrap_or_default(),
+                posted_at: item.date_published,
+                salary: extension.salary,
+                job_type: extension.employment_type,
+                ..Default::default()
+            };
+            split_board_title(&mut job);
+            job
+        })
+        .collect())
+}
+
+// Arbre XML minimal : noms locaux en minuscules (sans espace de noms)
+#[derive(Debug, Default)]
+struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<XmlElement>,
+}
+
+impl XmlElement {
+    fn from_start(start: &BytesStart) -> Self {
+        let attributes = start
+            .attributes()
+            .flatten()
+            .map(|attribute| {
+                let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).to_lowercase();
+                let value = attribute
+                    .unescape_value()
+                    .map(|value| value.into_owned())
+                    .unwrap_or_else(|_| String::from_utf8_lossy(&attribute.value).into_owned());
+                (key, value)
+            })
+            .collect();
+        Self {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).to_lowercase(),
+            attributes,
+            ..Default::default()
+        }
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    // Texte du premier enfant non vide, dans l'ordre de préférence des noms
+    fn child_text(&self, names: &[&str]) -> Option<String> {
+        names.iter().find_map(|name| {
+            self.children_named(name).map(|child| child.text.trim().to_string()).find(|text| !text.is_empty())
+        })
+    }
+}
+
+fn parse_xml(document: &str) -> Result<XmlElement, AppError> {
+    let invalid = |e: quick_xml::Error| AppError::Validation(format!("Flux XML invalide : {}", e));
+    let mut reader = Reader::from_str(document);
+    reader.trim_text(true);
+
+    let mut stack = vec![XmlElement::default()];
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(start) => stack.push(XmlElement::from_start(&start)),
+            Event::Empty(start) => {
+                let element = XmlElement::from_start(&start);
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(element);
+                }
+            }
+            Event::End(_) => {
+                let element = stack.pop().filter(|_| !stack.is_empty());
+                match (element, stack.last_mut()) {
+                    (Some(element), Some(parent)) => parent.children.push(element),
+                    _ => return Err(AppError::Validation("Flux XML invalide : balise fermante orpheline".to_string())),
+                }
+            }
+            Event::Text(text) => {
+                // Les entités HTML (&eacute;…) inconnues de XML sont conservées telles quelles
+                let content = text
+                    .unescape()
+                    .map(|content| content.into_owned())
+                    .unwrap_or_else(|_| String::from_utf8_lossy(&text).into_owned());
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&content);
+                }
+            }
+            Event::CData(data) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&String::from_utf8_lossy(&data.into_inner()));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if stack.len() != 1 {
+        return Err(AppError::Validation("Flux XML invalide : document incomplet".to_string()));
+    }
+    stack
+        .pop()
+        .and_then(|document| document.children.into_iter().next())
+        .ok_or_else(|| AppError::Validation("Flux XML vide".to_string()))
+}
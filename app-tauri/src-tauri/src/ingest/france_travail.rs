@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::error::AppError;
+use crate::geo::Gazetteer;
+use crate::ingest::{fetch_text, http_client, IngestQuery, JobSource, RawJob};
+
+const BASE_URL: &str = "https://api.francetravail.io/partenaire/offresdemploi/v2";
+const PAGE_SIZE: u32 = 50;
+
+// API « Offres d'emploi v2 » de France Travail (réponse JSON). Le jeton
+// d'accès OAuth est obtenu en dehors de l'application
+pub struct FranceTravailSource {
+    base_url: String,
+    token: String,
+}
+
+impl FranceTravailSource {
+    pub const NAME: &'static str = "france_travail";
+
+    pub fn new(token: String) -> Self {
+        Self::with_base_url(BASE_URL, token)
+    }
+
+    pub fn with_base_url(base_url: &str, token: String) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    resultats: Vec<Offer>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Offer {
+    id: String,
+    #[serde(default)]
+    intitule: String,
+    #[serde(default)]
+    description: String,
+    date_creation: Option<String>,
+    lieu_travail: Option<Place>,
+    entreprise: Option<Company>,
+    type_contrat: Option<String>,
+    experience_libelle: Option<String>,
+    #[serde(default)]
+    competences: Vec<Label>,
+    salaire: Option<Salary>,
+    origine_offre: Option<Origin>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Place {
+    #[serde(default)]
+    libelle: String,
+    code_postal: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Company {
+    nom: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Label {
+    libelle: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Salary {
+    libelle: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Origin {
+    url_origine: Option<String>,
+}
+
+// « 69 - LYON 03 » (code postal 69003) devient « 69003 Lyon 03 »
+fn location_label(place: &Place) -> String {
+    let city = place.libelle.split_once(" - ").map_or(place.libelle.as_str(), |(_, city)| city);
+    let city = city
+        .split(' ')
+        .map(|word| {
+            let lower = word.to_lowercase();
+            let mut chars = lower.chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+    match place.code_postal.as_deref() {
+        Some(code) if !code.is_empty() => format!("{} {}", code, city),
+        _ => city,
+    }
+}
+
+#[async_trait]
+impl JobSource for FranceTravailSource {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    async fn fetch(&self, query: &IngestQuery) -> Result<String, AppError> {
+        let page = query.page.unwrap_or(1).max(1);
+        let start = (page - 1) * PAGE_SIZE;
+        let mut params = vec![
+            ("motsCles", query.keywords.clone()),
+            ("range", format!("{}-{}", start, start + PAGE_SIZE - 1)),
+        ];
+        // L'API filtre par département, déduit du lieu saisi
+        if let Some(commune) = Gazetteer::bundled().locate(&query.location) {
+            params.push(("departement", commune.department.clone()));
+        }
+        let client = http_client()?;
+        let request = client
+            .get(format!("{}/offres/search", self.base_url))
+            .bearer_auth(&self.token)
+            .query(&params);
+        fetch_text(request).await
+    }
+
+    fn parse(&self, document: &str) -> Result<Vec<RawJob>, AppError> {
+        // L'API répond 204 sans contenu quand rien ne correspond
+        if document.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let response: SearchResponse = serde_json::from_str(document)
+            .map_err(|e| AppError::Validation(format!("Réponse France Travail invalide : {}", e)))?;
+
+        Ok(response
+            .resultats
+            .into_iter()
+            .map(|offer| RawJob {
+                url: offer
+                    .origine_offre
+                    .and_then(|origin| origin.url_origine)
+                    .unwrap_or_else(|| {
+                        format!("https://candidat.francetravail.fr/offres/recherche/detail/{}", offer.id)
+                    }),
+                external_id: Some(offer.id),
+                title: offer.intitule,
+                company: offer.entreprise.and_then(|company| company.nom).unwrap_or_default(),
+                location: offer.lieu_travail.as_ref().map(location_label).unwrap_or_default(),
+                description: offer.description,
+                posted_at: offer.date_creation,
+                salary: offer.salaire.and_then(|salary| salary.libelle),
+                job_type: offer.type_contrat,
+                experience: offer.experience_libelle,
+                skills: offer.competences.into_iter().filter_map(|skill| skill.libelle).collect(),
+                ..Default::default()
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::error::AppError;
+use crate::ingest::{fetch_text, http_client, normalize::clean_text, IngestQuery, JobSource, RawJob};
+
+const BASE_URL: &str = "https://fr.indeed.com";
+// Indeed pagine par tranches de 10 offres
+const PAGE_SIZE: u32 = 10;
+
+// Page de résultats HTML d'Indeed France
+pub struct IndeedSource {
+    base_url: String,
+}
+
+impl IndeedSource {
+    pub const NAME: &'static str = "indeed";
+
+    pub fn new() -> Self {
+        Self::with_base_url(BASE_URL)
+    }
+
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl Default for IndeedSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("sélecteur CSS invalide")
+}
+
+fn first_text(card: &ElementRef, css: &str) -> Option<String> {
+    card.select(&selector(css))
+        .next()
+        .map(|element| clean_text(&element.text().collect::<String>()))
+        .filter(|text| !text.is_empty())
+}
+
+#[async_trait]
+impl JobSource for IndeedSource {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    async fn fetch(&self, query: &IngestQuery) -> Result<String, AppError> {
+        let mut params = vec![("q", query.keywords.clone()), ("l", query.location.clone())];
+        if let Some(page) = query.page.filter(|&page| page > 1) {
+            params.push(("start", ((page - 1) * PAGE_SIZE).to_string()));
+        }
+        if query.remote {
+            // Filtre « Télétravail » d'Indeed
+            params.push(("sc", "0kf:attr(DSQF7);".to_string()));
+        }
+        let client = http_client()?;
+        fetch_text(client.get(format!("{}/emplois", self.base_url)).query(&params)).await
+    }
+
+    fn parse(&self, document: &str) -> Result<Vec<RawJob>, AppError> {
+        let html = Html::parse_document(document);
+        let mut jobs = Vec::new();
+
+        for card in html.select(&selector("div.job_seen_beacon")) {
+            let link = card.select(&selector("h2.jobTitle a")).next();
+            let external_id = link
+                .and_then(|a| a.value().attr("data-jk"))
+                .or_else(|| card.value().attr("data-jk"))
+                .map(str::to_string);
+            let title = card
+                .select(&selector("h2.jobTitle span[title]"))
+                .next()
+                .and_then(|span| span.value().attr("title"))
+                .map(clean_text)
+                .or_else(|| first_text(&card, "h2.jobTitle"))
+                .unwrap_or_default();
+            // Lien canonique de l'offre plutôt que le lien de suivi des clics
+            let url = match (&external_id, link.and_then(|a| a.value().attr("href"))) {
+                (Some(jk), _) => format!("{}/viewjob?jk={}", self.base_url, jk),
+                (None, Some(href)) if href.starts_with("http") => href.to_string(),
+                (None, Some(href)) => format!("{}{}", self.base_url, href),
+                (None, None) => String::new(),
+            };
+            let metadata: Vec<String> = card
+                .select(&selector("div.metadata, div.attribute_snippet"))
+                .map(|element| clean_text(&element.text().collect::<String>()))
+                .filter(|text| !text.is_empty())
+                .collect();
+            // L'extrait est une liste à puces, parfois un simple paragraphe
+            let items: Vec<String> = card
+                .select(&selector("div.job-snippet li"))
+                .map(|li| clean_text(&li.text().collect::<String>()))
+                .filter(|text| !text.is_empty())
+                .collect();
+            let description = if items.is_empty() {
+                first_text(&card, "div.job-snippet").unwrap_or_default()
+            } else {
+                items.join("\n")
+            };
+
+            jobs.push(RawJob {
+                external_id,
+                title,
+                company: first_text(&card, "[data-testid=company-name], span.companyName").unwrap_or_default(),
+                location: first_text(&card, "[data-testid=text-location], div.companyLocation").unwrap_or_default(),
+                description,
+                url,
+                posted_at: first_text(&card, "[data-testid=myJobsStateDate], span.date"),
+                salary: first_text(&card, "div.salary-snippet-container, div.salaryOnly"),
+                job_type: metadata.iter().find(|text| !text.contains('€')).cloned(),
+                ..Default::default()
+            });
+        }
+
+        Ok(jobs)
+    }
+}
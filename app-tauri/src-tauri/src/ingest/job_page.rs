@@ -0,0 +1,285 @@
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use scraper::{Html, Selector};
+use serde_json::Value;
+
+use crate::database::operations::{job_from_row, scored_job_columns, SCORED_JOBS_FROM};
+use crate::error::AppError;
+use crate::ingest::normalize::{clean_text, html_to_text, normalize_job};
+use crate::ingest::{upsert_job, RawJob};
+use crate::matching::score_and_store_job;
+use crate::time::now_timestamp;
+use crate::models::{Job, SalaryRange};
+
+// Source enregistrée quand la page ne donne pas son adresse
+pub const UNKNOWN_PAGE_SOURCE: &str = "html";
+
+// Importe l'offre d'une page enregistrée depuis le navigateur et renvoie
+// l'offre telle qu'enregistrée, notée aussitôt pour `user_id`. `page_url`
+// complète une page sans adresse
+pub fn import_job_page(
+    conn: &Connection,
+    user_id: i64,
+    html: &str,
+    page_url: Option<&str>,
+    today: NaiveDate,
+) -> Result<Job, AppError> {
+    let raw = parse_job_page(html, page_url)?;
+    let source = page_source(&raw.url);
+    let job = normalize_job(&source, raw, today)
+        .ok_or_else(|| AppError::Validation("L'offre de la page n'a pas de titre".to_string()))?;
+
+    let id = upsert_job(conn, &job)?;
+    score_and_store_job(conn, user_id, id, &now_timestamp())?;
+    let sql = format!("SELECT {} FROM {} WHERE jobs.id = ?", scored_job_columns(), SCORED_JOBS_FROM);
+    Ok(conn.query_row(&sql, [user_id, id], job_from_row)?)
+}
+
+// Extrait l'offre d'une page HTML : JSON-LD schema.org `JobPosting` en
+// priorité, complété ou remplacé par les balises OpenGraph et meta
+pub fn parse_job_page(html: &str, page_url: Option<&str>) -> Result<RawJob, AppError> {
+    let document = Html::parse_document(html);
+    let meta = PageMeta::read(&document);
+
+    let mut job = json_ld_postings(&document).first().map(posting_to_raw).unwrap_or_default();
+    if job.title.is_empty() {
+        job.title = meta.title.clone().unwrap_or_default();
+    }
+    if job.title.is_empty() {
+        return Err(AppError::Validation("Aucune offre d'emploi trouvée dans la page".to_string()));
+    }
+    if job.description.is_empty() {
+        job.description = meta.description.clone().unwrap_or_default();
+    }
+    if job.posted_at.is_none() {
+        job.posted_at = meta.published.clone();
+    }
+    if job.url.is_empty() {
+        job.url = meta
+            .url
+            .clone()
+            .or_else(|| page_url.map(str::to_string))
+            .or_else(|| saved_from_url(html))
+            .unwrap_or_default();
+    }
+    if job.external_id.is_none() && !job.url.is_empty() {
+        job.external_id = Some(job.url.clone());
+    }
+    Ok(job)
+}
+
+// Domaine de l'offre (« welcometothejungle.com »), à défaut « html »
+fn page_source(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.trim_start_matches("www.").to_string()))
+        .unwrap_or_else(|| UNKNOWN_PAGE_SOURCE.to_string())
+}
+
+// Chrome et Edge notent l'adresse d'origine : <!-- saved from url=(0042)https://… -->
+fn saved_from_url(html: &str) -> Option<String> {
+    let start = html.find("saved from url=(")?;
+    let rest = &html[start..];
+    let url = &rest[rest.find(')')? + 1..];
+    let end = url.find(char::is_whitespace).unwrap_or(url.len());
+    Some(url[..end].trim_end_matches("-->").to_string()).filter(|url| url.starts_with("http"))
+}
+
+#[derive(Debug, Default)]
+struct PageMeta {
+    title: Option<String>,
+    description: Option<String>,
+    url: Option<String>,
+    published: Option<String>,
+}
+
+impl PageMeta {
+    fn read(document: &Html) -> Self {
+        let content = |css: &str| {
+            let selector = Selector::parse(css).expect("sélecteur CSS invalide");
+            document
+                .select(&selector)
+                .filter_map(|element| element.value().attr("content").or_else(|| element.value().attr("href")))
+                .map(clean_text)
+                .find(|value| !value.is_empty())
+        };
+        let title_tag = || {
+            let selector = Selector::parse("title").expect("sélecteur CSS invalide");
+            document
+                .select(&selector)
+                .map(|title| clean_text(&title.text().collect::<String>()))
+                .find(|title| !title.is_empty())
+        };
+
+        Self {
+            title: content("meta[property='og:title'], meta[name='twitter:title']").or_else(title_tag),
+            description: content(
+                "meta[property='og:description'], meta[name='description'], meta[name='twitter:description']",
+            ),
+            url: content("meta[property='og:url'], link[rel='canonical']"),
+            published: content("meta[property='article:published_time'], meta[itemprop='datePosted']"),
+        }
+    }
+}
+
+// Objets `JobPosting` des blocs JSON-LD, y compris dans un @graph ou un tableau
+fn json_ld_postings(document: &Html) -> Vec<Value> {
+    let selector = Selector::parse("script[type='application/ld+json']").expect("sélecteur CSS invalide");
+    let mut postings = Vec::new();
+    for script in document.select(&selector) {
+        let text = script.text().collect::<String>();
+        // Certains sites terminent le bloc par un « ; » ou l'entourent de commentaires
+        let text = text.trim().trim_start_matches("<!--").trim_end_matches("-->").trim().trim_end_matches(';');
+        match serde_json::from_str::<Value>(text) {
+            Ok(value) => collect_postings(value, &mut postings),
+            Err(e) => log::warn!("Bloc JSON-LD illisible : {}", e),
+        }
+    }
+    postings
+}
+
+fn collect_postings(value: Value, postings: &mut Vec<Value>) {
+    match value {
+        Value::Array(items) => items.into_iter().for_each(|item| collect_postings(item, postings)),
+        Value::Object(mut object) => {
+            let is_posting = match object.get("@type") {
+                Some(Value::String(kind)) => kind == "JobPosting",
+                Some(Value::Array(kinds)) => kinds.iter().any(|kind| kind == "JobPosting"),
+                _ => false,
+            };
+            if is_posting {
+                postings.push(Value::Object(object));
+            } else if let Some(graph) = object.remove("@graph") {
+                collect_postings(graph, postings);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn posting_to_raw(posting: &Value) -> RawJob {
+    let location = first(&posting["jobLocation"]);
+    let remote = text(&posting["jobLocationType"]).is_some_and(|kind| kind.eq_ignore_ascii_case("TELECOMMUTE"));
+
+    RawJob {
+        // PropertyValue { name: <émetteur>, value: <identifiant> } ou simple chaîne
+        external_id: text(&first(&posting["identifier"])["value"]).or_else(|| text(first(&posting["identifier"]))),
+        title: text(&posting["title"]).or_else(|| text(&posting["name"])).unwrap_or_default(),
+        company: text(first(&posting["hiringOrganization"])).unwrap_or_default(),
+        location: location_label(location).unwrap_or_else(|| if remote { "Télétravail".to_string() } else { String::new() }),
+        description: text(&posting["description"]).map(|html| description_text(&html)).unwrap_or_default(),
+        url: text(&posting["url"]).unwrap_or_default(),
+        posted_at: text(&posting["datePosted"]),
+        salary_range: salary_range(&posting["baseSalary"]).or_else(|| salary_range(first(&posting["estimatedSalary"]))),
+        valid_through: text(&posting["validThrough"]),
+        job_type: text(first(&posting["employmentType"])),
+        experience: experience(&posting["experienceRequirements"]),
+        remote,
+        skills: match &posting["skills"] {
+            Value::Array(skills) => skills.iter().filter_map(text).collect(),
+            other => text(other)
+                .map(|skills| skills.split(',').map(clean_text).filter(|s| !s.is_empty()).collect())
+                .unwrap_or_default(),
+        },
+        ..Default::default()
+    }
+}
+
+// Premier élément d'un tableau, ou la valeur elle-même
+fn first(value: &Value) -> &Value {
+    match value {
+        Value::Array(items) => items.first().unwrap_or(&Value::Null),
+        other => other,
+    }
+}
+
+// Texte d'une valeur JSON-LD : chaîne, nombre, ou objet nommé (`name`, `value`)
+fn text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(text) => clean_text(text),
+        Value::Number(number) => number.to_string(),
+        Value::Object(object) => return ["name", "value", "@value"].iter().find_map(|key| object.get(*key).and_then(text)),
+        _ => return None,
+    };
+    Some(text).filter(|text| !text.is_empty())
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().replace([' ', '\u{a0}'], "").replace(',', ".").parse().ok(),
+        _ => None,
+    }
+}
+
+// La description est du HTML, parfois échappé une seconde fois (&lt;p&gt;)
+fn description_text(html: &str) -> String {
+    let text = html_to_text(html);
+    if text.contains('<') && text.contains('>') {
+        html_to_text(&text)
+    } else {
+        text
+    }
+}
+
+// Place → PostalAddress : « 69003 Lyon », à défaut la région ou le libellé
+fn location_label(place: &Value) -> Option<String> {
+    let address = first(&place["address"]);
+    if let Value::String(address) = address {
+        return Some(clean_text(address)).filter(|address| !address.is_empty());
+    }
+    let locality = text(&address["addressLocality"]).or_else(|| text(&address["addressRegion"]));
+    match (text(&address["postalCode"]), locality) {
+        (Some(postcode), Some(locality)) => Some(format!("{} {}", postcode, locality)),
+        (None, Some(locality)) => Some(locality),
+        (Some(postcode), None) => Some(postcode),
+        (None, None) => text(&place["name"]),
+    }
+}
+
+// MonetaryAmount { currency, value: QuantitativeValue { minValue, maxValue, value, unitText } }
+fn salary_range(amount: &Value) -> Option<SalaryRange> {
+    if amount.is_null() {
+        return None;
+    }
+    let value = &amount["value"];
+    let (min, max, unit) = match value {
+        Value::Object(_) => {
+            let exact = number(&value["value"]);
+            (
+                number(&value["minValue"]).or(exact),
+                number(&value["maxValue"]).or(exact),
+                text(&value["unitText"]).or_else(|| text(&amount["unitText"])),
+            )
+        }
+        other => {
+            let exact = number(other);
+            (exact, exact, text(&amount["unitText"]))
+        }
+    };
+    if min.is_none() && max.is_none() {
+        return None;
+    }
+    let period = match unit.map(|unit| unit.to_uppercase()).as_deref() {
+        Some("HOUR") => "hourly",
+        Some("DAY") => "daily",
+        Some("WEEK") => "weekly",
+        Some("MONTH") => "monthly",
+        _ => "yearly",
+    };
+    Some(SalaryRange {
+        min,
+        max,
+        currency: text(&amount["currency"]),
+        period: Some(period.to_string()),
+    })
+}
+
+// « 3 ans d'expérience » ou OccupationalExperienceRequirements { monthsOfExperience }
+fn experience(requirements: &Value) -> Option<String> {
+    let requirements = first(requirements);
+    match number(&requirements["monthsOfExperience"]) {
+        Some(months) => Some(format!("{} ans", months / 12.0)),
+        None => text(requirements).or_else(|| text(&requirements["description"])),
+    }
+}
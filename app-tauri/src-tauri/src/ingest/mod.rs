@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::error::AppError;
+use crate::models::SalaryRange;
+
+pub mod feed;
+pub mod france_travail;
+pub mod indeed;
+pub mod job_page;
+pub mod normalize;
+pub mod runner;
+
+pub use feed::FeedSource;
+pub use france_travail::FranceTravailSource;
+pub use indeed::IndeedSource;
+pub use normalize::NormalizedJob;
+pub use runner::{ingest_document, upsert_job, upsert_jobs};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+const USER_AGENT: &str = concat!("JobAppTauri/", env!("CARGO_PKG_VERSION"));
+
+// Paramètres transmis à une source pour une collecte
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IngestQuery {
+    pub keywords: String,
+    #[serde(default)]
+    pub location: String,
+    #[serde(default)]
+    pub remote: bool,
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+// Offre telle qu'extraite d'une page ou d'une réponse d'API, avant
+// normalisation. Les champs restent bruts (salaire en texte, date relative…)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RawJob {
+    pub external_id: Option<String>,
+    pub title: String,
+    pub company: String,
+    pub location: String,
+    pub description: String,
+    pub url: String,
+    pub posted_at: Option<String>,
+    pub salary: Option<String>,
+    // Salaire déjà structuré (JSON-LD), prioritaire sur `salary`
+    pub salary_range: Option<SalaryRange>,
+    pub valid_through: Option<String>,
+    pub job_type: Option<String>,
+    pub experience: Option<String>,
+    pub remote: bool,
+    pub skills: Vec<String>,
+}
+
+// Une source d'offres : récupération du document brut (réseau), extraction
+// des offres, puis normalisation vers `models::Job`. `parse` et `normalize`
+// sont synchrones et sans réseau, ce qui permet de les valider sur des
+// réponses enregistrées
+#[async_trait]
+pub trait JobSource: Send + Sync {
+    // Identifiant stocké dans `jobs.source`
+    fn name(&self) -> &str;
+
+    async fn fetch(&self, query: &IngestQuery) -> Result<String, AppError>;
+
+    fn parse(&self, document: &str) -> Result<Vec<RawJob>, AppError>;
+
+    fn normalize(&self, raw: RawJob, today: NaiveDate) -> Option<NormalizedJob> {
+        normalize::normalize_job(self.name(), raw, today)
+    }
+}
+
+// Sources disponibles depuis le frontend
+pub fn source_by_name(name: &str) -> Result<Box<dyn JobSource>, AppError> {
+    match name {
+        IndeedSource::NAME => Ok(Box::new(IndeedSource::new())),
+        FranceTravailSource::NAME => {
+            let token = std::env::var("FRANCE_TRAVAIL_TOKEN").map_err(|_| {
+                AppError::Configuration("FRANCE_TRAVAIL_TOKEN n'est pas défini".to_string())
+            })?;
+            Ok(Box::new(FranceTravailSource::new(token)))
+        }
+        _ => Err(AppError::Validation(format!("Source d'offres inconnue : {}", name))),
+    }
+}
+
+pub fn source_names() -> Vec<&'static str> {
+    vec![IndeedSource::NAME, FranceTravailSource::NAME]
+}
+
+pub(crate) fn http_client() -> Result<reqwest::Client, AppError> {
+    reqwest::Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|e| AppError::Network(e.to_string()))
+}
+
+// Exécute la requête et renvoie le corps, en erreur si le statut n'est pas 2xx
+pub(crate) async fn fetch_text(request: reqwest::RequestBuilder) -> Result<String, AppError> {
+    let response = request.send().await.map_err(|e| AppError::Network(e.to_string()))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AppError::Network(format!("Réponse HTTP {}", status)));
+    }
+    response.text().await.map_err(|e| AppError::Network(e.to_string()))
+}
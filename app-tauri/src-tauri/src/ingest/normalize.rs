@@ -0,0 +1,429 @@
+use chrono::{Duration, NaiveDate};
+use scraper::{node::Node, Html};
+
+use crate::ingest::RawJob;
+use crate::models::{CommuteTimes, Job, SalaryRange};
+use crate::skills::SkillTaxonomy;
+
+// Conversion des salaires en brut annuel (35 h × 52 semaines, 218 jours travaillés)
+const HOURS_PER_YEAR: f64 = 35.0 * 52.0;
+const DAYS_PER_YEAR: f64 = 218.0;
+const MONTHS_PER_YEAR: f64 = 12.0;
+const WEEKS_PER_YEAR: f64 = 52.0;
+
+const UNKNOWN_COMPANY: &str = "Entreprise inconnue";
+
+// Compétences reconnues dans les descriptions (motif en minuscules, libellé)
+const KNOWN_SKILLS: &[(&str, &str)] = &[
+    ("python", "Python"),
+    ("java", "Java"),
+    ("javascript", "JavaScript"),
+    ("typescript", "TypeScript"),
+    ("rust", "Rust"),
+    ("c++", "C++"),
+    ("c#", "C#"),
+    ("php", "PHP"),
+    ("ruby", "Ruby"),
+    ("golang", "Go"),
+    ("kotlin", "Kotlin"),
+    ("swift", "Swift"),
+    ("html", "HTML"),
+    ("css", "CSS"),
+    ("sql", "SQL"),
+    ("nosql", "NoSQL"),
+    ("react", "React"),
+    ("angular", "Angular"),
+    ("vue.js", "Vue.js"),
+    ("node.js", "Node.js"),
+    ("django", "Django"),
+    ("flask", "Flask"),
+    ("spring", "Spring"),
+    ("laravel", "Laravel"),
+    ("docker", "Docker"),
+    ("kubernetes", "Kubernetes"),
+    ("terraform", "Terraform"),
+    ("aws", "AWS"),
+    ("azure", "Azure"),
+    ("gcp", "GCP"),
+    ("linux", "Linux"),
+    ("git", "Git"),
+    ("ci/cd", "CI/CD"),
+    ("jenkins", "Jenkins"),
+    ("gitlab", "GitLab"),
+    ("agile", "Agile"),
+    ("scrum", "Scrum"),
+    ("jira", "Jira"),
+    ("machine learning", "Machine Learning"),
+    ("deep learning", "Deep Learning"),
+    ("data science", "Data Science"),
+    ("spark", "Spark"),
+    ("kafka", "Kafka"),
+    ("elasticsearch", "Elasticsearch"),
+    ("mongodb", "MongoDB"),
+    ("mysql", "MySQL"),
+    ("postgresql", "PostgreSQL"),
+    ("oracle", "Oracle"),
+    ("graphql", "GraphQL"),
+    ("microservices", "Microservices"),
+    ("api rest", "API REST"),
+];
+
+// Offre normalisée, prête à être enregistrée. `Job` ne porte ni le type de
+// contrat ni le télétravail, stockés dans leurs propres colonnes
+#[derive(Debug, Clone)]
+pub struct NormalizedJob {
+    pub job: Job,
+    pub external_id: Option<String>,
+    pub job_type: String,
+    pub remote: bool,
+}
+
+// Une offre sans titre est inexploitable : elle est écartée
+pub fn normalize_job(source: &str, raw: RawJob, today: NaiveDate) -> Option<NormalizedJob> {
+    let title = clean_text(&raw.title);
+    if title.is_empty() {
+        return None;
+    }
+    let company = match clean_text(&raw.company) {
+        company if company.is_empty() => UNKNOWN_COMPANY.to_string(),
+        company => company,
+    };
+    let location = clean_text(&raw.location);
+    let description = clean_multiline(&raw.description);
+
+    let remote = raw.remote || [&title, &location].iter().any(|text| mentions_remote(text));
+    let experience_level = raw
+        .experience
+        .as_deref()
+        .and_then(normalize_experience)
+        .or_else(|| normalize_experience(&title));
+
+    // Noms canoniques du référentiel : « JS » et « Javascript » deviennent « JavaScript »
+    let skills: Vec<String> = raw.skills.iter().map(|s| clean_text(s)).chain(extract_skills(&description)).collect();
+    let skills = SkillTaxonomy::bundled().canonicalize(&skills);
+
+    Some(NormalizedJob {
+        job: Job {
+            id: 0,
+            title,
+            company,
+            location,
+            description,
+            url: raw.url.trim().to_string(),
+            source: source.to_string(),
+            published_at: raw.posted_at.as_deref().and_then(|p| parse_posted_at(p, today)).unwrap_or_default(),
+            valid_through: raw.valid_through.as_deref().and_then(|v| parse_posted_at(v, today)),
+            salary: raw.salary_range.map(annualize_salary).or_else(|| raw.salary.as_deref().and_then(parse_salary)),
+            matching_score: 0.0,
+            commute_times: CommuteTimes::default(),
+            skills: Some(skills),
+            experience_level,
+            applied_at: None,
+            status: None,
+        },
+        external_id: raw.external_id.map(|id| id.trim().to_string()).filter(|id| !id.is_empty()),
+        job_type: raw.job_type.as_deref().map(normalize_job_type).unwrap_or_default(),
+        remote,
+    })
+}
+
+pub fn clean_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Comme `clean_text`, en conservant les retours à la ligne
+fn clean_multiline(text: &str) -> String {
+    text.lines().map(clean_text).filter(|line| !line.is_empty()).collect::<Vec<_>>().join("\n")
+}
+
+// Texte d'un fragment HTML (descriptions des flux), un bloc par ligne
+pub fn html_to_text(html: &str) -> String {
+    const BLOCKS: &[&str] = &["p", "br", "li", "div", "tr", "h1", "h2", "h3", "h4", "h5", "h6"];
+    let fragment = Html::parse_fragment(html);
+    let mut text = String::new();
+    for node in fragment.root_element().descendants() {
+        match node.value() {
+            Node::Text(content) => text.push_str(content),
+            Node::Element(element) if BLOCKS.contains(&element.name()) => text.push('\n'),
+            _ => {}
+        }
+    }
+    clean_multiline(&text)
+}
+
+fn mentions_remote(text: &str) -> bool {
+    let text = text.to_lowercase();
+    ["télétravail", "teletravail", "remote", "à distance"].iter().any(|word| text.contains(word))
+}
+
+// Salaire brut annuel en euros. Accepte « 45 000 € - 55 000 € par an »,
+// « 45k€ », « Mensuel de 2000.00 Euros à 2500.00 Euros sur 12 mois »,
+// « 15 € de l'heure »…
+pub fn parse_salary(text: &str) -> Option<SalaryRange> {
+    let lower = text.to_lowercase();
+    // « sur 12 mois », « sur 151.67 heures » : durée, pas un montant
+    let amounts_part = lower.split(" sur ").next().unwrap_or_default();
+    let amounts = parse_amounts(amounts_part);
+    let min = *amounts.first()?;
+    let max = amounts.get(1).copied().unwrap_or(min);
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+
+    let per_year = match salary_period(&lower) {
+        Some(SalaryPeriod::Yearly) => 1.0,
+        Some(SalaryPeriod::Monthly) => MONTHS_PER_YEAR,
+        Some(SalaryPeriod::Daily) => DAYS_PER_YEAR,
+        Some(SalaryPeriod::Hourly) => HOURS_PER_YEAR,
+        // Sans unité, l'ordre de grandeur tranche
+        None if max >= 10_000.0 => 1.0,
+        None if max >= 1_000.0 => MONTHS_PER_YEAR,
+        None => HOURS_PER_YEAR,
+    };
+
+    Some(SalaryRange {
+        min: Some((min * per_year).round()),
+        max: Some((max * per_year).round()),
+        currency: Some("EUR".to_string()),
+        period: Some("yearly".to_string()),
+    })
+}
+
+// Ramène un salaire structuré (période « monthly », « hourly »…) en annuel
+pub fn annualize_salary(salary: SalaryRange) -> SalaryRange {
+    let per_year = match salary.period.as_deref() {
+        Some("monthly") => MONTHS_PER_YEAR,
+        Some("weekly") => WEEKS_PER_YEAR,
+        Some("daily") => DAYS_PER_YEAR,
+        Some("hourly") => HOURS_PER_YEAR,
+        _ => 1.0,
+    };
+    SalaryRange {
+        min: salary.min.map(|min| (min * per_year).round()),
+        max: salary.max.map(|max| (max * per_year).round()),
+        currency: salary.currency.or_else(|| Some("EUR".to_string())),
+        period: Some("yearly".to_string()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SalaryPeriod {
+    Yearly,
+    Monthly,
+    Daily,
+    Hourly,
+}
+
+// L'unité citée en premier l'emporte (« Annuel de … sur 12 mois »)
+fn salary_period(text: &str) -> Option<SalaryPeriod> {
+    let markers: &[(&str, SalaryPeriod)] = &[
+        ("annuel", SalaryPeriod::Yearly),
+        ("par an", SalaryPeriod::Yearly),
+        ("/an", SalaryPeriod::Yearly),
+        ("year", SalaryPeriod::Yearly),
+        ("mensuel", SalaryPeriod::Monthly),
+        ("mois", SalaryPeriod::Monthly),
+        ("month", SalaryPeriod::Monthly),
+        ("journalier", SalaryPeriod::Daily),
+        ("par jour", SalaryPeriod::Daily),
+        ("/jour", SalaryPeriod::Daily),
+        ("tjm", SalaryPeriod::Daily),
+        ("day", SalaryPeriod::Daily),
+        ("horaire", SalaryPeriod::Hourly),
+        ("heure", SalaryPeriod::Hourly),
+        ("/h", SalaryPeriod::Hourly),
+        ("hour", SalaryPeriod::Hourly),
+    ];
+    markers
+        .iter()
+        .filter_map(|(marker, period)| text.find(marker).map(|position| (position, *period)))
+        .min_by_key(|(position, _)| *position)
+        .map(|(_, period)| period)
+}
+
+// Montants d'un texte : « 45 000 », « 45.000 », « 2 500,50 », « 40000.0 », « 45k »
+fn parse_amounts(text: &str) -> Vec<f64> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut amounts = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut integer = String::new();
+        let mut decimals = String::new();
+        loop {
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                integer.push(chars[i]);
+                i += 1;
+            }
+            let group_follows = |from: usize| {
+                from + 3 <= chars.len()
+                    && chars[from..from + 3].iter().all(|c| c.is_ascii_digit())
+                    && chars.get(from + 3).is_none_or(|c| !c.is_ascii_digit())
+            };
+            match chars.get(i) {
+                // Séparateur de milliers : espace (insécable ou non), point, virgule
+                Some(' ' | '\u{a0}' | '\u{202f}' | '.' | ',') if group_follows(i + 1) => i += 1,
+                Some('.' | ',') if chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()) => {
+                    i += 1;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        decimals.push(chars[i]);
+                        i += 1;
+                    }
+                    break;
+                }
+                _ => break,
+            }
+        }
+
+        let mut amount: f64 = format!("{}.{}0", integer, decimals).parse().unwrap_or(0.0);
+        let mut j = i;
+        while chars.get(j) == Some(&' ') {
+            j += 1;
+        }
+        if matches!(chars.get(j), Some('k' | 'K')) && chars.get(j + 1).is_none_or(|c| !c.is_alphabetic()) {
+            amount *= 1000.0;
+            i = j + 1;
+        }
+        if amount > 0.0 {
+            amounts.push(amount);
+        }
+    }
+    amounts
+}
+
+// Type de contrat, aligné sur les libellés de l'interface (CDI, CDD, Stage…).
+// Les codes France Travail (MIS, LIB…) sont reconnus tels quels
+pub fn normalize_job_type(raw: &str) -> String {
+    let raw = clean_text(raw);
+    match raw.to_uppercase().as_str() {
+        // Valeurs schema.org (JobPosting.employmentType)
+        "FULL_TIME" => return "CDI".to_string(),
+        "PART_TIME" => return "Temps partiel".to_string(),
+        "CONTRACTOR" => return "Freelance".to_string(),
+        "TEMPORARY" => return "CDD".to_string(),
+        "INTERN" => return "Stage".to_string(),
+        "CDI" => return "CDI".to_string(),
+        "CDD" | "SAI" => return "CDD".to_string(),
+        "MIS" => return "Intérim".to_string(),
+        "LIB" | "FRA" => return "Freelance".to_string(),
+        _ => {}
+    }
+
+    let lower = raw.to_lowercase();
+    let contains = |words: &[&str]| words.iter().any(|word| lower.contains(word));
+    if contains(&["cdi", "fulltime", "full-time", "permanent"]) {
+        "CDI".to_string()
+    } else if contains(&["cdd", "contract", "temporary", "durée déterminée"]) {
+        "CDD".to_string()
+    } else if contains(&["alternance", "apprenti", "professionnalisation"]) {
+        "Alternance".to_string()
+    } else if contains(&["stage", "internship", "stagiaire"]) {
+        "Stage".to_string()
+    } else if contains(&["intérim", "interim"]) {
+        "Intérim".to_string()
+    } else if contains(&["freelance", "indépendant", "portage"]) {
+        "Freelance".to_string()
+    } else if contains(&["parttime", "part-time", "temps partiel"]) {
+        "Temps partiel".to_string()
+    } else {
+        raw
+    }
+}
+
+// Niveau d'expérience (junior, mid, senior, lead) d'après un libellé
+// (« Débutant accepté », « 3 An(s) », « Senior »…) ou un titre
+pub fn normalize_experience(raw: &str) -> Option<String> {
+    let lower = raw.to_lowercase();
+    let contains = |words: &[&str]| words.iter().any(|word| lower.contains(word));
+    let level = if contains(&["lead", "principal", "head of", "manager"]) {
+        "lead"
+    } else if contains(&["senior", "confirmé", "expérimenté", "expert"]) {
+        "senior"
+    } else if contains(&["junior", "débutant", "jeune diplômé", "entry"]) {
+        "junior"
+    } else if let Some(years) = experience_years(&lower) {
+        match years {
+            y if y < 2.0 => "junior",
+            y if y < 5.0 => "mid",
+            _ => "senior",
+        }
+    } else if contains(&["intermédiaire", "mid-level", "mid level"]) {
+        "mid"
+    } else {
+        return None;
+    };
+    Some(level.to_string())
+}
+
+fn experience_years(text: &str) -> Option<f64> {
+    let mentions_years = ["an", "ans", "an(s)", "year", "years"];
+    let words: Vec<&str> = text.split_whitespace().collect();
+    words.windows(2).find_map(|pair| {
+        let years = pair[0].trim_start_matches('+').parse::<f64>().ok()?;
+        mentions_years.iter().any(|unit| pair[1].starts_with(unit)).then_some(years)
+    })
+}
+
+// Date de publication au format AAAA-MM-JJ : dates ISO ou françaises,
+// « il y a 3 jours », « Aujourd'hui », « Posted 2 days ago »…
+pub fn parse_posted_at(raw: &str, today: NaiveDate) -> Option<String> {
+    let raw = clean_text(raw);
+    let lower = raw.to_lowercase();
+
+    if let Some(date) = raw
+        .get(..10)
+        .and_then(|prefix| NaiveDate::parse_from_str(prefix, "%Y-%m-%d").ok())
+        .or_else(|| NaiveDate::parse_from_str(&raw, "%d/%m/%Y").ok())
+    {
+        return Some(date.format("%Y-%m-%d").to_string());
+    }
+
+    let days_ago = if ["aujourd'hui", "aujourd’hui", "à l'instant", "today", "just posted"]
+        .iter()
+        .any(|word| lower.contains(word))
+    {
+        Some(0)
+    } else if lower.contains("hier") || lower.contains("yesterday") {
+        Some(1)
+    } else {
+        let number = lower
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|part| !part.is_empty())
+            .and_then(|part| part.parse::<i64>().ok());
+        let unit_in_days = if lower.contains("semaine") || lower.contains("week") {
+            7
+        } else if lower.contains("mois") || lower.contains("month") {
+            30
+        } else if lower.contains("heure") || lower.contains("hour") || lower.contains("minute") {
+            0
+        } else if lower.contains("jour") || lower.contains("day") {
+            1
+        } else {
+            return None;
+        };
+        number.map(|n| n * unit_in_days)
+    }?;
+
+    Some((today - Duration::days(days_ago)).format("%Y-%m-%d").to_string())
+}
+
+// Compétences connues citées dans un texte, en mots entiers
+pub fn extract_skills(text: &str) -> Vec<String> {
+    let lower = text.to_lowercase();
+    KNOWN_SKILLS
+        .iter()
+        .filter(|(pattern, _)| contains_word(&lower, pattern))
+        .map(|(_, label)| label.to_string())
+        .collect()
+}
+
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '+' || c == '#';
+    text.match_indices(word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        before.is_none_or(|c| !is_word_char(c)) && after.is_none_or(|c| !is_word_char(c))
+    })
+}
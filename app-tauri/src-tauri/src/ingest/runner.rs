@@ -0,0 +1,280 @@
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashSet;
+
+use crate::database::executor::Db;
+use crate::error::AppError;
+use crate::geo::Gazetteer;
+use crate::ingest::{IngestQuery, JobSource, NormalizedJob};
+use crate::models::JobSearchStats;
+
+// Collecte complète : récupération réseau puis enregistrement
+pub async fn run_source(db: &Db, source: &dyn JobSource, query: &IngestQuery) -> Result<JobSearchStats, AppError> {
+    let document = source.fetch(query).await?;
+    let jobs = parse_and_normalize(source, &document, chrono::Local::now().date_naive())?;
+    db.call(move |conn| upsert_jobs(conn, &jobs)).await
+}
+
+// Extrait, normalise et enregistre les offres d'un document déjà récupéré
+// (page HTML, réponse JSON). `today` sert à dater les publications relatives
+pub fn ingest_document(
+    conn: &Connection,
+    source: &dyn JobSource,
+    document: &str,
+    today: NaiveDate,
+) -> Result<JobSearchStats, AppError> {
+    let jobs = parse_and_normalize(source, document, today)?;
+    upsert_jobs(conn, &jobs)
+}
+
+fn parse_and_normalize(
+    source: &dyn JobSource,
+    document: &str,
+    today: NaiveDate,
+) -> Result<Vec<NormalizedJob>, AppError> {
+    let raw_jobs = source.parse(document)?;
+    let found = raw_jobs.len();
+    let jobs: Vec<NormalizedJob> = raw_jobs.into_iter().filter_map(|raw| source.normalize(raw, today)).collect();
+    if jobs.len() < found {
+        log::warn!("{} : {} offre(s) sans titre ignorée(s)", source.name(), found - jobs.len());
+    }
+    Ok(jobs)
+}
+
+// Insère les nouvelles offres et met à jour celles déjà connues, dans une
+// seule transaction. Une offre est reconnue par son identifiant chez la
+// source, à défaut par son URL, à défaut par (source, titre, entreprise, lieu).
+// `total` compte les offres distinctes du lot ; une offre inchangée n'est
+// comptée ni comme nouvelle ni comme mise à jour
+pub fn upsert_jobs(conn: &Connection, jobs: &[NormalizedJob]) -> Result<JobSearchStats, AppError> {
+    let tx = conn.unchecked_transaction()?;
+    let mut stats = JobSearchStats::default();
+    let mut seen = HashSet::new();
+
+    for job in jobs {
+        let (id, outcome) = upsert_one(&tx, job)?;
+        match outcome {
+            UpsertOutcome::Inserted => stats.new += 1,
+            // Une offre vue deux fois dans le lot n'est comptée qu'une fois
+            UpsertOutcome::Updated if !seen.contains(&id) => stats.updated += 1,
+            _ => {}
+        }
+        seen.insert(id);
+    }
+
+    tx.commit()?;
+    stats.total = seen.len();
+    Ok(stats)
+}
+
+// Enregistre une seule offre et renvoie son identifiant
+pub fn upsert_job(conn: &Connection, job: &NormalizedJob) -> Result<i64, AppError> {
+    let (id, _) = upsert_one(conn, job)?;
+    Ok(id)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UpsertOutcome {
+    Inserted,
+    Updated,
+    Unchanged,
+}
+
+fn upsert_one(conn: &Connection, job: &NormalizedJob) -> Result<(i64, UpsertOutcome), AppError> {
+    match find_existing(conn, job)? {
+        Some(stored) => {
+            let merged = stored.merge(job);
+            if merged == stored {
+                return Ok((stored.id, UpsertOutcome::Unchanged));
+            }
+            update_job(conn, &merged)?;
+            Ok((stored.id, UpsertOutcome::Updated))
+        }
+        None => Ok((insert_job(conn, job)?, UpsertOutcome::Inserted)),
+    }
+}
+
+// Colonnes d'une offre gérées par la collecte
+#[derive(Debug, Clone, PartialEq)]
+struct StoredJob {
+    id: i64,
+    external_id: Option<String>,
+    title: String,
+    company: String,
+    location: String,
+    description: String,
+    url: String,
+    posted_at: String,
+    valid_through: Option<String>,
+    salary_min: Option<f64>,
+    salary_max: Option<f64>,
+    salary_currency: Option<String>,
+    salary_period: Option<String>,
+    job_type: String,
+    experience_level: String,
+    skills: String,
+    remote: bool,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+}
+
+impl StoredJob {
+    // Les champs renseignés par la source remplacent les valeurs stockées ;
+    // un champ vide (liste sans description, salaire absent…) n'efface rien
+    fn merge(&self, incoming: &NormalizedJob) -> StoredJob {
+        let job = &incoming.job;
+        let pick = |new: &str, old: &str| if new.is_empty() { old.to_string() } else { new.to_string() };
+        let skills = job.skills.clone().unwrap_or_default();
+        let salary = job.salary.as_ref();
+        let location = pick(&job.location, &self.location);
+        // Un lieu inconnu du gazetteer garde les coordonnées déjà connues,
+        // sauf s'il a changé
+        let (latitude, longitude) = match Gazetteer::bundled().geocode(&location) {
+            Some(coordinates) => (Some(coordinates.lat), Some(coordinates.lng)),
+            None if location == self.location => (self.latitude, self.longitude),
+            None => (None, None),
+        };
+
+        StoredJob {
+            id: self.id,
+            external_id: incoming.external_id.clone().or_else(|| self.external_id.clone()),
+            title: job.title.clone(),
+            company: job.company.clone(),
+            location,
+            description: pick(&job.description, &self.description),
+            url: pick(&job.url, &self.url),
+            posted_at: pick(&job.published_at, &self.posted_at),
+            valid_through: job.valid_through.clone().or_else(|| self.valid_through.clone()),
+            salary_min: salary.and_then(|s| s.min).or(self.salary_min),
+            salary_max: salary.and_then(|s| s.max).or(self.salary_max),
+            salary_currency: salary.and_then(|s| s.currency.clone()).or_else(|| self.salary_currency.clone()),
+            salary_period: salary.and_then(|s| s.period.clone()).or_else(|| self.salary_period.clone()),
+            job_type: pick(&incoming.job_type, &self.job_type),
+            experience_level: pick(job.experience_level.as_deref().unwrap_or_default(), &self.experience_level),
+            skills: if skills.is_empty() { self.skills.clone() } else { skills_json(&skills) },
+            remote: incoming.remote || self.remote,
+            latitude,
+            longitude,
+        }
+    }
+}
+
+fn find_existing(conn: &Connection, job: &NormalizedJob) -> Result<Option<StoredJob>, AppError> {
+    const SELECT: &str = "SELECT id, external_id, title, company, location, description, url, posted_at,
+            salary_min, salary_max, job_type, experience_level, skills, remote, valid_through,
+            salary_currency, salary_period, latitude, longitude
+         FROM jobs";
+    let read = |row: &rusqlite::Row| {
+        Ok(StoredJob {
+            id: row.get(0)?,
+            external_id: row.get(1)?,
+            title: row.get(2)?,
+            company: row.get(3)?,
+            location: row.get(4)?,
+            description: row.get(5)?,
+            url: row.get(6)?,
+            posted_at: row.get(7)?,
+            salary_min: row.get(8)?,
+            salary_max: row.get(9)?,
+            job_type: row.get(10)?,
+            experience_level: row.get(11)?,
+            skills: row.get(12)?,
+            remote: row.get(13)?,
+            valid_through: row.get(14)?,
+            salary_currency: row.get(15)?,
+            salary_period: row.get(16)?,
+            latitude: row.get(17)?,
+            longitude: row.get(18)?,
+        })
+    };
+    let source = &job.job.source;
+
+    if let Some(external_id) = &job.external_id {
+        let sql = format!("{} WHERE source = ? AND external_id = ? ORDER BY id LIMIT 1", SELECT);
+        if let Some(stored) = conn.query_row(&sql, params![source, external_id], read).optional()? {
+            return Ok(Some(stored));
+        }
+    }
+    if !job.job.url.is_empty() {
+        let sql = format!("{} WHERE url = ? ORDER BY id LIMIT 1", SELECT);
+        if let Some(stored) = conn.query_row(&sql, [&job.job.url], read).optional()? {
+            return Ok(Some(stored));
+        }
+    }
+    let sql = format!(
+        "{} WHERE source = ? AND title = ? AND company = ? AND location = ? ORDER BY id LIMIT 1",
+        SELECT
+    );
+    Ok(conn
+        .query_row(&sql, params![source, job.job.title, job.job.company, job.job.location], read)
+        .optional()?)
+}
+
+fn insert_job(conn: &Connection, normalized: &NormalizedJob) -> Result<i64, AppError> {
+    let job = &normalized.job;
+    let coordinates = Gazetteer::bundled().geocode(&job.location);
+    conn.execute(
+        "INSERT INTO jobs (external_id, title, company, location, description, url, source, posted_at,
+            salary_min, salary_max, salary_currency, salary_period, job_type, experience_level, skills,
+            remote, latitude, longitude, valid_through)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        params![
+            normalized.external_id,
+            job.title,
+            job.company,
+            job.location,
+            job.description,
+            job.url,
+            job.source,
+            job.published_at,
+            job.salary.as_ref().and_then(|s| s.min),
+            job.salary.as_ref().and_then(|s| s.max),
+            job.salary.as_ref().and_then(|s| s.currency.clone()),
+            job.salary.as_ref().and_then(|s| s.period.clone()),
+            normalized.job_type,
+            job.experience_level.clone().unwrap_or_default(),
+            skills_json(&job.skills.clone().unwrap_or_default()),
+            normalized.remote,
+            coordinates.as_ref().map(|c| c.lat),
+            coordinates.as_ref().map(|c| c.lng),
+            job.valid_through,
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn update_job(conn: &Connection, job: &StoredJob) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE jobs SET external_id = ?, title = ?, company = ?, location = ?, description = ?, url = ?,
+            posted_at = ?, salary_min = ?, salary_max = ?, salary_currency = ?, salary_period = ?, job_type = ?,
+            experience_level = ?, skills = ?, remote = ?, latitude = ?, longitude = ?, valid_through = ?,
+            updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?",
+        params![
+            job.external_id,
+            job.title,
+            job.company,
+            job.location,
+            job.description,
+            job.url,
+            job.posted_at,
+            job.salary_min,
+            job.salary_max,
+            job.salary_currency,
+            job.salary_period,
+            job.job_type,
+            job.experience_level,
+            job.skills,
+            job.remote,
+            job.latitude,
+            job.longitude,
+            job.valid_through,
+            job.id,
+        ],
+    )?;
+    Ok(())
+}
+
+fn skills_json(skills: &[String]) -> String {
+    serde_json::to_string(skills).unwrap_or_else(|_| "[]".to_string())
+}
@@ -1,6 +1,160 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+pub mod calendar;
+pub mod commands;
+pub mod cv;
+pub mod database;
+pub mod db;
+pub mod dedup;
+pub mod error;
+pub mod geo;
+pub mod ingest;
+pub mod matching;
+pub mod models;
+pub mod rescoring;
+pub mod scheduler;
+pub mod skills;
+pub mod time;
+
+use commands::*;
+use commands::profile::{last_active_user_id, LOCAL_USER_ID};
+use database::executor::Db;
+use database::pool::open_connection;
+use database::operations::DatabaseOperations;
+use db::{get_db_path, init_db};
+use rescoring::Rescorer;
+use scheduler::{spawn_reminder_scheduler, spawn_saved_search_scheduler};
+
+// Structure pour stocker l'état de l'application
+pub struct AppState {
+    pub python_path: std::sync::Mutex<String>,
+    pub app_path: std::sync::Mutex<String>,
+    pub db: Db,
+    pub operations: Arc<DatabaseOperations>,
+    pub rescorer: Rescorer,
+    // Profil sélectionné : toutes les commandes travaillent pour cet utilisateur
+    pub active_user: AtomicI64,
+}
+
+impl AppState {
+    pub fn user_id(&self) -> i64 {
+        self.active_user.load(Ordering::SeqCst)
+    }
+
+    pub fn set_user_id(&self, user_id: i64) {
+        self.active_user.store(user_id, Ordering::SeqCst);
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-  tauri::Builder::default()
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    // Initialiser la base de données (schéma géré par les migrations)
+    let db = match init_db() {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Failed to initialize database: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let operations = match DatabaseOperations::new(&get_db_path().to_string_lossy()) {
+        Ok(operations) => operations,
+        Err(e) => {
+            eprintln!("Failed to open database pool: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Rouvrir le dernier profil utilisé
+    let active_user = open_connection(&get_db_path().to_string_lossy())
+        .and_then(|conn| last_active_user_id(&conn))
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to load the active profile: {}", e);
+            LOCAL_USER_ID
+        });
+
+    let scheduler_db = db.clone();
+
+    tauri::Builder::default()
+        .setup(move |app| {
+            spawn_saved_search_scheduler(app.handle(), scheduler_db.clone());
+            spawn_reminder_scheduler(app.handle(), scheduler_db);
+            Ok(())
+        })
+        .manage(AppState {
+            python_path: std::sync::Mutex::new(String::new()),
+            app_path: std::sync::Mutex::new(String::new()),
+            db,
+            operations: Arc::new(operations),
+            rescorer: Rescorer::default(),
+            active_user: AtomicI64::new(active_user),
+        })
+        .invoke_handler(tauri::generate_handler![
+            run_python_command,
+            init_app,
+            get_user_profile,
+            update_user_profile,
+            profile::list_profiles,
+            profile::create_profile,
+            profile::switch_profile,
+            profile::get_active_profile,
+            kanban::get_kanban_columns,
+            kanban::move_kanban_card,
+            kanban::create_kanban_column,
+            kanban::update_kanban_column,
+            kanban::reorder_kanban_columns,
+            kanban::delete_kanban_column,
+            search_preferences::get_search_preferences,
+            search_preferences::update_search_preferences,
+            applications::create_application,
+            applications::get_application,
+            applications::update_application_status,
+            applications::add_application_stage,
+            applications::add_application_note,
+            applications::add_application_document,
+            applications::list_applications,
+            applications::delete_application,
+            applications::update_application_stage,
+            applications::delete_application_stage,
+            applications::update_application_note,
+            applications::delete_application_note,
+            applications::update_application_document,
+            applications::delete_application_document,
+            reminders::list_reminders,
+            reminders::snooze_reminder,
+            reminders::dismiss_reminder,
+            commands::calendar::export_calendar,
+            commands::calendar::import_calendar,
+            search::search_jobs,
+            search::get_job_details,
+            facets::search_job_facets,
+            saved_searches::create_saved_search,
+            saved_searches::list_saved_searches,
+            saved_searches::update_saved_search,
+            saved_searches::delete_saved_search,
+            saved_searches::run_saved_search,
+            commands::ingest::list_job_sources,
+            commands::ingest::ingest_jobs,
+            commands::ingest::import_job_feed,
+            commands::ingest::import_job_from_html,
+            duplicates::detect_duplicates,
+            duplicates::list_duplicates,
+            duplicates::merge_duplicates,
+            duplicates::ignore_duplicate,
+            commands::matching::compute_matching_scores,
+            commands::matching::explain_match,
+            commands::matching::start_rescoring,
+            commands::matching::cancel_rescoring,
+            commands::matching::get_rescoring_status,
+            commands::cv::import_cv,
+            commands::cv::reparse_cv,
+            commands::cv::get_latest_cv,
+            commands::skills::list_skills,
+            commands::skills::normalize_skills,
+            commands::database::get_pool_metrics,
+            commands::database::get_cache_stats,
+            commands::database::flush_job_cache,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
 }
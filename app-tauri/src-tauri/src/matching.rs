@@ -0,0 +1,499 @@
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{validate_matching_score, AppError};
+use crate::geo::normalize;
+use crate::models::{JobPreferences, KeywordWeight};
+
+// Poids de chaque facteur ; un facteur sans donnée (offre sans salaire,
+// profil sans préférence…) est écarté et les autres sont repondérés
+const SKILLS_WEIGHT: f64 = 0.35;
+const EXPERIENCE_WEIGHT: f64 = 0.15;
+const SALARY_WEIGHT: f64 = 0.15;
+const KEYWORDS_WEIGHT: f64 = 0.15;
+const JOB_TYPE_WEIGHT: f64 = 0.1;
+const REMOTE_WEIGHT: f64 = 0.05;
+const COMMUTE_WEIGHT: f64 = 0.05;
+
+// Trajet jugé idéal, puis rédhibitoire (minutes)
+const COMMUTE_IDEAL_MINUTES: f64 = 30.0;
+const COMMUTE_MAX_MINUTES: f64 = 90.0;
+
+// Années d'expérience attendues par niveau (`jobs.experience_level`)
+const EXPERIENCE_RANGES: &[(&str, i32, i32)] = &[("junior", 0, 2), ("mid", 2, 5), ("senior", 5, 10), ("lead", 8, 40)];
+
+// Profil du candidat tel qu'utilisé pour le matching
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MatchProfile {
+    pub skills: Vec<String>,
+    pub experience_years: Option<i32>,
+    pub job_preferences: Option<JobPreferences>,
+    pub keywords: Vec<KeywordWeight>,
+}
+
+// Champs d'une offre utiles au matching
+#[derive(Debug, Clone, Default)]
+pub struct MatchJob {
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+    pub skills: Vec<String>,
+    pub experience_level: Option<String>,
+    pub salary_min: Option<f64>,
+    pub salary_max: Option<f64>,
+    pub job_type: String,
+    pub remote: bool,
+    // Trajet le plus court depuis le domicile principal, en minutes
+    pub commute_minutes: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchFactorKind {
+    Skills,
+    Experience,
+    Salary,
+    Keywords,
+    JobType,
+    Remote,
+    Commute,
+}
+
+// Contribution d'un facteur : `score` est absent quand le facteur n'a pas pu
+// être évalué, `contribution` est sa part dans le score final
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchFactor {
+    pub kind: MatchFactorKind,
+    pub weight: f64,
+    pub score: Option<f64>,
+    pub contribution: f64,
+    pub detail: String,
+    #[serde(default)]
+    pub matched: Vec<String>,
+    #[serde(default)]
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchExplanation {
+    pub job_id: i64,
+    pub score: f64,
+    pub factors: Vec<MatchFactor>,
+    // Mots-clés obligatoires absents : le score est ramené à 0
+    #[serde(default)]
+    pub missing_required: Vec<String>,
+    pub computed_at: String,
+    // Version du profil et des préférences utilisée pour ce calcul
+    #[serde(default)]
+    pub profile_version: String,
+    // Le profil a changé depuis : le score est à recalculer
+    #[serde(default)]
+    pub stale: bool,
+}
+
+// Empreinte des critères de matching : elle change dès que le profil ou les
+// préférences de recherche changent, ce qui rend les scores existants périmés
+pub fn profile_version(profile: &MatchProfile) -> String {
+    let json = serde_json::to_string(profile).unwrap_or_default();
+    hex::encode(&Sha256::digest(json.as_bytes())[..8])
+}
+
+// Score entre 0 et 1 et son détail
+pub fn score_job(profile: &MatchProfile, job: &MatchJob, computed_at: &str) -> MatchExplanation {
+    let text = searchable(&format!("{} {} {}", job.title, job.description, job.skills.join(" ")));
+    let (keywords, missing_required) = keywords_factor(profile, &text);
+    let mut factors = vec![
+        skills_factor(profile, job),
+        experience_factor(profile, job),
+        salary_factor(profile, job),
+        keywords,
+        job_type_factor(profile, job),
+        remote_factor(profile, job),
+        commute_factor(job),
+    ];
+
+    let total_weight: f64 = factors.iter().filter(|f| f.score.is_some()).map(|f| f.weight).sum();
+    for factor in &mut factors {
+        if let Some(score) = factor.score {
+            factor.contribution = factor.weight * score / total_weight;
+        }
+    }
+    let score = if missing_required.is_empty() {
+        factors.iter().map(|f| f.contribution).sum::<f64>().clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    MatchExplanation {
+        job_id: job.id,
+        score,
+        factors,
+        missing_required,
+        computed_at: computed_at.to_string(),
+        profile_version: String::new(),
+        stale: false,
+    }
+}
+
+fn factor(kind: MatchFactorKind, weight: f64, score: Option<f64>, detail: String) -> MatchFactor {
+    MatchFactor {
+        kind,
+        weight,
+        score,
+        contribution: 0.0,
+        detail,
+        matched: Vec::new(),
+        missing: Vec::new(),
+    }
+}
+
+// Part des compétences demandées par l'offre présentes dans le CV
+fn skills_factor(profile: &MatchProfile, job: &MatchJob) -> MatchFactor {
+    if job.skills.is_empty() || profile.skills.is_empty() {
+        let detail = if job.skills.is_empty() {
+            "L'offre ne liste pas de compétences"
+        } else {
+            "Aucune compétence dans le CV"
+        };
+        return factor(MatchFactorKind::Skills, SKILLS_WEIGHT, None, detail.to_string());
+    }
+
+    let known: Vec<String> = profile.skills.iter().map(|skill| normalize(skill)).collect();
+    let (matched, missing): (Vec<String>, Vec<String>) =
+        job.skills.iter().cloned().partition(|skill| known.contains(&normalize(skill)));
+    let score = matched.len() as f64 / job.skills.len() as f64;
+    MatchFactor {
+        detail: format!("{} compétence(s) sur {} demandées", matched.len(), job.skills.len()),
+        matched,
+        missing,
+        ..factor(MatchFactorKind::Skills, SKILLS_WEIGHT, Some(score), String::new())
+    }
+}
+
+// Années d'expérience du CV comparées à la fourchette du niveau demandé ;
+// un profil plus expérimenté que nécessaire reste bien noté
+fn experience_factor(profile: &MatchProfile, job: &MatchJob) -> MatchFactor {
+    let range = job
+        .experience_level
+        .as_deref()
+        .and_then(|level| EXPERIENCE_RANGES.iter().find(|(name, _, _)| *name == level));
+    let (Some(years), Some(&(level, min, max))) = (profile.experience_years, range) else {
+        return factor(
+            MatchFactorKind::Experience,
+            EXPERIENCE_WEIGHT,
+            None,
+            "Niveau d'expérience inconnu".to_string(),
+        );
+    };
+
+    let score = if years < min {
+        (1.0 - (min - years) as f64 * 0.25).max(0.0)
+    } else if years > max + 5 {
+        0.8
+    } else {
+        1.0
+    };
+    factor(
+        MatchFactorKind::Experience,
+        EXPERIENCE_WEIGHT,
+        Some(score),
+        format!("{} an(s) d'expérience pour un poste {} ({} à {} ans)", years, level, min, max),
+    )
+}
+
+// Salaire le plus haut proposé comparé au salaire minimum souhaité
+fn salary_factor(profile: &MatchProfile, job: &MatchJob) -> MatchFactor {
+    let wanted = profile.job_preferences.as_ref().and_then(|p| p.min_salary).filter(|&s| s > 0.0);
+    let offered = job.salary_max.or(job.salary_min);
+    let (Some(wanted), Some(offered)) = (wanted, offered) else {
+        return factor(MatchFactorKind::Salary, SALARY_WEIGHT, None, "Salaire non comparable".to_string());
+    };
+
+    let score = (offered / wanted).min(1.0).powi(2);
+    factor(
+        MatchFactorKind::Salary,
+        SALARY_WEIGHT,
+        Some(score),
+        format!("Jusqu'à {:.0} € proposés pour {:.0} € souhaités", offered, wanted),
+    )
+}
+
+// Somme des poids des mots-clés trouvés dans l'offre, rapportée au total
+fn keywords_factor(profile: &MatchProfile, text: &str) -> (MatchFactor, Vec<String>) {
+    let keywords: Vec<&KeywordWeight> = profile.keywords.iter().filter(|k| !k.keyword.trim().is_empty()).collect();
+    let missing_required = keywords
+        .iter()
+        .filter(|k| k.required.unwrap_or(false) && !contains_phrase(text, &k.keyword))
+        .map(|k| k.keyword.clone())
+        .collect();
+
+    let weighted: Vec<&&KeywordWeight> = keywords.iter().filter(|k| k.weight > 0).collect();
+    if weighted.is_empty() {
+        let factor = factor(MatchFactorKind::Keywords, KEYWORDS_WEIGHT, None, "Aucun mot-clé pondéré".to_string());
+        return (factor, missing_required);
+    }
+
+    let (matched, missing): (Vec<&&KeywordWeight>, Vec<&&KeywordWeight>) =
+        weighted.iter().partition(|k| contains_phrase(text, &k.keyword));
+    let total: i32 = weighted.iter().map(|k| k.weight).sum();
+    let found: i32 = matched.iter().map(|k| k.weight).sum();
+    let factor = MatchFactor {
+        detail: format!("Poids {} sur {}", found, total),
+        matched: matched.iter().map(|k| k.keyword.clone()).collect(),
+        missing: missing.iter().map(|k| k.keyword.clone()).collect(),
+        ..factor(MatchFactorKind::Keywords, KEYWORDS_WEIGHT, Some(found as f64 / total as f64), String::new())
+    };
+    (factor, missing_required)
+}
+
+fn job_type_factor(profile: &MatchProfile, job: &MatchJob) -> MatchFactor {
+    let preferred = profile
+        .job_preferences
+        .as_ref()
+        .and_then(|p| p.preferred_job_types.as_ref())
+        .filter(|types| !types.is_empty());
+    let (Some(preferred), false) = (preferred, job.job_type.is_empty()) else {
+        return factor(MatchFactorKind::JobType, JOB_TYPE_WEIGHT, None, "Type de contrat non comparable".to_string());
+    };
+
+    let accepted = preferred.iter().any(|kind| normalize(kind) == normalize(&job.job_type));
+    factor(
+        MatchFactorKind::JobType,
+        JOB_TYPE_WEIGHT,
+        Some(if accepted { 1.0 } else { 0.0 }),
+        format!("{} {} parmi les contrats souhaités", job.job_type, if accepted { "figure" } else { "ne figure pas" }),
+    )
+}
+
+// `remote_preference` : « remote » (télétravail complet), « onsite » (sur
+// site) ; « hybrid » ou toute autre valeur accepte les deux
+fn remote_factor(profile: &MatchProfile, job: &MatchJob) -> MatchFactor {
+    let preference = profile.job_preferences.as_ref().and_then(|p| p.remote_preference.as_deref()).map(normalize);
+    let wants_remote = match preference.as_deref() {
+        Some("remote" | "full remote" | "teletravail") => true,
+        Some("onsite" | "on site" | "office" | "sur site" | "presentiel") => false,
+        _ => {
+            return factor(MatchFactorKind::Remote, REMOTE_WEIGHT, None, "Pas de préférence de télétravail".to_string())
+        }
+    };
+
+    let detail = match (wants_remote, job.remote) {
+        (true, true) => "Télétravail proposé",
+        (true, false) => "Télétravail souhaité mais non proposé",
+        (false, true) => "Poste en télétravail alors que le travail sur site est souhaité",
+        (false, false) => "Poste sur site",
+    };
+    let score = if wants_remote == job.remote { 1.0 } else { 0.0 };
+    factor(MatchFactorKind::Remote, REMOTE_WEIGHT, Some(score), detail.to_string())
+}
+
+fn commute_factor(job: &MatchJob) -> MatchFactor {
+    if job.remote {
+        return factor(MatchFactorKind::Commute, COMMUTE_WEIGHT, Some(1.0), "Pas de trajet : télétravail".to_string());
+    }
+    let Some(minutes) = job.commute_minutes else {
+        return factor(MatchFactorKind::Commute, COMMUTE_WEIGHT, None, "Temps de trajet non calculé".to_string());
+    };
+
+    let score = 1.0
+        - ((minutes as f64 - COMMUTE_IDEAL_MINUTES) / (COMMUTE_MAX_MINUTES - COMMUTE_IDEAL_MINUTES)).clamp(0.0, 1.0);
+    factor(MatchFactorKind::Commute, COMMUTE_WEIGHT, Some(score), format!("{} min de trajet", minutes))
+}
+
+// Texte normalisé, mots séparés par une espace et entouré d'espaces
+fn searchable(text: &str) -> String {
+    let words: Vec<String> = normalize(text)
+        .split(|c: char| !c.is_alphanumeric() && c != '+' && c != '#')
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+// Le mot-clé apparaît comme mot entier (« go » ne correspond pas à « google »)
+fn contains_phrase(text: &str, phrase: &str) -> bool {
+    let phrase = searchable(phrase);
+    !phrase.trim().is_empty() && text.contains(&phrase)
+}
+
+pub fn load_match_profile(conn: &Connection, user_id: i64) -> Result<MatchProfile, AppError> {
+    let stored: Option<(String, Option<i32>, Option<String>)> = conn
+        .query_row(
+            "SELECT skills, experience_years, job_preferences FROM user_profiles WHERE user_id = ?",
+            [user_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?;
+    let (skills, experience_years, job_preferences) = stored.unwrap_or_else(|| ("[]".to_string(), None, None));
+
+    // Mots-clés des préférences de recherche actives du profil
+    let mut stmt = conn.prepare(
+        "SELECT k.keyword, k.weight, k.required
+         FROM keywords k
+         JOIN search_categories c ON c.id = k.category_id
+         JOIN search_preferences p ON p.id = c.preference_id
+         WHERE p.user_id = ? AND p.is_active = 1
+         ORDER BY k.id",
+    )?;
+    let keywords = stmt
+        .query_map([user_id], |row| {
+            Ok(KeywordWeight {
+                keyword: row.get(0)?,
+                weight: row.get(1)?,
+                required: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(MatchProfile {
+        skills: serde_json::from_str(&skills).unwrap_or_default(),
+        experience_years,
+        job_preferences: job_preferences.and_then(|json| serde_json::from_str(&json).ok()),
+        keywords,
+    })
+}
+
+const MATCH_JOB_SELECT: &str = "SELECT j.id, j.title, j.description, j.skills, j.experience_level, j.salary_min,
+        j.salary_max, j.job_type, j.remote,
+        (SELECT MIN(duration) FROM commute_times WHERE job_id = j.id AND home_type = 'primary')
+     FROM jobs j";
+
+fn match_job_from_row(row: &Row) -> rusqlite::Result<MatchJob> {
+    let skills: String = row.get(3)?;
+    let experience_level: String = row.get(4)?;
+    Ok(MatchJob {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        description: row.get(2)?,
+        skills: serde_json::from_str(&skills).unwrap_or_default(),
+        experience_level: Some(experience_level).filter(|level| !level.is_empty()),
+        salary_min: row.get(5)?,
+        salary_max: row.get(6)?,
+        job_type: row.get(7)?,
+        remote: row.get(8)?,
+        commute_minutes: row.get(9)?,
+    })
+}
+
+pub fn load_match_job(conn: &Connection, job_id: i64) -> Result<MatchJob, AppError> {
+    conn.query_row(&format!("{} WHERE j.id = ?", MATCH_JOB_SELECT), [job_id], match_job_from_row)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Offre {} introuvable", job_id)))
+}
+
+// Enregistre le score du profil et son détail dans `job_match_scores`,
+// seule source des scores (lus via `SCORED_JOBS_FROM`)
+pub fn store_match(conn: &Connection, user_id: i64, explanation: &MatchExplanation) -> Result<(), AppError> {
+    validate_matching_score(explanation.score)?;
+    let breakdown = serde_json::to_string(explanation).map_err(|e| AppError::Internal(e.to_string()))?;
+    conn.execute(
+        "INSERT INTO job_match_scores (user_id, job_id, score, breakdown, computed_at, profile_version)
+         VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT(user_id, job_id) DO UPDATE SET
+            score = excluded.score, breakdown = excluded.breakdown, computed_at = excluded.computed_at,
+            profile_version = excluded.profile_version",
+        params![
+            user_id,
+            explanation.job_id,
+            explanation.score,
+            breakdown,
+            explanation.computed_at,
+            explanation.profile_version
+        ],
+    )?;
+    Ok(())
+}
+
+fn score_versioned(profile: &MatchProfile, version: &str, job: &MatchJob, now: &str) -> MatchExplanation {
+    MatchExplanation {
+        profile_version: version.to_string(),
+        ..score_job(profile, job, now)
+    }
+}
+
+pub fn score_and_store_job(conn: &Connection, user_id: i64, job_id: i64, now: &str) -> Result<MatchExplanation, AppError> {
+    let profile = load_match_profile(conn, user_id)?;
+    let explanation = score_versioned(&profile, &profile_version(&profile), &load_match_job(conn, job_id)?, now);
+    store_match(conn, user_id, &explanation)?;
+    Ok(explanation)
+}
+
+// Recalcule le score de toutes les offres ; renvoie le nombre d'offres notées
+pub fn score_all_jobs(conn: &Connection, user_id: i64, now: &str) -> Result<usize, AppError> {
+    let profile = load_match_profile(conn, user_id)?;
+    let ids = {
+        let mut stmt = conn.prepare("SELECT id FROM jobs ORDER BY id")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect::<Result<Vec<i64>, _>>()?
+    };
+    score_jobs(conn, user_id, &profile, &profile_version(&profile), &ids, now)
+}
+
+// Note les offres `job_ids` dans une transaction ; une offre supprimée
+// entre-temps est ignorée
+pub fn score_jobs(
+    conn: &Connection,
+    user_id: i64,
+    profile: &MatchProfile,
+    version: &str,
+    job_ids: &[i64],
+    now: &str,
+) -> Result<usize, AppError> {
+    let tx = conn.unchecked_transaction()?;
+    let mut scored = 0;
+    {
+        let mut stmt = tx.prepare(&format!("{} WHERE j.id = ?", MATCH_JOB_SELECT))?;
+        for &id in job_ids {
+            if let Some(job) = stmt.query_row([id], match_job_from_row).optional()? {
+                store_match(&tx, user_id, &score_versioned(profile, version, &job, now))?;
+                scored += 1;
+            }
+        }
+    }
+    tx.commit()?;
+    Ok(scored)
+}
+
+// Offres jamais notées, notées avec une autre version du profil ou modifiées
+// depuis leur dernier calcul (salaire, compétences, lieu mis à jour par la collecte)
+pub fn stale_job_ids(conn: &Connection, user_id: i64, version: &str) -> Result<Vec<i64>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT j.id FROM jobs j
+         LEFT JOIN job_match_scores s ON s.job_id = j.id AND s.user_id = ?1
+         WHERE s.profile_version IS NULL OR s.profile_version != ?2 OR s.computed_at < j.updated_at
+         ORDER BY j.id",
+    )?;
+    let ids = stmt.query_map(params![user_id, version], |row| row.get(0))?;
+    Ok(ids.collect::<Result<Vec<i64>, _>>()?)
+}
+
+// Détail enregistré du score d'une offre, calculé à la demande s'il manque
+pub fn load_match_explanation(
+    conn: &Connection,
+    user_id: i64,
+    job_id: i64,
+    now: &str,
+) -> Result<MatchExplanation, AppError> {
+    let stored: Option<(String, bool)> = conn
+        .query_row(
+            "SELECT s.breakdown, COALESCE(s.computed_at < j.updated_at, 0)
+             FROM job_match_scores s
+             JOIN jobs j ON j.id = s.job_id
+             WHERE s.user_id = ? AND s.job_id = ?",
+            [user_id, job_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let stored = stored.and_then(|(json, job_changed)| {
+        serde_json::from_str::<MatchExplanation>(&json).ok().map(|explanation| (explanation, job_changed))
+    });
+    match stored {
+        Some((mut explanation, job_changed)) => {
+            let current = profile_version(&load_match_profile(conn, user_id)?);
+            explanation.stale = job_changed || explanation.profile_version != current;
+            Ok(explanation)
+        }
+        None => score_and_store_job(conn, user_id, job_id, now),
+    }
+}
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-#[derive(Debug, Serialize, Deserialize)]
+mod validation;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UserProfile {
     pub id: i64,
     pub name: String,
@@ -13,26 +15,26 @@ pub struct UserProfile {
     pub job_preferences: Option<JobPreferences>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UserLocations {
     pub primary: String,
     pub secondary: Option<String>,
     pub coordinates: Option<LocationCoordinates>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LocationCoordinates {
     pub primary: Coordinates,
     pub secondary: Option<Coordinates>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Coordinates {
     pub lat: f64,
     pub lng: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CVInfo {
     pub path: PathBuf,
     pub last_updated: String,
@@ -42,7 +44,7 @@ pub struct CVInfo {
     pub certifications: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Education {
     pub institution: String,
     pub degree: String,
@@ -51,7 +53,7 @@ pub struct Education {
     pub end_year: Option<i32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UserPreferences {
     pub notifications: Option<bool>,
     pub dark_mode: Option<bool>,
@@ -59,14 +61,14 @@ pub struct UserPreferences {
     pub commute_mode: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct JobPreferences {
     pub min_salary: Option<f64>,
     pub preferred_job_types: Option<Vec<String>>,
     pub remote_preference: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Job {
     pub id: i64,
     pub title: String,
@@ -76,6 +78,9 @@ pub struct Job {
     pub url: String,
     pub source: String,
     pub published_at: String,
+    // Date limite de candidature, quand l'annonce la précise
+    #[serde(default)]
+    pub valid_through: Option<String>,
     pub salary: Option<SalaryRange>,
     pub matching_score: f64,
     pub commute_times: CommuteTimes,
@@ -85,7 +90,7 @@ pub struct Job {
     pub status: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SalaryRange {
     pub min: Option<f64>,
     pub max: Option<f64>,
@@ -93,13 +98,13 @@ pub struct SalaryRange {
     pub period: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CommuteTimes {
     pub primary_home: CommuteLocation,
     pub secondary_home: Option<CommuteLocation>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CommuteLocation {
     pub duration: i32,
     pub distance: f64,
@@ -162,7 +167,7 @@ pub struct SearchCategory {
     pub priority: Option<i32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeywordWeight {
     pub keyword: String,
     pub weight: i32,
@@ -220,6 +225,14 @@ pub struct DocumentFeedback {
     pub comments: Option<String>,
 }
 
+// Bilan d'une collecte d'offres
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobSearchStats {
+    pub total: usize,
+    pub new: usize,
+    pub updated: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JobStats {
     pub total_jobs: i64,
@@ -247,12 +260,90 @@ pub struct ApplicationStats {
     pub success_rate: f64,
 }
 
+// Cycle de vie d'une candidature. Les passages autorisés sont définis par
+// `allowed_transitions` ; accepted, rejected et withdrawn sont définitifs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApplicationStatus {
+    Draft,
+    Applied,
+    Screening,
+    Interview,
+    Offer,
+    Accepted,
+    Rejected,
+    Withdrawn,
+    Ghosted,
+}
+
+impl ApplicationStatus {
+    pub const ALL: [ApplicationStatus; 9] = [
+        ApplicationStatus::Draft,
+        ApplicationStatus::Applied,
+        ApplicationStatus::Screening,
+        ApplicationStatus::Interview,
+        ApplicationStatus::Offer,
+        ApplicationStatus::Accepted,
+        ApplicationStatus::Rejected,
+        ApplicationStatus::Withdrawn,
+        ApplicationStatus::Ghosted,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApplicationStatus::Draft => "draft",
+            ApplicationStatus::Applied => "applied",
+            ApplicationStatus::Screening => "screening",
+            ApplicationStatus::Interview => "interview",
+            ApplicationStatus::Offer => "offer",
+            ApplicationStatus::Accepted => "accepted",
+            ApplicationStatus::Rejected => "rejected",
+            ApplicationStatus::Withdrawn => "withdrawn",
+            ApplicationStatus::Ghosted => "ghosted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_str() == value)
+    }
+
+    pub fn allowed_transitions(&self) -> &'static [ApplicationStatus] {
+        use ApplicationStatus::*;
+        match self {
+            Draft => &[Applied, Withdrawn],
+            Applied => &[Screening, Interview, Offer, Rejected, Withdrawn, Ghosted],
+            Screening => &[Interview, Offer, Rejected, Withdrawn, Ghosted],
+            Interview => &[Offer, Rejected, Withdrawn, Ghosted],
+            Offer => &[Accepted, Rejected, Withdrawn],
+            // Un recruteur qui reprend contact relance le processus
+            Ghosted => &[Screening, Interview, Offer, Rejected, Withdrawn],
+            Accepted | Rejected | Withdrawn => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: ApplicationStatus) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+
+    // Statuts signifiant que l'employeur a répondu à la candidature
+    pub fn is_response(&self) -> bool {
+        matches!(
+            self,
+            ApplicationStatus::Screening
+                | ApplicationStatus::Interview
+                | ApplicationStatus::Offer
+                | ApplicationStatus::Accepted
+                | ApplicationStatus::Rejected
+        )
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Application {
     pub id: i64,
     pub user_id: i64,
     pub job_id: i64,
-    pub status: String,
+    pub status: ApplicationStatus,
     pub applied_at: Option<String>,
     pub response_received: bool,
     pub notes: Option<String>,
@@ -261,6 +352,18 @@ pub struct Application {
     pub stages: Option<Vec<ApplicationStage>>,
     pub documents: Option<Vec<ApplicationDocument>>,
     pub application_notes: Option<Vec<ApplicationNote>>,
+    pub status_history: Option<Vec<ApplicationStatusChange>>,
+}
+
+// Changement de statut ; `from_status` est absent pour le statut initial
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApplicationStatusChange {
+    pub id: i64,
+    pub application_id: i64,
+    pub from_status: Option<ApplicationStatus>,
+    pub to_status: ApplicationStatus,
+    pub reason: Option<String>,
+    pub changed_at: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -294,4 +397,23 @@ pub struct ApplicationNote {
     pub content: String,
     pub created_at: String,
     pub updated_at: String,
-} 
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: i64,
+    pub email: String,
+    #[serde(skip_serializing)]
+    pub password_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Document {
+    pub id: i64,
+    pub user_id: i64,
+    pub title: String,
+    pub document_type: String,
+    pub file_path: Option<String>,
+    pub content: Option<String>,
+    pub created_at: String,
+}
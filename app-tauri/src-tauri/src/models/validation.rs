@@ -0,0 +1,141 @@
+use crate::error::{
+    validate_coordinates, validate_email, validate_location, validate_phone, AppError, Validate,
+};
+
+use super::{CVInfo, Coordinates, Education, JobPreferences, LocationCoordinates, UserLocations, UserPreferences, UserProfile};
+
+pub const LANGUAGES: &[&str] = &["fr", "en"];
+pub const REMOTE_PREFERENCES: &[&str] = &["remote", "hybrid", "onsite", "any"];
+const MAX_EXPERIENCE_YEARS: i32 = 60;
+const MAX_LABEL_LENGTH: usize = 100;
+
+fn require(value: &str, message: &str) -> Result<(), AppError> {
+    if value.trim().is_empty() {
+        return Err(AppError::Validation(message.to_string()));
+    }
+    Ok(())
+}
+
+// Liste de libellés (compétences, certifications) : ni vides, ni trop longs
+fn validate_labels(labels: &[String], what: &str) -> Result<(), AppError> {
+    for label in labels {
+        if label.trim().is_empty() || label.len() > MAX_LABEL_LENGTH {
+            return Err(AppError::Validation(format!("{} invalide : « {} »", what, label)));
+        }
+    }
+    Ok(())
+}
+
+impl Validate for UserProfile {
+    fn validate(&self) -> Result<(), AppError> {
+        require(&self.name, "Le nom ne peut pas être vide")?;
+        if self.name.len() > MAX_LABEL_LENGTH {
+            return Err(AppError::Validation("Le nom est trop long".to_string()));
+        }
+        if let Some(email) = self.email.as_deref().filter(|email| !email.is_empty()) {
+            validate_email(email)?;
+        }
+        if let Some(phone) = self.phone.as_deref().filter(|phone| !phone.is_empty()) {
+            validate_phone(phone)?;
+        }
+        self.locations.validate()?;
+        self.cv.validate()?;
+        if let Some(preferences) = &self.preferences {
+            preferences.validate()?;
+        }
+        if let Some(job_preferences) = &self.job_preferences {
+            job_preferences.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for UserLocations {
+    fn validate(&self) -> Result<(), AppError> {
+        validate_location(self.primary.trim())?;
+        if let Some(secondary) = self.secondary.as_deref().filter(|secondary| !secondary.is_empty()) {
+            validate_location(secondary.trim())?;
+        }
+        if let Some(coordinates) = &self.coordinates {
+            coordinates.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for LocationCoordinates {
+    fn validate(&self) -> Result<(), AppError> {
+        self.primary.validate()?;
+        if let Some(secondary) = &self.secondary {
+            secondary.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for Coordinates {
+    fn validate(&self) -> Result<(), AppError> {
+        validate_coordinates(self.lat, self.lng)
+    }
+}
+
+impl Validate for CVInfo {
+    fn validate(&self) -> Result<(), AppError> {
+        if let Some(years) = self.experience_years {
+            if !(0..=MAX_EXPERIENCE_YEARS).contains(&years) {
+                return Err(AppError::Validation(format!(
+                    "Les années d'expérience doivent être entre 0 et {}",
+                    MAX_EXPERIENCE_YEARS
+                )));
+            }
+        }
+        validate_labels(self.skills.as_deref().unwrap_or_default(), "Compétence")?;
+        validate_labels(self.certifications.as_deref().unwrap_or_default(), "Certification")?;
+        for education in self.education.iter().flatten() {
+            education.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for Education {
+    fn validate(&self) -> Result<(), AppError> {
+        require(&self.institution, "L'établissement ne peut pas être vide")?;
+        require(&self.degree, "Le diplôme ne peut pas être vide")?;
+        if !(1950..=2100).contains(&self.start_year) {
+            return Err(AppError::Validation(format!("Année de début invalide : {}", self.start_year)));
+        }
+        if self.end_year.is_some_and(|end| end < self.start_year) {
+            return Err(AppError::Validation(
+                "L'année de fin ne peut pas précéder l'année de début".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for UserPreferences {
+    fn validate(&self) -> Result<(), AppError> {
+        if let Some(language) = self.language.as_deref() {
+            if !LANGUAGES.contains(&language) {
+                return Err(AppError::Validation(format!("Langue non prise en charge : {}", language)));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Validate for JobPreferences {
+    fn validate(&self) -> Result<(), AppError> {
+        if self.min_salary.is_some_and(|salary| salary < 0.0) {
+            return Err(AppError::Validation("Le salaire minimum ne peut pas être négatif".to_string()));
+        }
+        validate_labels(self.preferred_job_types.as_deref().unwrap_or_default(), "Type de contrat")?;
+        if let Some(remote) = self.remote_preference.as_deref() {
+            if !REMOTE_PREFERENCES.contains(&remote) {
+                return Err(AppError::Validation(format!("Préférence de télétravail inconnue : {}", remote)));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+use crate::database::executor::Db;
+use crate::database::operations::DatabaseOperations;
+use crate::error::AppError;
+use crate::matching::{load_match_profile, profile_version, score_jobs, stale_job_ids};
+use crate::time::now_timestamp;
+
+// Événement reçu par le frontend après chaque lot recalculé
+pub const RESCORE_PROGRESS_EVENT: &str = "matching-rescore-progress";
+
+// Offres recalculées par transaction : l'écrivain est libéré entre deux lots
+pub const RESCORE_CHUNK_SIZE: usize = 200;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RescoreProgress {
+    pub profile_version: String,
+    pub processed: usize,
+    pub total: usize,
+    pub done: bool,
+    pub cancelled: bool,
+}
+
+// Recalcul en arrière-plan des scores périmés. Un seul recalcul à la fois :
+// en lancer un nouveau annule le précédent, dont le profil est dépassé
+#[derive(Default)]
+pub struct Rescorer {
+    running: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+impl Rescorer {
+    // Le cache des listes d'offres est vidé après chaque lot enregistré :
+    // elles affichent les nouveaux scores au fil du recalcul
+    pub fn start(&self, app: AppHandle, db: Db, operations: Arc<DatabaseOperations>, user_id: i64) {
+        let cancel = Arc::new(AtomicBool::new(false));
+        if let Some(previous) = self.running.lock().unwrap().replace(cancel.clone()) {
+            previous.store(true, Ordering::SeqCst);
+        }
+
+        tauri::async_runtime::spawn(async move {
+            let result = rescore_stale_jobs(&db, user_id, RESCORE_CHUNK_SIZE, &cancel, |progress| {
+                operations.flush_cache();
+                if let Err(e) = app.emit_all(RESCORE_PROGRESS_EVENT, progress.clone()) {
+                    log::error!("Impossible de notifier le frontend : {}", e);
+                }
+            })
+            .await;
+            if let Err(e) = result {
+                log::error!("Échec du recalcul des scores : {}", e);
+            }
+        });
+    }
+
+    // Renvoie false si aucun recalcul n'était en cours
+    pub fn cancel(&self) -> bool {
+        match self.running.lock().unwrap().take() {
+            Some(cancel) => !cancel.swap(true, Ordering::SeqCst),
+            None => false,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+            .lock()
+            .unwrap()
+            .as_ref()
+            // La tâche détient l'autre référence jusqu'à sa fin
+            .is_some_and(|cancel| !cancel.load(Ordering::SeqCst) && Arc::strong_count(cancel) > 1)
+    }
+}
+
+// Recalcule par lots les offres dont le score est périmé pour la version
+// actuelle du profil. `on_progress` est appelé après chaque lot, puis une
+// dernière fois avec `done` ou `cancelled`
+pub async fn rescore_stale_jobs(
+    db: &Db,
+    user_id: i64,
+    chunk_size: usize,
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(&RescoreProgress),
+) -> Result<RescoreProgress, AppError> {
+    let (profile, version, job_ids) = db
+        .read(move |conn| {
+            let profile = load_match_profile(conn, user_id)?;
+            let version = profile_version(&profile);
+            let job_ids = stale_job_ids(conn, user_id, &version)?;
+            Ok((profile, version, job_ids))
+        })
+        .await?;
+    let profile = Arc::new(profile);
+
+    let mut progress = RescoreProgress {
+        profile_version: version.clone(),
+        total: job_ids.len(),
+        ..Default::default()
+    };
+
+    for chunk in job_ids.chunks(chunk_size.max(1)) {
+        if cancel.load(Ordering::SeqCst) {
+            progress.cancelled = true;
+            on_progress(&progress);
+            return Ok(progress);
+        }
+
+        let chunk_len = chunk.len();
+        let (profile, version, chunk) = (profile.clone(), version.clone(), chunk.to_vec());
+        db.call(move |conn| score_jobs(conn, user_id, &profile, &version, &chunk, &now_timestamp()))
+            .await?;
+        progress.processed += chunk_len;
+        on_progress(&progress);
+    }
+
+    progress.done = true;
+    on_progress(&progress);
+    Ok(progress)
+}
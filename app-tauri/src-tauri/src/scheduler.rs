@@ -0,0 +1,100 @@
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::reminders::{take_due_reminders, Reminder};
+use crate::commands::saved_searches::{due_saved_searches, execute_saved_search, SavedSearchRun};
+use crate::database::executor::Db;
+use crate::error::AppError;
+use crate::time::now_timestamp;
+use crate::AppState;
+
+// Les deux planificateurs ne traitent que le profil actif : l'unique fenêtre
+// ne reçoit pas les notifications des autres profils, qui restent dues
+// jusqu'à leur activation
+
+// Événement reçu par le frontend quand une recherche sauvegardée change
+pub const SAVED_SEARCH_EVENT: &str = "saved-search-matches";
+// Événement d'un rappel arrivé à échéance ; le frontend en fait une
+// notification système
+pub const REMINDER_EVENT: &str = "reminder-due";
+
+// Fréquence de vérification des recherches à rejouer ; chaque recherche a
+// ensuite son propre intervalle (`interval_minutes`)
+const DEFAULT_TICK: Duration = Duration::from_secs(60);
+
+fn tick_interval(variable: &str) -> Duration {
+    std::env::var(variable)
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|&seconds| seconds > 0)
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TICK)
+}
+
+pub fn spawn_saved_search_scheduler(app: AppHandle, db: Db) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(tick_interval("SAVED_SEARCH_TICK_SECONDS"));
+        loop {
+            ticker.tick().await;
+            let user_id = app.state::<AppState>().user_id();
+            let runs = match run_due_saved_searches(&db, user_id).await {
+                Ok(runs) => runs,
+                Err(e) => {
+                    log::error!("Échec des recherches sauvegardées : {}", e);
+                    continue;
+                }
+            };
+
+            // La première exécution sert de référence : rien à signaler
+            for run in runs.iter().filter(|run| !run.first_run && run.has_changes()) {
+                if let Err(e) = app.emit_all(SAVED_SEARCH_EVENT, run.clone()) {
+                    log::error!("Impossible de notifier le frontend : {}", e);
+                }
+            }
+        }
+    });
+}
+
+// Rejoue les recherches dues du profil ; l'échec de l'une n'empêche pas les autres
+pub async fn run_due_saved_searches(db: &Db, user_id: i64) -> Result<Vec<SavedSearchRun>, AppError> {
+    db.call(move |conn| {
+        let now = now_timestamp();
+        let mut runs = Vec::new();
+        for id in due_saved_searches(conn, user_id, &now)? {
+            match execute_saved_search(conn, id, &now) {
+                Ok(run) => runs.push(run),
+                Err(e) => log::warn!("Recherche sauvegardée {} ignorée : {}", id, e),
+            }
+        }
+        Ok(runs)
+    })
+    .await
+}
+
+pub fn spawn_reminder_scheduler(app: AppHandle, db: Db) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(tick_interval("REMINDER_TICK_SECONDS"));
+        loop {
+            ticker.tick().await;
+            let user_id = app.state::<AppState>().user_id();
+            let reminders = match run_due_reminders(&db, user_id).await {
+                Ok(reminders) => reminders,
+                Err(e) => {
+                    log::error!("Échec du calcul des rappels : {}", e);
+                    continue;
+                }
+            };
+
+            for reminder in reminders {
+                if let Err(e) = app.emit_all(REMINDER_EVENT, reminder) {
+                    log::error!("Impossible de notifier le frontend : {}", e);
+                }
+            }
+        }
+    });
+}
+
+// Rappels échus du profil, marqués comme notifiés
+pub async fn run_due_reminders(db: &Db, user_id: i64) -> Result<Vec<Reminder>, AppError> {
+    db.call(move |conn| take_due_reminders(conn, user_id, &now_timestamp())).await
+}
@@ -0,0 +1,179 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::error::AppError;
+
+// Référentiel de compétences embarqué : nom canonique, synonymes, catégorie
+// et compétence parente (React → JavaScript). Recopié en base au démarrage
+// pour que les filtres SQL puissent parcourir la hiérarchie
+const BUNDLED_SKILLS: &str = include_str!("../data/skills.csv");
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Skill {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    pub parent_id: Option<String>,
+    pub aliases: Vec<String>,
+}
+
+pub struct SkillTaxonomy {
+    skills: Vec<Skill>,
+    by_key: HashMap<String, usize>,
+}
+
+// Clé de comparaison : minuscules, sans accents, sans ponctuation ni espaces,
+// mais en gardant « + » et « # » (C++, C#). « Node.js », « node js » et
+// « NodeJS » partagent la même clé
+pub fn skill_key(label: &str) -> String {
+    label
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            'à' | 'â' | 'ä' => Some('a'),
+            'é' | 'è' | 'ê' | 'ë' => Some('e'),
+            'î' | 'ï' => Some('i'),
+            'ô' | 'ö' => Some('o'),
+            'ù' | 'û' | 'ü' => Some('u'),
+            'ç' => Some('c'),
+            c if c.is_alphanumeric() || c == '+' || c == '#' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+impl SkillTaxonomy {
+    pub fn bundled() -> &'static SkillTaxonomy {
+        static TAXONOMY: OnceLock<SkillTaxonomy> = OnceLock::new();
+        TAXONOMY.get_or_init(|| SkillTaxonomy::from_csv(BUNDLED_SKILLS))
+    }
+
+    // Format : `id;nom;categorie;parent;alias`, avec en-tête, synonymes séparés
+    // par « | ». Un parent doit être déclaré avant ses enfants ; en cas de
+    // synonyme partagé, la première compétence l'emporte
+    pub fn from_csv(csv: &str) -> Self {
+        let mut taxonomy = SkillTaxonomy {
+            skills: Vec::new(),
+            by_key: HashMap::new(),
+        };
+
+        for line in csv.lines().skip(1) {
+            let fields: Vec<&str> = line.split(';').map(str::trim).collect();
+            let [id, name, category, parent, aliases] = fields[..] else {
+                continue;
+            };
+            if id.is_empty() || name.is_empty() || taxonomy.find(id).is_some() {
+                continue;
+            }
+
+            let skill = Skill {
+                id: id.to_string(),
+                name: name.to_string(),
+                category: category.to_string(),
+                parent_id: Some(parent.to_string()).filter(|parent| taxonomy.find(parent).is_some()),
+                aliases: aliases.split('|').map(str::trim).filter(|alias| !alias.is_empty()).map(str::to_string).collect(),
+            };
+            let index = taxonomy.skills.len();
+            for label in [id, name].into_iter().chain(skill.aliases.iter().map(String::as_str)) {
+                taxonomy.by_key.entry(skill_key(label)).or_insert(index);
+            }
+            taxonomy.skills.push(skill);
+        }
+
+        taxonomy
+    }
+
+    pub fn skills(&self) -> &[Skill] {
+        &self.skills
+    }
+
+    fn find(&self, id: &str) -> Option<&Skill> {
+        self.skills.iter().find(|skill| skill.id == id)
+    }
+
+    // Compétence désignée par son identifiant, son nom ou l'un de ses synonymes
+    pub fn resolve(&self, label: &str) -> Option<&Skill> {
+        self.by_key.get(&skill_key(label)).map(|&index| &self.skills[index])
+    }
+
+    // Remplace chaque compétence connue par son nom canonique et supprime les
+    // doublons ; une compétence inconnue est conservée telle quelle
+    pub fn canonicalize(&self, skills: &[String]) -> Vec<String> {
+        let mut seen = Vec::new();
+        let mut canonical = Vec::new();
+        for skill in skills {
+            let skill = skill.split_whitespace().collect::<Vec<_>>().join(" ");
+            if skill.is_empty() {
+                continue;
+            }
+            let (key, name) = match self.resolve(&skill) {
+                Some(known) => (known.id.clone(), known.name.clone()),
+                None => (skill_key(&skill), skill),
+            };
+            if !seen.contains(&key) {
+                seen.push(key);
+                canonical.push(name);
+            }
+        }
+        canonical
+    }
+}
+
+// Recopie le référentiel dans les tables `skills` et `skill_aliases`. Les
+// compétences retirées du référentiel sont supprimées
+pub fn sync_skill_taxonomy(conn: &Connection, taxonomy: &SkillTaxonomy) -> Result<usize, AppError> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM skill_aliases", [])?;
+    for skill in taxonomy.skills() {
+        tx.execute(
+            "INSERT INTO skills (id, name, category, parent_id) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(id) DO UPDATE SET name = excluded.name, category = excluded.category,
+                parent_id = excluded.parent_id",
+            params![skill.id, skill.name, skill.category, skill.parent_id],
+        )?;
+        let labels = [&skill.id, &skill.name].into_iter().chain(&skill.aliases);
+        for label in labels {
+            tx.execute(
+                "INSERT OR IGNORE INTO skill_aliases (alias, skill_id) VALUES (?, ?)",
+                params![skill_key(label), skill.id],
+            )?;
+        }
+    }
+
+    let ids = serde_json::to_string(&taxonomy.skills().iter().map(|skill| &skill.id).collect::<Vec<_>>())
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    tx.execute("DELETE FROM skills WHERE id NOT IN (SELECT value FROM json_each(?))", [ids])?;
+    tx.commit()?;
+    Ok(taxonomy.skills().len())
+}
+
+fn canonical_json(taxonomy: &SkillTaxonomy, json: &str) -> Option<String> {
+    let skills: Vec<String> = serde_json::from_str(json).ok()?;
+    let canonical = taxonomy.canonicalize(&skills);
+    (canonical != skills).then(|| serde_json::to_string(&canonical).ok()).flatten()
+}
+
+// Ramène aux noms canoniques les compétences des offres et des profils
+// enregistrés avant le référentiel (ou avant son dernier enrichissement)
+pub fn normalize_stored_skills(conn: &Connection, taxonomy: &SkillTaxonomy) -> Result<usize, AppError> {
+    let mut updated = 0;
+    for (table, key) in [("jobs", "id"), ("user_profiles", "user_id")] {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, skills FROM {} WHERE skills IS NOT NULL AND skills NOT IN ('', '[]')",
+            key, table
+        ))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (id, skills) in rows {
+            if let Some(canonical) = canonical_json(taxonomy, &skills) {
+                conn.execute(&format!("UPDATE {} SET skills = ? WHERE {} = ?", table, key), params![canonical, id])?;
+                updated += 1;
+            }
+        }
+    }
+    Ok(updated)
+}
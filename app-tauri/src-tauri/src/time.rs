@@ -0,0 +1,5 @@
+// Horodatage courant au format SQLite `YYYY-MM-DD HH:MM:SS` (UTC), celui de
+// `CURRENT_TIMESTAMP` : les dates enregistrées se comparent entre elles
+pub fn now_timestamp() -> String {
+    chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
@@ -0,0 +1,331 @@
+use app_lib::commands::applications::{
+    find_applications, insert_application, insert_document, insert_note, insert_stage, load_application,
+    modify_document, modify_note, modify_stage, remove_application, remove_note, remove_stage,
+    set_application_status, ApplicationFilter, CreateApplicationRequest, UpdateDocumentRequest, UpdateStageRequest,
+};
+use app_lib::commands::profile::create_profile_user;
+use app_lib::commands::profile::LOCAL_USER_ID;
+use app_lib::database::migrations::{migrate_to, run_migrations};
+use app_lib::database::pool::open_connection;
+use app_lib::error::AppError;
+use app_lib::models::ApplicationStatus;
+use rusqlite::{params, Connection};
+
+fn migrated_conn(dir: &tempfile::TempDir) -> Connection {
+    let conn = open_connection(&dir.path().join("jobs.db").to_string_lossy()).unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn insert_job(conn: &Connection) -> i64 {
+    conn.execute("INSERT INTO jobs (title, company) VALUES ('Dev Rust', 'Acme')", []).unwrap();
+    conn.last_insert_rowid()
+}
+
+fn create_at(conn: &Connection, company: &str, status: ApplicationStatus, applied_at: &str) -> i64 {
+    conn.execute("INSERT INTO jobs (title, company) VALUES ('Dev Rust', ?)", [company]).unwrap();
+    let request = CreateApplicationRequest { job_id: conn.last_insert_rowid(), status, notes: None };
+    let id = insert_application(conn, LOCAL_USER_ID, &request).unwrap().id;
+    conn.execute("UPDATE applications SET applied_at = ? WHERE id = ?", params![applied_at, id]).unwrap();
+    id
+}
+
+fn create(conn: &Connection, status: ApplicationStatus) -> i64 {
+    let job_id = insert_job(conn);
+    let request = CreateApplicationRequest { job_id, status, notes: None };
+    insert_application(conn, LOCAL_USER_ID, &request).unwrap().id
+}
+
+#[test]
+fn test_transition_table() {
+    use ApplicationStatus::*;
+
+    assert!(Draft.can_transition_to(Applied));
+    assert!(!Draft.can_transition_to(Interview));
+    assert!(Applied.can_transition_to(Ghosted));
+    assert!(Ghosted.can_transition_to(Interview));
+    assert!(!Interview.can_transition_to(Screening));
+    for terminal in [Accepted, Rejected, Withdrawn] {
+        assert!(terminal.allowed_transitions().is_empty());
+    }
+    for status in ApplicationStatus::ALL {
+        assert!(!status.can_transition_to(status));
+        assert_eq!(ApplicationStatus::parse(status.as_str()), Some(status));
+    }
+}
+
+#[test]
+fn test_status_changes_are_recorded() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let id = create(&conn, ApplicationStatus::Draft);
+
+    let draft = load_application(&conn, LOCAL_USER_ID, id).unwrap();
+    assert!(draft.applied_at.is_none());
+    assert_eq!(draft.status_history.unwrap().len(), 1);
+
+    let now = "2024-05-01 09:00:00";
+    let applied = set_application_status(&conn, LOCAL_USER_ID, id, ApplicationStatus::Applied, None, now).unwrap();
+    assert_eq!(applied.applied_at.as_deref(), Some("2024-05-01 09:00:00"));
+    assert!(!applied.response_received);
+
+    let interview = set_application_status(
+        &conn,
+        LOCAL_USER_ID,
+        id,
+        ApplicationStatus::Interview,
+        Some("  Appel du recruteur  "),
+        "2024-05-03 14:00:00",
+    )
+    .unwrap();
+    assert_eq!(interview.status, ApplicationStatus::Interview);
+    assert!(interview.response_received);
+
+    let history = interview.status_history.unwrap();
+    let steps: Vec<_> = history.iter().map(|change| (change.from_status, change.to_status)).collect();
+    assert_eq!(
+        steps,
+        vec![
+            (None, ApplicationStatus::Draft),
+            (Some(ApplicationStatus::Draft), ApplicationStatus::Applied),
+            (Some(ApplicationStatus::Applied), ApplicationStatus::Interview),
+        ]
+    );
+    assert_eq!(history[2].reason.as_deref(), Some("Appel du recruteur"));
+    assert_eq!(history[2].changed_at, "2024-05-03 14:00:00");
+}
+
+#[test]
+fn test_invalid_transitions_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let id = create(&conn, ApplicationStatus::Applied);
+
+    let now = "2024-05-01 09:00:00";
+    assert!(matches!(
+        set_application_status(&conn, LOCAL_USER_ID, id, ApplicationStatus::Draft, None, now),
+        Err(AppError::Validation(_))
+    ));
+    assert!(matches!(
+        set_application_status(&conn, LOCAL_USER_ID, id, ApplicationStatus::Applied, None, now),
+        Err(AppError::Validation(_))
+    ));
+
+    // Un statut final ne peut plus évoluer
+    set_application_status(&conn, LOCAL_USER_ID, id, ApplicationStatus::Rejected, None, now).unwrap();
+    assert!(matches!(
+        set_application_status(&conn, LOCAL_USER_ID, id, ApplicationStatus::Offer, None, now),
+        Err(AppError::Validation(_))
+    ));
+
+    let loaded = load_application(&conn, LOCAL_USER_ID, id).unwrap();
+    assert_eq!(loaded.status, ApplicationStatus::Rejected);
+    assert_eq!(loaded.status_history.unwrap().len(), 2);
+}
+
+#[test]
+fn test_unknown_status_is_rejected_by_the_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let id = create(&conn, ApplicationStatus::Applied);
+
+    assert!(conn
+        .execute("UPDATE applications SET status = 'pending' WHERE id = ?", params![id])
+        .is_err());
+    let job_id = insert_job(&conn);
+    assert!(conn
+        .execute(
+            "INSERT INTO applications (user_id, job_id, status) VALUES (?, ?, 'maybe')",
+            params![LOCAL_USER_ID, job_id],
+        )
+        .is_err());
+}
+
+#[test]
+fn test_legacy_statuses_are_normalized() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    migrate_to(&conn, 19, false).unwrap();
+
+    let job_id = insert_job(&conn);
+    for (status, applied_at) in [
+        ("Interviewing", None),
+        ("offer_received", None),
+        ("pending", Some("2024-04-01 10:00:00")),
+        ("pending", None),
+        ("Rejected", None),
+    ] {
+        conn.execute(
+            "INSERT INTO applications (user_id, job_id, status, applied_at) VALUES (?, ?, ?, ?)",
+            params![LOCAL_USER_ID, job_id, status, applied_at],
+        )
+        .unwrap();
+    }
+
+    run_migrations(&conn).unwrap();
+    let mut stmt = conn.prepare("SELECT status FROM applications ORDER BY id").unwrap();
+    let statuses: Vec<String> = stmt.query_map([], |row| row.get(0)).unwrap().collect::<Result<_, _>>().unwrap();
+    assert_eq!(statuses, vec!["interview", "offer", "applied", "draft", "rejected"]);
+
+    let seeded: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM application_status_history WHERE from_status IS NULL",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(seeded, 5);
+}
+
+#[test]
+fn test_list_applications_filters_and_sorts() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let acme = create_at(&conn, "Acme", ApplicationStatus::Applied, "2024-04-01 10:00:00");
+    let globex = create_at(&conn, "Globex", ApplicationStatus::Interview, "2024-04-10 10:00:00");
+    let initech = create_at(&conn, "Initech", ApplicationStatus::Rejected, "2024-04-20 10:00:00");
+    insert_stage(&conn, LOCAL_USER_ID, globex, "interview", Some("2024-05-02T14:00:00")).unwrap();
+    insert_stage(&conn, LOCAL_USER_ID, acme, "screening", Some("2024-04-05 09:00:00")).unwrap();
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    let job_id = insert_job(&conn);
+    let request = CreateApplicationRequest { job_id, status: ApplicationStatus::Applied, notes: None };
+    insert_application(&conn, other, &request).unwrap();
+
+    let now = "2024-05-01 09:00:00";
+    let ids = |filter: ApplicationFilter| -> Vec<i64> {
+        find_applications(&conn, LOCAL_USER_ID, &filter, now)
+            .unwrap()
+            .into_iter()
+            .map(|item| item.application.id)
+            .collect()
+    };
+
+    // Profil courant seulement
+    assert_eq!(ids(ApplicationFilter::default()).len(), 3);
+    assert_eq!(
+        ids(ApplicationFilter { sort_by: Some("applied".to_string()), ..Default::default() }),
+        vec![initech, globex, acme]
+    );
+    assert_eq!(
+        ids(ApplicationFilter {
+            statuses: vec![ApplicationStatus::Applied, ApplicationStatus::Interview],
+            ..Default::default()
+        }),
+        vec![globex, acme]
+    );
+    assert_eq!(ids(ApplicationFilter { company: Some("glob".to_string()), ..Default::default() }), vec![globex]);
+    assert_eq!(
+        ids(ApplicationFilter {
+            date_from: Some("2024-04-10".to_string()),
+            date_to: Some("2024-04-19".to_string()),
+            ..Default::default()
+        }),
+        vec![globex]
+    );
+
+    // L'étape passée d'Acme ne compte pas
+    let upcoming = find_applications(
+        &conn,
+        LOCAL_USER_ID,
+        &ApplicationFilter { has_upcoming_stage: Some(true), ..Default::default() },
+        now,
+    )
+    .unwrap();
+    assert_eq!(upcoming.len(), 1);
+    assert_eq!(upcoming[0].company, "Globex");
+    assert_eq!(upcoming[0].next_stage_at.as_deref(), Some("2024-05-02 14:00:00"));
+
+    assert_eq!(
+        ids(ApplicationFilter { sort_by: Some("next_stage".to_string()), ..Default::default() }),
+        vec![globex, acme, initech]
+    );
+    assert_eq!(
+        ids(ApplicationFilter { sort_by: Some("company".to_string()), ascending: Some(false), ..Default::default() }),
+        vec![initech, globex, acme]
+    );
+}
+
+#[test]
+fn test_update_and_delete_children() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let id = create(&conn, ApplicationStatus::Applied);
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+
+    let stage = insert_stage(&conn, LOCAL_USER_ID, id, "interview", Some("2024-05-02 14:00:00")).unwrap();
+    let stage = modify_stage(
+        &conn,
+        LOCAL_USER_ID,
+        stage.id,
+        UpdateStageRequest {
+            completed_at: Some(Some("2024-05-02 15:00:00".to_string())),
+            outcome: Some(Some("positif".to_string())),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(stage.stage_type, "interview");
+    assert_eq!(stage.outcome.as_deref(), Some("positif"));
+    assert!(matches!(
+        modify_stage(&conn, other, stage.id, UpdateStageRequest::default()),
+        Err(AppError::NotFound(_))
+    ));
+    let blank = UpdateStageRequest { stage_type: Some(" ".to_string()), ..Default::default() };
+    assert!(matches!(modify_stage(&conn, LOCAL_USER_ID, stage.id, blank), Err(AppError::Validation(_))));
+
+    let note = insert_note(&conn, LOCAL_USER_ID, id, "Relancer").unwrap();
+    assert_eq!(modify_note(&conn, LOCAL_USER_ID, note.id, "Relancé le 3 mai").unwrap().content, "Relancé le 3 mai");
+    assert!(matches!(modify_note(&conn, LOCAL_USER_ID, note.id, ""), Err(AppError::Validation(_))));
+    assert!(matches!(modify_note(&conn, other, note.id, "Piraté"), Err(AppError::NotFound(_))));
+
+    let document = insert_document(&conn, LOCAL_USER_ID, id, "cv", Some("/tmp/cv.pdf"), None).unwrap();
+    let document = modify_document(
+        &conn,
+        LOCAL_USER_ID,
+        document.id,
+        UpdateDocumentRequest { file_path: Some(Some("/tmp/cv-v2.pdf".to_string())), ..Default::default() },
+    )
+    .unwrap();
+    assert_eq!(document.document_type, "cv");
+    assert_eq!(document.file_path.as_deref(), Some("/tmp/cv-v2.pdf"));
+
+    // `null` efface le champ, un champ absent le laisse inchangé
+    let cleared: UpdateStageRequest = serde_json::from_str(r#"{"outcome": null}"#).unwrap();
+    let stage = modify_stage(&conn, LOCAL_USER_ID, stage.id, cleared).unwrap();
+    assert_eq!(stage.outcome, None);
+    assert_eq!(stage.completed_at.as_deref(), Some("2024-05-02 15:00:00"));
+    let cleared: UpdateDocumentRequest = serde_json::from_str(r#"{"file_path": null}"#).unwrap();
+    assert_eq!(modify_document(&conn, LOCAL_USER_ID, document.id, cleared).unwrap().file_path, None);
+
+    assert!(!remove_note(&conn, other, note.id).unwrap());
+    assert!(remove_note(&conn, LOCAL_USER_ID, note.id).unwrap());
+    assert!(remove_stage(&conn, LOCAL_USER_ID, stage.id).unwrap());
+    assert!(!remove_stage(&conn, LOCAL_USER_ID, stage.id).unwrap());
+
+    let loaded = load_application(&conn, LOCAL_USER_ID, id).unwrap();
+    assert!(loaded.stages.unwrap().is_empty());
+    assert!(loaded.application_notes.unwrap().is_empty());
+    assert_eq!(loaded.documents.unwrap().len(), 1);
+}
+
+#[test]
+fn test_deleting_an_application_cascades() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let id = create(&conn, ApplicationStatus::Applied);
+    insert_stage(&conn, LOCAL_USER_ID, id, "interview", None).unwrap();
+    insert_note(&conn, LOCAL_USER_ID, id, "Relancer").unwrap();
+    insert_document(&conn, LOCAL_USER_ID, id, "cv", None, Some("...")).unwrap();
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+
+    assert!(!remove_application(&conn, other, id).unwrap());
+    assert!(remove_application(&conn, LOCAL_USER_ID, id).unwrap());
+    assert!(matches!(load_application(&conn, LOCAL_USER_ID, id), Err(AppError::NotFound(_))));
+
+    for table in ["application_stages", "application_notes", "application_documents", "application_status_history"] {
+        let count: i64 = conn
+            .query_row(&format!("SELECT COUNT(*) FROM {} WHERE application_id = ?", table), [id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0, "{}", table);
+    }
+}
@@ -0,0 +1,150 @@
+use app_lib::database::cache::LruCache;
+use app_lib::database::operations::DatabaseOperations;
+use app_lib::models::Job;
+use std::time::Duration;
+
+fn job(title: &str) -> Job {
+    Job {
+        id: 0,
+        title: title.to_string(),
+        company: "Acme".to_string(),
+        location: "Paris".to_string(),
+        description: String::new(),
+        url: String::new(),
+        source: "test".to_string(),
+        published_at: String::new(),
+        valid_through: None,
+        salary: None,
+        matching_score: 0.0,
+        commute_times: Default::default(),
+        skills: None,
+        experience_level: None,
+        applied_at: None,
+        status: None,
+    }
+}
+
+async fn operations(dir: &tempfile::TempDir) -> DatabaseOperations {
+    let path = dir.path().join("jobs.db").to_string_lossy().to_string();
+    let operations = DatabaseOperations::new(&path).unwrap();
+    operations.init().await.unwrap();
+    operations
+}
+
+#[test]
+fn test_least_recently_used_entry_is_evicted() {
+    let cache = LruCache::new(2, Duration::from_secs(60));
+    cache.insert("a".to_string(), 1, 0);
+    cache.insert("b".to_string(), 2, 0);
+
+    // "a" redevient la plus récente, "b" doit partir
+    assert_eq!(cache.get("a"), Some(1));
+    cache.insert("c".to_string(), 3, 0);
+
+    assert_eq!(cache.get("b"), None);
+    assert_eq!(cache.get("a"), Some(1));
+    assert_eq!(cache.get("c"), Some(3));
+
+    let stats = cache.stats();
+    assert_eq!(stats.size, 2);
+    assert_eq!(stats.evictions, 1);
+}
+
+#[test]
+fn test_expired_entries_are_misses() {
+    let cache = LruCache::new(4, Duration::from_millis(20));
+    cache.insert("a".to_string(), 1, 0);
+    std::thread::sleep(Duration::from_millis(40));
+
+    assert_eq!(cache.get("a"), None);
+    let stats = cache.stats();
+    assert_eq!(stats.misses, 1);
+    assert_eq!(stats.size, 0);
+}
+
+#[test]
+fn test_value_read_before_invalidation_is_not_cached() {
+    let cache = LruCache::new(4, Duration::from_secs(60));
+    let generation = cache.generation();
+
+    // Une écriture invalide le cache pendant la lecture de la base
+    cache.invalidate_all();
+    cache.insert("a".to_string(), 1, generation);
+    assert_eq!(cache.get("a"), None);
+
+    cache.insert("a".to_string(), 2, cache.generation());
+    assert_eq!(cache.get("a"), Some(2));
+}
+
+#[test]
+fn test_stats_track_hits_and_misses() {
+    let cache = LruCache::new(4, Duration::from_secs(60));
+    cache.insert("a".to_string(), 1, 0);
+    cache.get("a");
+    cache.get("a");
+    cache.get("b");
+
+    let stats = cache.stats();
+    assert_eq!(stats.hits, 2);
+    assert_eq!(stats.misses, 1);
+    assert!((stats.hit_rate - 2.0 / 3.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_create_job_invalidates_cached_searches() {
+    let dir = tempfile::tempdir().unwrap();
+    let operations = operations(&dir).await;
+
+    operations.create_job(job("Dev Rust")).await.unwrap();
+    assert_eq!(operations.search_jobs(1, Some("Dev"), None, 50, 0).await.unwrap().len(), 1);
+    assert_eq!(operations.search_jobs(1, Some("Dev"), None, 50, 0).await.unwrap().len(), 1);
+    assert_eq!(operations.cache_stats().hits, 1);
+
+    operations.create_job(job("Dev Python")).await.unwrap();
+
+    assert_eq!(operations.search_jobs(1, Some("Dev"), None, 50, 0).await.unwrap().len(), 2);
+    assert_eq!(operations.cache_stats().invalidations, 1);
+}
+
+#[tokio::test]
+async fn test_update_job_status_invalidates_cached_jobs() {
+    let dir = tempfile::tempdir().unwrap();
+    let operations = operations(&dir).await;
+
+    let created = operations.create_job(job("Dev Rust")).await.unwrap();
+    assert_eq!(operations.get_jobs(1).await.unwrap()[0].status.as_deref(), Some("new"));
+
+    assert!(operations.update_job_status(created.id, "applied").await.unwrap());
+
+    assert_eq!(operations.get_jobs(1).await.unwrap()[0].status.as_deref(), Some("applied"));
+}
+
+#[tokio::test]
+async fn test_flush_cache_empties_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let operations = operations(&dir).await;
+
+    operations.get_jobs(1).await.unwrap();
+    operations.search_jobs(1, Some("Rust"), None, 50, 0).await.unwrap();
+    assert_eq!(operations.cache_stats().size, 2);
+
+    operations.flush_cache();
+
+    assert_eq!(operations.cache_stats().size, 0);
+}
+
+#[tokio::test]
+async fn test_cache_size_is_bounded() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("jobs.db").to_string_lossy().to_string();
+    let operations = DatabaseOperations::with_cache_capacity(&path, 3).unwrap();
+    operations.init().await.unwrap();
+
+    for i in 0..10 {
+        operations.search_jobs(1, Some(&format!("kw{}", i)), None, 50, 0).await.unwrap();
+    }
+
+    let stats = operations.cache_stats();
+    assert_eq!(stats.size, 3);
+    assert_eq!(stats.evictions, 7);
+}
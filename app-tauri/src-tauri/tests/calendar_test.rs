@@ -0,0 +1,192 @@
+use app_lib::calendar::{parse_calendar, write_calendar, CalendarEvent};
+use app_lib::commands::applications::{insert_application, insert_stage, load_application, CreateApplicationRequest};
+use app_lib::commands::calendar::{export_calendar_text, import_calendar_text};
+use app_lib::commands::profile::{create_profile_user, LOCAL_USER_ID};
+use app_lib::database::migrations::run_migrations;
+use app_lib::database::pool::open_connection;
+use app_lib::error::AppError;
+use app_lib::models::ApplicationStatus;
+use rusqlite::Connection;
+
+fn migrated_conn(dir: &tempfile::TempDir) -> Connection {
+    let conn = open_connection(&dir.path().join("jobs.db").to_string_lossy()).unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn apply(conn: &Connection, user_id: i64, title: &str, company: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO jobs (title, company, location) VALUES (?, ?, 'Paris')",
+        [title, company],
+    )
+    .unwrap();
+    let request = CreateApplicationRequest {
+        job_id: conn.last_insert_rowid(),
+        status: ApplicationStatus::Applied,
+        notes: None,
+    };
+    insert_application(conn, user_id, &request).unwrap().id
+}
+
+const INVITATIONS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Agenda//FR\r
+BEGIN:VEVENT\r
+UID:abc-123@calendar.example\r
+DTSTART:20240502T120000Z\r
+DTEND:20240502T130000Z\r
+SUMMARY:Entretien technique\\, développeur backend\r
+ORGANIZER;CN=\"Julie: RH\":mailto:julie@globex.fr\r
+BEGIN:VALARM\r
+ACTION:DISPLAY\r
+DESCRIPTION:Rappel Initech\r
+END:VALARM\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:def-456@calendar.example\r
+DTSTART:20240503T090000Z\r
+SUMMARY:Entretien Acme\r
+DESCRIPTION:Deux postes ouverts chez Acme : visio avec l'équipe pl\r
+ ateforme\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:ghi-789@calendar.example\r
+DTSTART:20240504T090000Z\r
+SUMMARY:Dentiste\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:jkl-000@calendar.example\r
+DTSTART:20240505T090000Z\r
+SUMMARY:Entretien Globex annulé\r
+STATUS:CANCELLED\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+#[test]
+fn test_parse_calendar_handles_folding_and_escapes() {
+    let events = parse_calendar(INVITATIONS);
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0].summary, "Entretien technique, développeur backend");
+    assert_eq!(events[0].start.as_deref(), Some("2024-05-02 12:00:00"));
+    assert_eq!(events[0].organizer.as_deref(), Some("Julie: RH julie@globex.fr"));
+    // La description de l'alarme n'est pas celle de l'événement
+    assert_eq!(events[0].description, None);
+    assert_eq!(
+        events[1].description.as_deref(),
+        Some("Deux postes ouverts chez Acme : visio avec l'équipe plateforme")
+    );
+    assert!(events[3].cancelled);
+}
+
+#[test]
+fn test_written_calendar_folds_long_lines() {
+    let event = CalendarEvent {
+        uid: "stage-1@jobapp-tauri".to_string(),
+        start: "2024-05-02 12:00:00".to_string(),
+        end: None,
+        summary: "Entretien; ".to_string() + &"é".repeat(80),
+        description: Some("Ligne 1\nLigne 2".to_string()),
+        location: None,
+        last_modified: None,
+    };
+    let ics = write_calendar(std::slice::from_ref(&event), "2024-05-01 09:00:00");
+
+    assert!(ics.lines().all(|line| line.trim_end_matches('\r').len() <= 75));
+    assert!(ics.contains("DTSTART:20240502T120000Z\r\nDTEND:20240502T130000Z\r\n"));
+    assert!(ics.contains("DESCRIPTION:Ligne 1\\nLigne 2\r\n"));
+    let parsed = &parse_calendar(&ics)[0];
+    assert_eq!(parsed.summary, event.summary);
+    assert_eq!(parsed.description, event.description);
+}
+
+#[test]
+fn test_import_matches_invitations_to_applications() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let globex = apply(&conn, LOCAL_USER_ID, "Développeur Backend", "Globex SAS");
+    apply(&conn, LOCAL_USER_ID, "Développeur Frontend", "Globex SAS");
+    apply(&conn, LOCAL_USER_ID, "Dev Rust", "Acme");
+    apply(&conn, LOCAL_USER_ID, "Dev Go", "Acme");
+
+    let report = import_calendar_text(&conn, LOCAL_USER_ID, INVITATIONS).unwrap();
+    assert_eq!(report.created.len(), 1);
+    assert_eq!(report.created[0].application_id, globex);
+    assert_eq!(report.created[0].stage_type, "interview");
+    assert_eq!(report.created[0].scheduled_at.as_deref(), Some("2024-05-02 12:00:00"));
+    // Deux candidatures Acme sans intitulé pour les départager
+    assert_eq!(report.unmatched, vec!["Entretien Acme", "Dentiste"]);
+    assert_eq!(report.ignored, 1);
+
+    // Invitation déplacée puis réimportée : même étape
+    let moved = INVITATIONS.replace("DTSTART:20240502T120000Z", "DTSTART:20240506T080000Z");
+    let report = import_calendar_text(&conn, LOCAL_USER_ID, &moved).unwrap();
+    assert!(report.created.is_empty());
+    assert_eq!(report.updated.len(), 1);
+    let stages = load_application(&conn, LOCAL_USER_ID, globex).unwrap().stages.unwrap();
+    assert_eq!(stages.len(), 1);
+    assert_eq!(stages[0].scheduled_at.as_deref(), Some("2024-05-06 08:00:00"));
+}
+
+#[test]
+fn test_same_invitation_imported_by_two_profiles() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    let mine = apply(&conn, LOCAL_USER_ID, "Développeur Backend", "Globex SAS");
+    let theirs = apply(&conn, other, "Développeur Backend", "Globex SAS");
+
+    let report = import_calendar_text(&conn, LOCAL_USER_ID, INVITATIONS).unwrap();
+    assert_eq!(report.created[0].application_id, mine);
+    let report = import_calendar_text(&conn, other, INVITATIONS).unwrap();
+    assert_eq!(report.created.len(), 1);
+    assert_eq!(report.created[0].application_id, theirs);
+}
+
+#[test]
+fn test_reimported_invitation_without_uid_is_not_duplicated() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let globex = apply(&conn, LOCAL_USER_ID, "Développeur Backend", "Globex SAS");
+    let without_uid = INVITATIONS.replace("UID:abc-123@calendar.example\r\n", "");
+
+    let report = import_calendar_text(&conn, LOCAL_USER_ID, &without_uid).unwrap();
+    assert_eq!(report.created.len(), 1);
+    let report = import_calendar_text(&conn, LOCAL_USER_ID, &without_uid).unwrap();
+    assert!(report.created.is_empty());
+    assert_eq!(report.ignored, 2);
+    assert_eq!(load_application(&conn, LOCAL_USER_ID, globex).unwrap().stages.unwrap().len(), 1);
+}
+
+#[test]
+fn test_export_uses_stable_uids() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let id = apply(&conn, LOCAL_USER_ID, "Dev Rust", "Acme");
+    let past = insert_stage(&conn, LOCAL_USER_ID, id, "screening", Some("2024-04-20 09:00:00")).unwrap();
+    let next = insert_stage(&conn, LOCAL_USER_ID, id, "interview", Some("2024-05-02T14:00:00")).unwrap();
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    let theirs = apply(&conn, other, "Dev Go", "Globex");
+    insert_stage(&conn, other, theirs, "interview", Some("2024-05-03 14:00:00")).unwrap();
+
+    let now = "2024-05-01 09:00:00";
+    let upcoming = export_calendar_text(&conn, LOCAL_USER_ID, None, now).unwrap();
+    assert!(upcoming.contains(&format!("UID:stage-{}@jobapp-tauri\r\n", next.id)));
+    assert!(!upcoming.contains(&format!("UID:stage-{}@", past.id)));
+    assert!(!upcoming.contains("Globex"));
+    assert!(upcoming.contains("SUMMARY:interview : Dev Rust (Acme)\r\n"));
+    assert_eq!(export_calendar_text(&conn, LOCAL_USER_ID, None, now).unwrap(), upcoming);
+
+    let application = export_calendar_text(&conn, LOCAL_USER_ID, Some(id), now).unwrap();
+    assert_eq!(application.matches("BEGIN:VEVENT").count(), 2);
+    assert!(matches!(
+        export_calendar_text(&conn, LOCAL_USER_ID, Some(theirs), now),
+        Err(AppError::NotFound(_))
+    ));
+
+    // Un export réimporté met à jour les étapes au lieu de les dupliquer
+    let report = import_calendar_text(&conn, LOCAL_USER_ID, &application).unwrap();
+    assert!(report.created.is_empty());
+    assert_eq!(report.updated.len(), 2);
+    assert_eq!(load_application(&conn, LOCAL_USER_ID, id).unwrap().stages.unwrap().len(), 2);
+}
@@ -0,0 +1,195 @@
+use app_lib::commands::cv::{import_cv_document, load_latest_cv_document, reparse_cv_document};
+use app_lib::commands::profile::{load_user_profile, save_user_profile};
+use app_lib::cv::{extract_text, parse_cv, CvFormat, CvSectionKind, CV_PARSER_VERSION};
+use app_lib::database::migrations::run_migrations;
+use app_lib::error::AppError;
+use app_lib::models::*;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+
+const NOW: &str = "2024-03-15 10:00:00";
+const FRENCH_DOCX: &[u8] = include_bytes!("fixtures/cv/cv_fr.docx");
+const ENGLISH_PDF: &[u8] = include_bytes!("fixtures/cv/resume_en.pdf");
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()
+}
+
+fn migrated_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn profile() -> UserProfile {
+    UserProfile {
+        id: 1,
+        name: "Camille Martin".to_string(),
+        email: None,
+        phone: None,
+        locations: UserLocations {
+            primary: "Lyon".to_string(),
+            secondary: None,
+            coordinates: None,
+        },
+        cv: CVInfo {
+            path: PathBuf::new(),
+            last_updated: String::new(),
+            skills: Some(strings(&["Java"])),
+            experience_years: Some(2),
+            education: None,
+            certifications: Some(strings(&["TOEIC"])),
+        },
+        preferences: None,
+        job_preferences: None,
+    }
+}
+
+#[test]
+fn test_parses_french_docx_cv() {
+    let text = extract_text(FRENCH_DOCX, CvFormat::Docx).unwrap();
+    assert!(text.starts_with("Camille Martin\nDéveloppeuse backend — Lyon\n\nExpériences professionnelles"));
+
+    let parsed = parse_cv(&text, today());
+    assert_eq!(parsed.language.as_deref(), Some("fr"));
+    let kinds: Vec<CvSectionKind> = parsed.sections.iter().map(|section| section.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            CvSectionKind::Experience,
+            CvSectionKind::Education,
+            CvSectionKind::Skills,
+            CvSectionKind::Certifications,
+            CvSectionKind::Interests,
+        ]
+    );
+    assert_eq!(parsed.skills, strings(&["Rust", "Python", "SQL", "Docker", "Git"]));
+    assert_eq!(parsed.certifications, strings(&["Scrum Master PSM I"]));
+    // Janvier 2021 à aujourd'hui (39 mois) et septembre 2017 à décembre 2020 (40 mois)
+    assert_eq!(parsed.experience_years, Some(6));
+    assert_eq!(
+        parsed.education,
+        vec![Education {
+            institution: "INSA Lyon".to_string(),
+            degree: "Diplôme d'ingénieur".to_string(),
+            field: "informatique".to_string(),
+            start_year: 2012,
+            end_year: Some(2017),
+        }]
+    );
+}
+
+#[test]
+fn test_parses_english_pdf_cv() {
+    let text = extract_text(ENGLISH_PDF, CvFormat::Pdf).unwrap();
+    assert!(text.contains("PROFESSIONAL EXPERIENCE"));
+
+    let parsed = parse_cv(&text, today());
+    assert_eq!(parsed.language.as_deref(), Some("en"));
+    assert_eq!(parsed.skills, strings(&["Rust", "Go", "Python", "Docker", "Kubernetes", "PostgreSQL"]));
+    assert_eq!(
+        parsed.certifications,
+        strings(&["AWS Certified Solutions Architect", "Certified Kubernetes Administrator"])
+    );
+    // March 2019 to now (61 months) and 06/2015 to 02/2019 (45 months)
+    assert_eq!(parsed.experience_years, Some(8));
+    assert_eq!(parsed.education.len(), 2);
+    assert_eq!(parsed.education[0].degree, "Master of Science");
+    assert_eq!(parsed.education[0].field, "Computer Science");
+    assert_eq!(parsed.education[0].institution, "University of Edinburgh");
+    assert_eq!((parsed.education[0].start_year, parsed.education[0].end_year), (2013, Some(2015)));
+    assert_eq!(parsed.education[1].institution, "Imperial College London");
+}
+
+#[test]
+fn test_overlapping_periods_count_once() {
+    let text = "Jean Dupont\n\nExpérience\nConsultant - 2018 - 2022\nFormateur (en parallèle) - 2020 - 2021\n\
+        Compétences : Rust, SQL\n";
+    let parsed = parse_cv(text, today());
+    assert_eq!(parsed.experience_years, Some(4));
+    // Titre et contenu sur la même ligne
+    assert_eq!(parsed.skills, strings(&["Rust", "SQL"]));
+    assert!(parse_cv("Jean Dupont\nRust, SQL", today()).sections.is_empty());
+}
+
+#[test]
+fn test_import_keeps_raw_text_and_completes_profile() {
+    let conn = migrated_conn();
+    save_user_profile(&conn, 1, &profile()).unwrap();
+
+    let document = import_cv_document(&conn, 1, Path::new("/home/camille/cv.docx"), FRENCH_DOCX, today(), NOW).unwrap();
+    assert!(document.profile_updated);
+    assert_eq!(document.format, CvFormat::Docx);
+    assert_eq!(document.parser_version, CV_PARSER_VERSION);
+    assert!(document.raw_text.contains("INSA Lyon, 2012 - 2017"));
+
+    let saved = load_user_profile(&conn, 1).unwrap();
+    assert_eq!(saved.cv.path, PathBuf::from("/home/camille/cv.docx"));
+    assert_eq!(saved.cv.last_updated, "2024-03-15");
+    assert_eq!(saved.cv.skills, Some(strings(&["Rust", "Python", "SQL", "Docker", "Git"])));
+    assert_eq!(saved.cv.experience_years, Some(6));
+    assert_eq!(saved.cv.education.as_ref().map(Vec::len), Some(1));
+    assert_eq!(saved.cv, document.cv);
+
+    let latest = load_latest_cv_document(&conn, 1).unwrap().unwrap();
+    assert_eq!(latest.id, document.id);
+    assert_eq!(latest.raw_text, document.raw_text);
+}
+
+#[test]
+fn test_missing_information_does_not_erase_profile() {
+    let conn = migrated_conn();
+    save_user_profile(&conn, 1, &profile()).unwrap();
+
+    let text = "Camille Martin\n\nCompétences\nRust, Go\n";
+    import_cv_document(&conn, 1, Path::new("cv.txt"), text.as_bytes(), today(), NOW).unwrap();
+
+    let saved = load_user_profile(&conn, 1).unwrap();
+    assert_eq!(saved.cv.skills, Some(strings(&["Rust", "Go"])));
+    assert_eq!(saved.cv.experience_years, Some(2));
+    assert_eq!(saved.cv.certifications, Some(strings(&["TOEIC"])));
+}
+
+#[test]
+fn test_reparse_uses_stored_text() {
+    let conn = migrated_conn();
+    let document = import_cv_document(&conn, 1, Path::new("resume.pdf"), ENGLISH_PDF, today(), NOW).unwrap();
+    // Sans profil, le CV est conservé sans rien compléter
+    assert!(!document.profile_updated);
+
+    // Analyse produite par une version antérieure
+    conn.execute(
+        "UPDATE cv_documents SET parsed = '{\"language\":null,\"sections\":[],\"skills\":[],\"experience_years\":null,\"education\":[],\"certifications\":[]}', parser_version = 0",
+        [],
+    )
+    .unwrap();
+    save_user_profile(&conn, 1, &profile()).unwrap();
+
+    let reparsed = reparse_cv_document(&conn, 1, document.id, today(), "2024-03-16 09:00:00").unwrap();
+    assert_eq!(reparsed.parser_version, CV_PARSER_VERSION);
+    assert_eq!(reparsed.parsed_at, "2024-03-16 09:00:00");
+    assert_eq!(reparsed.imported_at, NOW);
+    assert_eq!(reparsed.cv, document.cv);
+    assert!(reparsed.profile_updated);
+    assert_eq!(load_user_profile(&conn, 1).unwrap().cv.experience_years, Some(8));
+
+    assert!(matches!(reparse_cv_document(&conn, 2, document.id, today(), NOW), Err(AppError::NotFound(_))));
+}
+
+#[test]
+fn test_rejects_unsupported_or_unreadable_files() {
+    let conn = migrated_conn();
+    let import = |path: &str, bytes: &[u8]| import_cv_document(&conn, 1, Path::new(path), bytes, today(), NOW);
+
+    assert!(matches!(import("cv.doc", FRENCH_DOCX), Err(AppError::Validation(_))));
+    assert!(matches!(import("cv.odt", FRENCH_DOCX), Err(AppError::Validation(_))));
+    assert!(matches!(import("cv.pdf", b"not a pdf"), Err(AppError::Validation(_))));
+    assert!(matches!(import("cv.docx", ENGLISH_PDF), Err(AppError::Validation(_))));
+    assert!(matches!(import("cv.txt", b"  \n\n "), Err(AppError::Validation(_))));
+    assert!(load_latest_cv_document(&conn, 1).unwrap().is_none());
+}
@@ -0,0 +1,234 @@
+use app_lib::commands::duplicates::{
+    find_duplicate_candidates, ignore_duplicate_candidate, load_duplicate_candidates, merge_duplicate_candidate,
+    merge_jobs, DuplicateDetectionStats, DuplicateStatus, DEFAULT_DUPLICATE_THRESHOLD,
+};
+use app_lib::database::migrations::run_migrations;
+use app_lib::dedup::{candidate_pairs, similarity, JobFingerprint};
+use app_lib::error::AppError;
+use rusqlite::{params, Connection};
+
+const NOW: &str = "2024-03-15 10:00:00";
+const RUST_DESCRIPTION: &str = "Nous recherchons un développeur Rust pour concevoir nos API de paiement, \
+    maintenir la base PostgreSQL et déployer les services sur Kubernetes au sein d'une équipe de huit personnes.";
+
+fn migrated_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn insert_job(conn: &Connection, title: &str, company: &str, location: &str, description: &str, source: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO jobs (title, company, location, description, source, posted_at) VALUES (?, ?, ?, ?, ?, '2024-03-01')",
+        [title, company, location, description, source],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+// Même offre publiée sur deux sites, une offre différente chez le même employeur
+fn seed(conn: &Connection) -> (i64, i64, i64) {
+    let indeed = insert_job(conn, "Développeur Rust H/F", "Octo Systems SAS", "69003 Lyon", RUST_DESCRIPTION, "indeed");
+    let feed = insert_job(conn, "Développeur Rust (F/H)", "Octo Systems", "Lyon 3e", RUST_DESCRIPTION, "jobs-example");
+    let other = insert_job(
+        conn,
+        "Product owner",
+        "Octo Systems",
+        "Lyon",
+        "Animation du backlog produit et des rituels agiles avec les équipes métier.",
+        "indeed",
+    );
+    (indeed, feed, other)
+}
+
+fn count(conn: &Connection, sql: &str) -> i64 {
+    conn.query_row(sql, [], |row| row.get(0)).unwrap()
+}
+
+#[test]
+fn test_fingerprint_ignores_gender_marks_legal_forms_and_postcodes() {
+    let a = JobFingerprint::new(1, "Développeur Rust H/F", "Octo Systems SAS", "69003 Lyon", RUST_DESCRIPTION);
+    let b = JobFingerprint::new(2, "développeur  rust (F/H)", "OCTO Systems", "Lyon 3e", RUST_DESCRIPTION);
+    assert_eq!(a.key, b.key);
+
+    let similarity = similarity(&a, &b);
+    assert!(similarity.same_fingerprint);
+    assert_eq!(similarity.description, Some(1.0));
+    assert!((similarity.score - 1.0).abs() < 1e-9);
+
+    let c = JobFingerprint::new(3, "Product owner", "Octo Systems", "Lyon", "Animation du backlog produit.");
+    let unrelated = app_lib::dedup::similarity(&a, &c);
+    assert!(unrelated.score < DEFAULT_DUPLICATE_THRESHOLD);
+    assert!(unrelated.description.unwrap() < 0.2);
+}
+
+#[test]
+fn test_near_identical_descriptions_score_high_without_same_fingerprint() {
+    let edited = RUST_DESCRIPTION.replace("huit", "dix");
+    let a = JobFingerprint::new(1, "Développeur Rust", "Octo Systems", "Lyon", RUST_DESCRIPTION);
+    let b = JobFingerprint::new(2, "Développeur Rust senior", "Octo", "Villeurbanne", &edited);
+
+    let similarity = similarity(&a, &b);
+    assert!(!similarity.same_fingerprint);
+    assert!(similarity.description.unwrap() > 0.6, "{:?}", similarity);
+}
+
+#[test]
+fn test_detection_stores_candidate_pairs() {
+    let conn = migrated_conn();
+    let (indeed, feed, _) = seed(&conn);
+
+    let stats = find_duplicate_candidates(&conn, DEFAULT_DUPLICATE_THRESHOLD, NOW).unwrap();
+    assert_eq!(stats, DuplicateDetectionStats { scanned: 3, pending: 1, new: 1 });
+
+    let candidates = load_duplicate_candidates(&conn, 1, Some(DuplicateStatus::Pending)).unwrap();
+    assert_eq!(candidates.len(), 1);
+    let candidate = &candidates[0];
+    assert_eq!((candidate.job_id, candidate.duplicate_job_id), (indeed, feed));
+    assert_eq!(candidate.reason, "fingerprint");
+    assert_eq!(candidate.job.as_ref().unwrap().source, "indeed");
+    assert_eq!(candidate.duplicate.as_ref().unwrap().source, "jobs-example");
+
+    // Une nouvelle détection ne duplique pas la paire
+    let again = find_duplicate_candidates(&conn, DEFAULT_DUPLICATE_THRESHOLD, NOW).unwrap();
+    assert_eq!(again.new, 0);
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM duplicate_candidates"), 1);
+
+    assert!(matches!(find_duplicate_candidates(&conn, 1.5, NOW), Err(AppError::Validation(_))));
+}
+
+#[test]
+fn test_pending_pair_disappears_when_jobs_diverge() {
+    let conn = migrated_conn();
+    let (_, feed, _) = seed(&conn);
+    find_duplicate_candidates(&conn, DEFAULT_DUPLICATE_THRESHOLD, NOW).unwrap();
+
+    conn.execute(
+        "UPDATE jobs SET title = 'Administrateur systèmes', company = 'Autre', location = 'Brest', description = 'Linux' WHERE id = ?",
+        [feed],
+    )
+    .unwrap();
+    let stats = find_duplicate_candidates(&conn, DEFAULT_DUPLICATE_THRESHOLD, NOW).unwrap();
+    assert_eq!(stats.pending, 0);
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM duplicate_candidates"), 0);
+}
+
+#[test]
+fn test_ignored_pair_is_not_proposed_again() {
+    let conn = migrated_conn();
+    seed(&conn);
+    find_duplicate_candidates(&conn, DEFAULT_DUPLICATE_THRESHOLD, NOW).unwrap();
+    let id = load_duplicate_candidates(&conn, 1, None).unwrap()[0].id;
+
+    let ignored = ignore_duplicate_candidate(&conn, id, NOW).unwrap();
+    assert_eq!(ignored.status, DuplicateStatus::Ignored);
+    assert_eq!(ignored.resolved_at.as_deref(), Some(NOW));
+
+    let stats = find_duplicate_candidates(&conn, DEFAULT_DUPLICATE_THRESHOLD, NOW).unwrap();
+    assert_eq!((stats.pending, stats.new), (0, 0));
+    assert!(load_duplicate_candidates(&conn, 1, Some(DuplicateStatus::Pending)).unwrap().is_empty());
+    assert_eq!(load_duplicate_candidates(&conn, 1, Some(DuplicateStatus::Ignored)).unwrap().len(), 1);
+}
+
+#[test]
+fn test_merge_repoints_references_to_survivor() {
+    let conn = migrated_conn();
+    let (indeed, feed, _) = seed(&conn);
+    conn.execute("UPDATE jobs SET description = '', salary_min = NULL WHERE id = ?", [indeed]).unwrap();
+    conn.execute("UPDATE jobs SET salary_min = 45000 WHERE id = ?", [feed]).unwrap();
+
+    conn.execute("INSERT INTO applications (user_id, job_id, status) VALUES (1, ?, 'applied')", [feed]).unwrap();
+    conn.execute("INSERT INTO kanban_columns (id, name, position) VALUES (1, 'À postuler', 0)", []).unwrap();
+    conn.execute("INSERT INTO kanban_cards (job_id, column_id, position) VALUES (?, 1, 0)", [feed]).unwrap();
+    // Trajet déjà connu pour l'offre conservée : celui du doublon est abandonné
+    conn.execute(
+        "INSERT INTO commute_times (job_id, home_type, duration, distance, mode) VALUES (?1, 'primary', 20, 5.0, 'transit'), (?2, 'primary', 25, 5.0, 'transit'), (?2, 'secondary', 40, 18.0, 'transit')",
+        [indeed, feed],
+    )
+    .unwrap();
+
+    // Le meilleur score de chaque profil est conservé
+    conn.execute(
+        "INSERT INTO job_match_scores (user_id, job_id, score, breakdown, computed_at)
+         VALUES (1, ?1, 0.4, '{}', ?3), (1, ?2, 0.7, '{}', ?3)",
+        params![indeed, feed, NOW],
+    )
+    .unwrap();
+
+    find_duplicate_candidates(&conn, DEFAULT_DUPLICATE_THRESHOLD, NOW).unwrap();
+    let id = load_duplicate_candidates(&conn, 1, None).unwrap()[0].id;
+    let survivor = merge_duplicate_candidate(&conn, 1, id, None, NOW).unwrap();
+
+    assert_eq!(survivor.id, indeed);
+    assert_eq!(survivor.matching_score, 0.7);
+    // Les champs vides de l'offre conservée sont complétés par le doublon
+    assert_eq!(survivor.description, RUST_DESCRIPTION);
+    assert_eq!(survivor.salary.and_then(|salary| salary.min), Some(45000.0));
+
+    assert_eq!(count(&conn, &format!("SELECT COUNT(*) FROM jobs WHERE id = {}", feed)), 0);
+    assert_eq!(count(&conn, &format!("SELECT COUNT(*) FROM applications WHERE job_id = {}", indeed)), 1);
+    assert_eq!(count(&conn, &format!("SELECT COUNT(*) FROM kanban_cards WHERE job_id = {}", indeed)), 1);
+    assert_eq!(count(&conn, &format!("SELECT duration FROM commute_times WHERE job_id = {} AND home_type = 'primary'", indeed)), 20);
+    assert_eq!(count(&conn, &format!("SELECT COUNT(*) FROM commute_times WHERE job_id = {}", indeed)), 2);
+    assert_eq!(count(&conn, &format!("SELECT COUNT(*) FROM commute_times WHERE job_id = {}", feed)), 0);
+
+    let merged = &load_duplicate_candidates(&conn, 1, Some(DuplicateStatus::Merged)).unwrap()[0];
+    assert_eq!(merged.merged_into, Some(indeed));
+    assert!(merged.duplicate.is_none());
+    assert!(matches!(merge_duplicate_candidate(&conn, 1, id, None, NOW), Err(AppError::Validation(_))));
+}
+
+#[test]
+fn test_merge_folds_a_profile_rows_on_both_jobs() {
+    let conn = migrated_conn();
+    let (indeed, feed, _) = seed(&conn);
+    conn.execute_batch(&format!(
+        "INSERT INTO applications (id, user_id, job_id, status) VALUES (1, 1, {indeed}, 'applied'), (2, 1, {feed}, 'interview');
+         INSERT INTO application_stages (application_id, stage_type, scheduled_at) VALUES (2, 'interview', '2024-03-20 10:00:00');
+         INSERT INTO application_notes (application_id, content) VALUES (2, 'Rappeler Julie');
+         INSERT INTO kanban_columns (id, name, position) VALUES (1, 'À postuler', 0), (2, 'Entretiens', 1);
+         INSERT INTO kanban_cards (id, job_id, column_id, position) VALUES (1, {indeed}, 1, 0), (2, {feed}, 2, 0), (3, {feed}, 1, 1);
+         INSERT INTO kanban_cards (id, job_id, column_id, position) VALUES (4, {indeed}, 2, 1);
+         INSERT INTO interviews (card_id, date, interview_type) VALUES (2, '2024-03-20 10:00:00', 'visio');"
+    ))
+    .unwrap();
+
+    merge_jobs(&conn, 1, indeed, feed).unwrap();
+
+    // Une seule candidature et une seule carte par profil pour l'offre conservée
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM applications"), 1);
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM application_stages WHERE application_id = 1"), 1);
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM application_notes WHERE application_id = 1"), 1);
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM kanban_cards"), 2);
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM interviews WHERE card_id IN (1, 4)"), 1);
+    assert_eq!(count(&conn, "SELECT position FROM kanban_cards WHERE id = 4"), 0);
+}
+
+#[test]
+fn test_shared_boilerplate_does_not_pair_every_job() {
+    // Même texte type sur toutes les offres d'un site : le bloc LSH est écarté
+    let mut jobs: Vec<JobFingerprint> = (0..250)
+        .map(|i| {
+            let title = format!("Poste {}", i);
+            let company = format!("Entreprise {}", i);
+            JobFingerprint::new(i, &title, &company, "Lyon", RUST_DESCRIPTION)
+        })
+        .collect();
+    jobs.push(JobFingerprint::new(250, "Poste 0", "Entreprise 0", "Lyon", RUST_DESCRIPTION));
+
+    assert_eq!(candidate_pairs(&jobs), vec![(0, 250)]);
+}
+
+#[test]
+fn test_merge_rejects_unrelated_survivor_and_self_merge() {
+    let conn = migrated_conn();
+    let (indeed, _, other) = seed(&conn);
+    find_duplicate_candidates(&conn, DEFAULT_DUPLICATE_THRESHOLD, NOW).unwrap();
+    let id = load_duplicate_candidates(&conn, 1, None).unwrap()[0].id;
+
+    assert!(matches!(merge_duplicate_candidate(&conn, 1, id, Some(other), NOW), Err(AppError::Validation(_))));
+    assert!(matches!(merge_jobs(&conn, 1, indeed, indeed), Err(AppError::Validation(_))));
+    assert!(matches!(merge_jobs(&conn, 1, indeed, 999), Err(AppError::NotFound(_))));
+    assert!(matches!(ignore_duplicate_candidate(&conn, 999, NOW), Err(AppError::NotFound(_))));
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM jobs"), 3);
+}
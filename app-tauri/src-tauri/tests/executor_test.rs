@@ -0,0 +1,96 @@
+use app_lib::commands::applications::{insert_application, load_application, CreateApplicationRequest};
+use app_lib::database::executor::Db;
+use app_lib::database::migrations::run_migrations;
+use app_lib::database::pool::open_connection;
+use app_lib::error::AppError;
+use app_lib::models::ApplicationStatus;
+use std::time::{Duration, Instant};
+
+fn migrated_db(dir: &tempfile::TempDir) -> Db {
+    let path = dir.path().join("jobs.db").to_string_lossy().to_string();
+    run_migrations(&open_connection(&path).unwrap()).unwrap();
+    Db::open(&path).unwrap()
+}
+
+#[tokio::test]
+async fn test_write_then_read_through_executor() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = migrated_db(&dir);
+
+    let created = db
+        .call(|conn| {
+            conn.execute("INSERT INTO jobs (title, company) VALUES ('Dev Rust', 'Acme')", [])?;
+            let job_id = conn.last_insert_rowid();
+            let request = CreateApplicationRequest {
+                job_id,
+                status: ApplicationStatus::Applied,
+                notes: None,
+            };
+            insert_application(conn, 1, &request)
+        })
+        .await
+        .unwrap();
+
+    let loaded = db
+        .read(move |conn| load_application(conn, 1, created.id))
+        .await
+        .unwrap();
+    assert_eq!(loaded.status, ApplicationStatus::Applied);
+    assert_eq!(loaded.stages.unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_read_connections_reject_writes() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = migrated_db(&dir);
+
+    let result = db
+        .read(|conn| {
+            conn.execute("INSERT INTO jobs (title, company) VALUES ('x', 'y')", [])?;
+            Ok(())
+        })
+        .await;
+
+    assert!(matches!(result, Err(AppError::Database(_))));
+}
+
+#[tokio::test]
+async fn test_reads_run_concurrently() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = migrated_db(&dir);
+
+    // Quatre lectures de 200 ms en parallèle ne doivent pas être sérialisées
+    let start = Instant::now();
+    let reads = (0..4).map(|_| {
+        db.read(|conn| {
+            std::thread::sleep(Duration::from_millis(200));
+            let count: i64 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0))?;
+            Ok(count)
+        })
+    });
+    let results = futures::future::join_all(reads).await;
+
+    assert!(results.iter().all(|r| r.is_ok()));
+    assert!(start.elapsed() < Duration::from_millis(700));
+    assert_eq!(db.metrics().reader.in_use, 0);
+}
+
+#[tokio::test]
+async fn test_blocking_work_does_not_stall_runtime() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = migrated_db(&dir);
+
+    let slow = db.read(|_| {
+        std::thread::sleep(Duration::from_millis(300));
+        Ok(())
+    });
+    let tick = async {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        Instant::now()
+    };
+
+    let start = Instant::now();
+    let (slow, ticked_at) = tokio::join!(slow, tick);
+    slow.unwrap();
+    assert!(ticked_at.duration_since(start) < Duration::from_millis(200));
+}
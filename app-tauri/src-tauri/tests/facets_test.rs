@@ -0,0 +1,89 @@
+use app_lib::commands::facets::{find_job_facets, FacetValue};
+use app_lib::commands::search::{find_jobs, SearchCriteria};
+use app_lib::database::migrations::run_migrations;
+use rusqlite::Connection;
+
+// (titre, entreprise, contrat, niveau, source, télétravail, salaire max)
+type JobRow = (&'static str, &'static str, &'static str, &'static str, &'static str, bool, Option<i64>);
+
+fn seeded_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    let jobs: &[JobRow] = &[
+        ("Développeur Rust", "Ferris", "CDI", "senior", "linkedin", true, Some(65000)),
+        ("Développeur Go", "Gopher", "CDI", "mid", "indeed", false, Some(50000)),
+        ("Stage Rust", "Ferris", "Stage", "junior", "linkedin", false, Some(12000)),
+        ("Lead Rust", "Crab Inc", "Freelance", "senior", "welcome", true, None),
+        ("Comptable", "Compta SA", "CDI", "", "indeed", false, Some(40000)),
+    ];
+    for (title, company, job_type, level, source, remote, salary_max) in jobs {
+        conn.execute(
+            "INSERT INTO jobs (title, company, job_type, experience_level, source, remote, salary_max)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            rusqlite::params![title, company, job_type, level, source, remote, salary_max],
+        )
+        .unwrap();
+    }
+    conn
+}
+
+fn facet(value: &str, count: i64) -> FacetValue {
+    FacetValue { value: value.to_string(), count }
+}
+
+#[test]
+fn test_facets_over_all_jobs() {
+    let conn = seeded_conn();
+    let facets = find_job_facets(&conn, 1, &SearchCriteria::default()).unwrap();
+
+    assert_eq!(facets.total, 5);
+    assert_eq!(
+        facets.job_type,
+        vec![facet("CDI", 3), facet("Freelance", 1), facet("Stage", 1)]
+    );
+    // Un niveau vide n'est pas une facette
+    assert_eq!(
+        facets.experience_level,
+        vec![facet("senior", 2), facet("junior", 1), facet("mid", 1)]
+    );
+    assert_eq!(facets.remote, vec![facet("false", 3), facet("true", 2)]);
+    assert_eq!(facets.company[0], facet("Ferris", 2));
+    assert!(facets.salary_bucket.contains(&facet("unknown", 1)));
+    assert!(facets.salary_bucket.contains(&facet("60k-80k", 1)));
+    assert!(facets.salary_bucket.contains(&facet("0-30k", 1)));
+}
+
+#[test]
+fn test_facet_counts_match_search_results() {
+    let conn = seeded_conn();
+    let criteria = || SearchCriteria {
+        keywords: "rust".to_string(),
+        contract_types: vec!["CDI".to_string(), "Stage".to_string()],
+        ..Default::default()
+    };
+
+    let facets = find_job_facets(&conn, 1, &criteria()).unwrap();
+    let results = find_jobs(&conn, 1, criteria()).unwrap();
+
+    assert_eq!(facets.total, results.total);
+    assert_eq!(facets.total, 2);
+    assert_eq!(facets.company, vec![facet("Ferris", 2)]);
+    assert_eq!(facets.source, vec![facet("linkedin", 2)]);
+    let by_type: i64 = facets.job_type.iter().map(|f| f.count).sum();
+    assert_eq!(by_type, results.jobs.len() as i64);
+}
+
+#[test]
+fn test_facets_on_empty_result() {
+    let conn = seeded_conn();
+    let criteria = SearchCriteria {
+        keywords: "cobol".to_string(),
+        ..Default::default()
+    };
+
+    let facets = find_job_facets(&conn, 1, &criteria).unwrap();
+
+    assert_eq!(facets.total, 0);
+    assert!(facets.job_type.is_empty());
+    assert!(facets.salary_bucket.is_empty());
+}
@@ -0,0 +1,183 @@
+use app_lib::database::executor::Db;
+use app_lib::database::migrations::run_migrations;
+use app_lib::database::pool::open_connection;
+use app_lib::error::AppError;
+use app_lib::ingest::feed::parse_feed;
+use app_lib::ingest::runner::run_source;
+use app_lib::ingest::{ingest_document, FeedSource, IngestQuery, JobSource};
+use app_lib::models::JobSearchStats;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const RSS_FIXTURE: &str = include_str!("fixtures/feeds/board.rss");
+const ATOM_FIXTURE: &str = include_str!("fixtures/feeds/board.atom");
+const JSON_FEED_FIXTURE: &str = include_str!("fixtures/feeds/board.json");
+
+fn migrated_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn migrated_db(dir: &tempfile::TempDir) -> Db {
+    let path = dir.path().join("jobs.db").to_string_lossy().to_string();
+    run_migrations(&open_connection(&path).unwrap()).unwrap();
+    Db::open(&path).unwrap()
+}
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()
+}
+
+// Serveur HTTP local qui sert `body` à chaque requête
+async fn serve(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut request = [0u8; 4096];
+            let _ = socket.read(&mut request).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+    format!("http://{}/feed.rss", address)
+}
+
+#[test]
+fn test_rss_items_are_parsed() {
+    let jobs = parse_feed(RSS_FIXTURE).unwrap();
+    assert_eq!(jobs.len(), 3);
+
+    let rust = &jobs[0];
+    assert_eq!(rust.external_id.as_deref(), Some("jobs-example-1042"));
+    assert_eq!(rust.title, "Développeur Rust Backend");
+    assert_eq!(rust.company, "Octo Systems");
+    assert_eq!(rust.location, "69003 Lyon");
+    assert_eq!(rust.url, "https://jobs.example.org/offres/1042");
+    assert_eq!(rust.posted_at.as_deref(), Some("2024-03-12"));
+    assert_eq!(rust.salary.as_deref(), Some("50 000 € - 60 000 € par an"));
+    assert_eq!(rust.description, "Nous recherchons un développeur Rust pour nos API.\nPostgreSQL\nDocker");
+
+    // Sans champ dédié, l'entreprise est tirée du titre ; sans GUID, l'URL identifie l'offre
+    let react = &jobs[1];
+    assert_eq!(react.company, "Studio Lumière");
+    assert_eq!(react.title, "Intégrateur web React");
+    assert_eq!(react.external_id.as_deref(), Some("https://jobs.example.org/offres/1043"));
+    assert_eq!(react.description, "Intégration React et TypeScript.");
+}
+
+#[test]
+fn test_atom_entries_are_parsed() {
+    let jobs = parse_feed(ATOM_FIXTURE).unwrap();
+    assert_eq!(jobs.len(), 2);
+
+    assert_eq!(jobs[0].title, "Data engineer");
+    assert_eq!(jobs[0].company, "Nantes Data");
+    assert_eq!(jobs[0].location, "Nantes");
+    assert_eq!(jobs[0].url, "https://board.example.com/offer/77");
+    assert_eq!(jobs[0].external_id.as_deref(), Some("urn:board:offer:77"));
+    assert_eq!(jobs[0].description, "Pipelines Spark et Kafka.");
+
+    assert_eq!(jobs[1].company, "Cloud Atlantique");
+    assert_eq!(jobs[1].url, "https://board.example.com/offer/78");
+    assert_eq!(jobs[1].posted_at.as_deref(), Some("2024-03-10T17:45:00+01:00"));
+}
+
+#[test]
+fn test_json_feed_items_are_parsed() {
+    let jobs = parse_feed(JSON_FEED_FIXTURE).unwrap();
+    assert_eq!(jobs.len(), 2);
+
+    assert_eq!(jobs[0].company, "Pixel Nord");
+    assert_eq!(jobs[0].location, "Lille");
+    assert_eq!(jobs[0].job_type.as_deref(), Some("fulltime"));
+    assert_eq!(jobs[0].description, "API Django, déploiement sur GCP.");
+
+    assert_eq!(jobs[1].external_id.as_deref(), Some("502"));
+    assert_eq!(jobs[1].url, "https://careers.example.com/502");
+    assert_eq!(jobs[1].title, "QA engineer");
+    assert_eq!(jobs[1].company, "Vérif");
+    assert_eq!(jobs[1].location, "Bordeaux");
+}
+
+#[test]
+fn test_invalid_feeds_are_rejected() {
+    assert!(matches!(parse_feed("<html><body/></html>"), Err(AppError::Validation(_))));
+    assert!(matches!(parse_feed("<rss><channel>"), Err(AppError::Validation(_))));
+    assert!(matches!(parse_feed(r#"{"version": "1.0", "items": []}"#), Err(AppError::Validation(_))));
+    assert!(matches!(FeedSource::new(" ", "board.rss"), Err(AppError::Validation(_))));
+}
+
+#[test]
+fn test_feed_import_records_source_and_deduplicates() {
+    let conn = migrated_conn();
+    let source = FeedSource::new("jobs-example", "board.rss").unwrap();
+
+    let first = ingest_document(&conn, &source, RSS_FIXTURE, today()).unwrap();
+    // L'offre publiée deux fois sous le même GUID n'est enregistrée qu'une fois
+    assert_eq!(first, JobSearchStats { total: 2, new: 2, updated: 0 });
+
+    let again = ingest_document(&conn, &source, RSS_FIXTURE, today()).unwrap();
+    assert_eq!(again.new, 0);
+
+    let (count, source_name, published_at, job_type): (i64, String, String, String) = conn
+        .query_row(
+            "SELECT (SELECT COUNT(*) FROM jobs), source, posted_at, job_type FROM jobs WHERE external_id = 'jobs-example-1042'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(source_name, "jobs-example");
+    assert_eq!(published_at, "2024-03-12");
+    assert_eq!(job_type, "CDI");
+}
+
+#[tokio::test]
+async fn test_feed_is_imported_from_a_local_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = migrated_db(&dir);
+
+    for (name, content) in [("atom-board", ATOM_FIXTURE), ("json-board", JSON_FEED_FIXTURE)] {
+        let path = dir.path().join(format!("{}.xml", name));
+        std::fs::write(&path, content).unwrap();
+        let source = FeedSource::new(name, &format!("file://{}", path.display())).unwrap();
+        let stats = run_source(&db, &source, &IngestQuery::default()).await.unwrap();
+        assert_eq!(stats, JobSearchStats { total: 2, new: 2, updated: 0 });
+    }
+
+    let remote: bool = db
+        .read(|conn| {
+            Ok(conn.query_row("SELECT remote FROM jobs WHERE external_id = 'rj-501'", [], |row| row.get(0))?)
+        })
+        .await
+        .unwrap();
+    assert!(remote);
+}
+
+#[tokio::test]
+async fn test_feed_is_imported_over_http() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = migrated_db(&dir);
+    let url = serve(RSS_FIXTURE).await;
+
+    let source = FeedSource::new("jobs-example", &url).unwrap();
+    assert_eq!(source.fetch(&IngestQuery::default()).await.unwrap(), RSS_FIXTURE);
+
+    let stats = run_source(&db, &source, &IngestQuery::default()).await.unwrap();
+    assert_eq!(stats, JobSearchStats { total: 2, new: 2, updated: 0 });
+}
+
+#[tokio::test]
+async fn test_missing_feed_file_is_an_io_error() {
+    let source = FeedSource::new("absent", "/nonexistent/board.rss").unwrap();
+    let result = source.fetch(&IngestQuery::default()).await;
+    assert!(matches!(result, Err(AppError::IO(_))));
+}
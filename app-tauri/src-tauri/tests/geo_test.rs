@@ -0,0 +1,186 @@
+use app_lib::commands::search::{find_jobs, SearchCriteria};
+use app_lib::database::migrations::run_migrations;
+use app_lib::database::pool::open_connection;
+use app_lib::error::AppError;
+use app_lib::geo::{geocode_missing_jobs, haversine_km, Gazetteer};
+use app_lib::models::Coordinates;
+use rusqlite::Connection;
+
+fn migrated_conn(dir: &tempfile::TempDir) -> Connection {
+    // `open_connection` enregistre la fonction SQL haversine_km
+    let conn = open_connection(&dir.path().join("jobs.db").to_string_lossy()).unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn insert_job(conn: &Connection, title: &str, location: &str) {
+    conn.execute(
+        "INSERT INTO jobs (title, company, location) VALUES (?, 'Acme', ?)",
+        rusqlite::params![title, location],
+    )
+    .unwrap();
+}
+
+fn set_homes(conn: &Connection, primary: &str, secondary: Option<&str>) {
+    conn.execute(
+        "INSERT INTO user_profiles (user_id, name, primary_home, secondary_home) VALUES (1, 'Moi', ?, ?)",
+        rusqlite::params![primary, secondary],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_gazetteer_locates_common_label_formats() {
+    let gazetteer = Gazetteer::bundled();
+    let name = |location: &str| gazetteer.locate(location).map(|commune| commune.name.clone());
+
+    assert_eq!(name("75008 Paris").as_deref(), Some("Paris"));
+    assert_eq!(name("Paris 15e").as_deref(), Some("Paris"));
+    assert_eq!(name("69003").as_deref(), Some("Lyon"));
+    assert_eq!(name("Lyon (69)").as_deref(), Some("Lyon"));
+    assert_eq!(name("Saint-Etienne, Auvergne-Rhône-Alpes").as_deref(), Some("Saint-Étienne"));
+    assert_eq!(name("st etienne").as_deref(), Some("Saint-Étienne"));
+    assert_eq!(name("Aix-en-Provence - 13").as_deref(), Some("Aix-en-Provence"));
+    assert_eq!(name("13090").as_deref(), Some("Aix-en-Provence"));
+    assert_eq!(name("Télétravail"), None);
+}
+
+#[test]
+fn test_haversine_distance() {
+    let paris = Coordinates { lat: 48.8566, lng: 2.3522 };
+    let lyon = Coordinates { lat: 45.7640, lng: 4.8357 };
+
+    let distance = haversine_km(&paris, &lyon);
+    assert!((distance - 392.0).abs() < 5.0, "distance Paris-Lyon : {}", distance);
+    assert_eq!(haversine_km(&paris, &paris), 0.0);
+}
+
+#[test]
+fn test_radius_search_around_primary_home() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    set_homes(&conn, "Lyon", Some("Paris"));
+    insert_job(&conn, "Dev Villeurbanne", "Villeurbanne");
+    insert_job(&conn, "Dev Grenoble", "Grenoble (38)");
+    insert_job(&conn, "Dev Paris", "75002 Paris");
+    insert_job(&conn, "Dev télétravail", "Télétravail");
+    geocode_missing_jobs(&conn).unwrap();
+
+    let response = find_jobs(
+        &conn,
+        1,
+        SearchCriteria {
+            radius_km: Some(120.0),
+            sort_by: Some("distance".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    // L'offre non géolocalisée est écartée du rayon mais signalée
+    let titles: Vec<&str> = response.jobs.iter().map(|job| job.title.as_str()).collect();
+    assert_eq!(titles, vec!["Dev Villeurbanne", "Dev Grenoble"]);
+    assert!(response.jobs[0].distance_km.unwrap() < 10.0);
+    assert!(response.jobs[1].distance_km.unwrap() > 80.0);
+    assert_eq!(response.total, 2);
+    assert_eq!(response.unknown_distance, Some(1));
+
+    // À la demande, elle est listée en dernier
+    let response = find_jobs(
+        &conn,
+        1,
+        SearchCriteria {
+            radius_km: Some(120.0),
+            sort_by: Some("distance".to_string()),
+            include_unknown_distance: Some(true),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(response.total, 3);
+    assert_eq!(response.jobs[2].title, "Dev télétravail");
+    assert!(response.jobs[2].distance_km.is_none());
+    assert_eq!(response.unknown_distance, Some(1));
+}
+
+#[test]
+fn test_radius_search_around_secondary_home() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    set_homes(&conn, "Lyon", Some("Paris"));
+    insert_job(&conn, "Dev Boulogne", "Boulogne-Billancourt");
+    insert_job(&conn, "Dev Lyon", "Lyon");
+    geocode_missing_jobs(&conn).unwrap();
+
+    let response = find_jobs(
+        &conn,
+        1,
+        SearchCriteria {
+            radius_km: Some(30.0),
+            home: Some("secondary".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(response.jobs.len(), 1);
+    assert_eq!(response.jobs[0].title, "Dev Boulogne");
+}
+
+#[test]
+fn test_distance_is_returned_without_radius() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    set_homes(&conn, "Nantes", None);
+    insert_job(&conn, "Dev Rennes", "Rennes");
+    insert_job(&conn, "Dev sans lieu", "");
+    geocode_missing_jobs(&conn).unwrap();
+
+    let response = find_jobs(
+        &conn,
+        1,
+        SearchCriteria { sort_by: Some("distance".to_string()), ..Default::default() },
+    )
+    .unwrap();
+
+    // Les offres non géolocalisées restent listées, en dernier
+    assert_eq!(response.jobs.len(), 2);
+    assert_eq!(response.jobs[0].title, "Dev Rennes");
+    assert!(response.jobs[0].distance_km.is_some());
+    assert!(response.jobs[1].distance_km.is_none());
+    assert_eq!(response.unknown_distance, Some(1));
+}
+
+#[test]
+fn test_radius_search_requires_a_located_home() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    set_homes(&conn, "Quelque part", None);
+
+    let result = find_jobs(&conn, 1, SearchCriteria { radius_km: Some(10.0), ..Default::default() });
+    assert!(matches!(result, Err(AppError::Validation(_))));
+
+    let result = find_jobs(
+        &conn,
+        1,
+        SearchCriteria { radius_km: Some(10.0), home: Some("bureau".to_string()), ..Default::default() },
+    );
+    assert!(matches!(result, Err(AppError::Validation(_))));
+}
+
+#[test]
+fn test_stored_home_coordinates_take_precedence() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    set_homes(&conn, "Adresse inconnue", None);
+    conn.execute(
+        "UPDATE user_profiles SET primary_latitude = 43.6047, primary_longitude = 1.4442 WHERE user_id = 1",
+        [],
+    )
+    .unwrap();
+    insert_job(&conn, "Dev Toulouse", "31000 Toulouse");
+    geocode_missing_jobs(&conn).unwrap();
+
+    let response = find_jobs(&conn, 1, SearchCriteria { radius_km: Some(5.0), ..Default::default() }).unwrap();
+    assert_eq!(response.jobs.len(), 1);
+}
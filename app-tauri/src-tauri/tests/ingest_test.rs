@@ -0,0 +1,257 @@
+use app_lib::database::migrations::run_migrations;
+use app_lib::ingest::normalize::{normalize_experience, normalize_job_type, parse_posted_at, parse_salary};
+use app_lib::ingest::{ingest_document, FranceTravailSource, IndeedSource, JobSource};
+use app_lib::models::JobSearchStats;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+
+const INDEED_FIXTURE: &str = include_str!("fixtures/ingest/indeed_search.html");
+const FRANCE_TRAVAIL_FIXTURE: &str = include_str!("fixtures/ingest/france_travail_search.json");
+
+fn migrated_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()
+}
+
+fn stats(total: usize, new: usize, updated: usize) -> JobSearchStats {
+    JobSearchStats { total, new, updated }
+}
+
+#[test]
+fn test_indeed_fixture_is_parsed() {
+    let jobs = IndeedSource::new().parse(INDEED_FIXTURE).unwrap();
+    assert_eq!(jobs.len(), 4);
+
+    let first = &jobs[0];
+    assert_eq!(first.external_id.as_deref(), Some("8f1c2a9b7d3e4f50"));
+    assert_eq!(first.title, "Développeur Rust Senior H/F");
+    assert_eq!(first.company, "Octo Systems");
+    assert_eq!(first.location, "69003 Lyon 3e");
+    assert_eq!(first.url, "https://fr.indeed.com/viewjob?jk=8f1c2a9b7d3e4f50");
+    assert_eq!(first.salary.as_deref(), Some("45 000 € - 55 000 € par an"));
+    assert_eq!(first.job_type.as_deref(), Some("CDI"));
+    assert_eq!(first.posted_at.as_deref(), Some("Publiée il y a 3 jours"));
+    assert!(first.description.starts_with("Vous développerez nos services backend"));
+
+    // L'annonce sponsorisée pointe vers la même offre
+    assert_eq!(jobs[2].url, first.url);
+    assert!(jobs[3].title.is_empty());
+}
+
+#[test]
+fn test_indeed_ingestion_normalizes_and_deduplicates() {
+    let conn = migrated_conn();
+    let result = ingest_document(&conn, &IndeedSource::new(), INDEED_FIXTURE, today()).unwrap();
+    // L'annonce sans titre est écartée, l'annonce répétée comptée une fois
+    assert_eq!(result, stats(2, 2, 0));
+
+    let (salary_min, salary_max, job_type, posted_at, experience, skills, source, latitude): (
+        f64,
+        f64,
+        String,
+        String,
+        String,
+        String,
+        String,
+        Option<f64>,
+    ) = conn
+        .query_row(
+            "SELECT salary_min, salary_max, job_type, posted_at, experience_level, skills, source, latitude
+             FROM jobs WHERE external_id = '8f1c2a9b7d3e4f50'",
+            [],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                    row.get(7)?,
+                ))
+            },
+        )
+        .unwrap();
+    assert_eq!((salary_min, salary_max), (45000.0, 55000.0));
+    assert_eq!(job_type, "CDI");
+    assert_eq!(posted_at, "2024-03-12");
+    assert_eq!(experience, "senior");
+    assert_eq!(skills, r#"["Rust","Kubernetes","GitLab","PostgreSQL"]"#);
+    assert_eq!(source, "indeed");
+    assert!(latitude.is_some());
+
+    let (remote, salary_max, posted_at): (bool, f64, String) = conn
+        .query_row(
+            "SELECT remote, salary_max, posted_at FROM jobs WHERE external_id = '0a1b2c3d4e5f6071'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert!(remote);
+    assert_eq!(salary_max, 9600.0);
+    assert_eq!(posted_at, "2024-03-15");
+}
+
+#[test]
+fn test_reingestion_reports_updates_and_keeps_user_data() {
+    let conn = migrated_conn();
+    let source = IndeedSource::new();
+    ingest_document(&conn, &source, INDEED_FIXTURE, today()).unwrap();
+    conn.execute(
+        "UPDATE jobs SET status = 'applied', matching_score = 0.8 WHERE external_id = '8f1c2a9b7d3e4f50'",
+        [],
+    )
+    .unwrap();
+
+    let unchanged = ingest_document(&conn, &source, INDEED_FIXTURE, today()).unwrap();
+    assert_eq!(unchanged, stats(2, 0, 0));
+
+    let raised = INDEED_FIXTURE.replace("45 000 € - 55 000 €", "50 000 € - 60 000 €");
+    let result = ingest_document(&conn, &source, &raised, today()).unwrap();
+    assert_eq!(result, stats(2, 0, 1));
+
+    let (count, salary_min, status, score): (i64, f64, String, f64) = conn
+        .query_row(
+            "SELECT (SELECT COUNT(*) FROM jobs), salary_min, status, matching_score
+             FROM jobs WHERE external_id = '8f1c2a9b7d3e4f50'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(salary_min, 50000.0);
+    assert_eq!(status, "applied");
+    assert_eq!(score, 0.8);
+}
+
+#[test]
+fn test_update_keeps_salary_unit_and_known_coordinates() {
+    let conn = migrated_conn();
+    let source = IndeedSource::new();
+    ingest_document(&conn, &source, INDEED_FIXTURE, today()).unwrap();
+    conn.execute_batch(
+        "UPDATE jobs SET salary_currency = NULL, salary_period = NULL WHERE external_id = '8f1c2a9b7d3e4f50';
+         UPDATE jobs SET latitude = 48.85, longitude = 2.35 WHERE external_id = '0a1b2c3d4e5f6071';",
+    )
+    .unwrap();
+
+    // « Télétravail » est inconnu du gazetteer : les coordonnées saisies restent
+    let changed = INDEED_FIXTURE
+        .replace("45 000 € - 55 000 €", "50 000 € - 60 000 €")
+        .replace("Stage - Développeur web React", "Stage - Développeur React");
+    let result = ingest_document(&conn, &source, &changed, today()).unwrap();
+    assert_eq!(result, stats(2, 0, 2));
+
+    let salary: (String, String) = conn
+        .query_row(
+            "SELECT salary_currency, salary_period FROM jobs WHERE external_id = '8f1c2a9b7d3e4f50'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(salary, ("EUR".to_string(), "yearly".to_string()));
+    let coordinates: (f64, f64) = conn
+        .query_row(
+            "SELECT latitude, longitude FROM jobs WHERE external_id = '0a1b2c3d4e5f6071'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(coordinates, (48.85, 2.35));
+}
+
+#[test]
+fn test_known_url_is_updated_instead_of_duplicated() {
+    let conn = migrated_conn();
+    conn.execute(
+        "INSERT INTO jobs (title, company, url, description)
+         VALUES ('Dev Rust', 'Octo Systems', 'https://fr.indeed.com/viewjob?jk=8f1c2a9b7d3e4f50', 'Ajoutée à la main')",
+        [],
+    )
+    .unwrap();
+
+    let result = ingest_document(&conn, &IndeedSource::new(), INDEED_FIXTURE, today()).unwrap();
+    assert_eq!(result, stats(2, 1, 1));
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0)).unwrap();
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn test_france_travail_fixture_is_ingested() {
+    let conn = migrated_conn();
+    let source = FranceTravailSource::new("jeton".to_string());
+    let result = ingest_document(&conn, &source, FRANCE_TRAVAIL_FIXTURE, today()).unwrap();
+    assert_eq!(result, stats(2, 2, 0));
+
+    type Row = (String, String, String, f64, f64, String, String, String);
+    let read = |external_id: &str| -> Row {
+        conn.query_row(
+            "SELECT company, location, url, salary_min, salary_max, job_type, experience_level, posted_at
+             FROM jobs WHERE source = 'france_travail' AND external_id = ?",
+            [external_id],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                    row.get(7)?,
+                ))
+            },
+        )
+        .unwrap()
+    };
+
+    let developer = read("174XKQM");
+    assert_eq!(developer.0, "ATLANTIQUE LOGICIELS");
+    assert_eq!(developer.1, "44000 Nantes");
+    assert_eq!(developer.2, "https://candidat.francetravail.fr/offres/recherche/detail/174XKQM");
+    assert_eq!((developer.3, developer.4), (42000.0, 48000.0));
+    assert_eq!(developer.5, "CDI");
+    assert_eq!(developer.6, "mid");
+    assert_eq!(developer.7, "2024-03-12");
+
+    let technician = read("174XLBZ");
+    assert_eq!(technician.0, "Entreprise inconnue");
+    assert_eq!(technician.3, 24570.0);
+    assert_eq!(technician.5, "Intérim");
+    assert_eq!(technician.6, "junior");
+}
+
+#[test]
+fn test_empty_france_travail_response() {
+    let source = FranceTravailSource::new("jeton".to_string());
+    assert!(source.parse("").unwrap().is_empty());
+    assert!(source.parse("<html>").is_err());
+}
+
+#[test]
+fn test_salary_and_field_normalization() {
+    let yearly = |text: &str| parse_salary(text).map(|s| (s.min.unwrap(), s.max.unwrap()));
+    assert_eq!(yearly("45k€ - 50k€"), Some((45000.0, 50000.0)));
+    assert_eq!(yearly("De 2 500,50 € à 3 000 € par mois"), Some((30006.0, 36000.0)));
+    assert_eq!(yearly("Mensuel de 2000.00 Euros sur 12 mois"), Some((24000.0, 24000.0)));
+    assert_eq!(yearly("TJM : 450 €/jour"), Some((98100.0, 98100.0)));
+    assert_eq!(yearly("15 € de l'heure"), Some((27300.0, 27300.0)));
+    assert_eq!(yearly("Selon profil"), None);
+
+    assert_eq!(parse_posted_at("il y a 2 semaines", today()).as_deref(), Some("2024-03-01"));
+    assert_eq!(parse_posted_at("Hier", today()).as_deref(), Some("2024-03-14"));
+    assert_eq!(parse_posted_at("05/03/2024", today()).as_deref(), Some("2024-03-05"));
+    assert_eq!(parse_posted_at("Récemment", today()), None);
+
+    assert_eq!(normalize_job_type("fulltime"), "CDI");
+    assert_eq!(normalize_job_type("Contrat d'apprentissage"), "Alternance");
+    assert_eq!(normalize_job_type("LIB"), "Freelance");
+    assert_eq!(normalize_experience("Expérience exigée de 7 An(s)").as_deref(), Some("senior"));
+    assert_eq!(normalize_experience("Développeur"), None);
+}
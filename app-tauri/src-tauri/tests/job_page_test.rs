@@ -0,0 +1,117 @@
+use app_lib::database::migrations::run_migrations;
+use app_lib::error::AppError;
+use app_lib::ingest::job_page::{import_job_page, parse_job_page, UNKNOWN_PAGE_SOURCE};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+
+const JSON_LD_PAGE: &str = include_str!("fixtures/pages/json_ld.html");
+const GRAPH_PAGE: &str = include_str!("fixtures/pages/graph.html");
+const OPEN_GRAPH_PAGE: &str = include_str!("fixtures/pages/open_graph.html");
+
+fn migrated_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()
+}
+
+#[test]
+fn test_json_ld_posting_is_parsed() {
+    let job = parse_job_page(JSON_LD_PAGE, None).unwrap();
+    assert_eq!(job.title, "Développeur Rust Backend");
+    assert_eq!(job.company, "Octo Systems");
+    assert_eq!(job.location, "69003 Lyon");
+    assert_eq!(job.external_id.as_deref(), Some("WTTJ-8841"));
+    assert_eq!(job.url, "https://www.welcometothejungle.com/fr/companies/octo/jobs/dev-rust");
+    assert_eq!(job.valid_through.as_deref(), Some("2024-04-30T23:59"));
+    assert_eq!(job.job_type.as_deref(), Some("FULL_TIME"));
+    assert_eq!(job.experience.as_deref(), Some("3 ans"));
+    assert_eq!(job.skills, vec!["Rust", "PostgreSQL", "Docker"]);
+    // La description, échappée deux fois, est ramenée à du texte
+    assert_eq!(job.description, "Nous recherchons un développeur Rust.\nPostgreSQL\nDocker");
+}
+
+#[test]
+fn test_json_ld_posting_in_graph_is_parsed() {
+    let job = parse_job_page(GRAPH_PAGE, None).unwrap();
+    assert_eq!(job.title, "Data engineer");
+    assert_eq!(job.company, "Nantes Data");
+    assert_eq!(job.location, "Télétravail");
+    assert!(job.remote);
+    assert_eq!(job.skills, vec!["Spark", "Kafka"]);
+    assert_eq!(job.description, "Pipelines Spark et Kafka.");
+
+    let salary = job.salary_range.unwrap();
+    assert_eq!((salary.min, salary.max), (Some(550.0), Some(550.0)));
+    assert_eq!(salary.period.as_deref(), Some("daily"));
+}
+
+#[test]
+fn test_open_graph_fallback_uses_saved_from_url() {
+    let job = parse_job_page(OPEN_GRAPH_PAGE, None).unwrap();
+    assert_eq!(job.title, "Intégrateur web React");
+    assert_eq!(job.description, "Intégration React et TypeScript pour un studio lyonnais.");
+    assert_eq!(job.posted_at.as_deref(), Some("2024-03-13"));
+    assert_eq!(job.url, "https://jobs.example.org/offres/front-end-1043");
+    assert_eq!(job.external_id.as_deref(), Some("https://jobs.example.org/offres/front-end-1043"));
+
+    // L'adresse fournie par l'appelant passe avant le commentaire du navigateur
+    let job = parse_job_page(OPEN_GRAPH_PAGE, Some("https://example.net/offre")).unwrap();
+    assert_eq!(job.url, "https://example.net/offre");
+}
+
+#[test]
+fn test_page_without_offer_is_rejected() {
+    let result = parse_job_page("<html><head></head><body><p>Rien ici</p></body></html>", None);
+    assert!(matches!(result, Err(AppError::Validation(_))));
+}
+
+#[test]
+fn test_imported_page_is_normalized_and_stored() {
+    let conn = migrated_conn();
+    let job = import_job_page(&conn, 1, JSON_LD_PAGE, None, today()).unwrap();
+
+    assert!(job.id > 0);
+    assert_eq!(job.source, "welcometothejungle.com");
+    assert_eq!(job.published_at, "2024-03-11");
+    assert_eq!(job.valid_through.as_deref(), Some("2024-04-30"));
+    assert_eq!(job.experience_level.as_deref(), Some("mid"));
+    // 4 000 à 5 000 € par mois, ramenés à l'année
+    let salary = job.salary.unwrap();
+    assert_eq!((salary.min, salary.max), (Some(48_000.0), Some(60_000.0)));
+
+    let (job_type, latitude): (String, Option<f64>) = conn
+        .query_row("SELECT job_type, latitude FROM jobs WHERE id = ?", [job.id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(job_type, "CDI");
+    assert!(latitude.is_some());
+
+    // L'offre importée est notée pour le profil sans attendre un recalcul
+    let scored: i64 = conn
+        .query_row("SELECT COUNT(*) FROM job_match_scores WHERE user_id = 1 AND job_id = ?", [job.id], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(scored, 1);
+}
+
+#[test]
+fn test_reimported_page_updates_the_same_job() {
+    let conn = migrated_conn();
+    let first = import_job_page(&conn, 1, JSON_LD_PAGE, None, today()).unwrap();
+    let again = import_job_page(&conn, 1, JSON_LD_PAGE, None, today()).unwrap();
+    assert_eq!(first.id, again.id);
+
+    // Une page sans adresse est rattachée à la source générique
+    let page = OPEN_GRAPH_PAGE.replace("saved from url", "enregistrée");
+    let orphan = import_job_page(&conn, 1, &page, None, today()).unwrap();
+    assert_eq!(orphan.source, UNKNOWN_PAGE_SOURCE);
+
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0)).unwrap();
+    assert_eq!(count, 2);
+}
@@ -0,0 +1,161 @@
+use app_lib::commands::kanban::{
+    insert_kanban_column, load_kanban_board, modify_kanban_column, relocate_card, remove_kanban_column,
+    reorder_user_columns, CreateKanbanColumnRequest, UpdateKanbanColumnRequest,
+};
+use app_lib::commands::profile::{create_profile_user, LOCAL_USER_ID};
+use app_lib::database::migrations::run_migrations;
+use app_lib::database::pool::open_connection;
+use app_lib::error::AppError;
+use app_lib::models::KanbanColumn;
+use rusqlite::{params, Connection};
+
+fn open(dir: &tempfile::TempDir) -> Connection {
+    let conn = open_connection(&dir.path().join("jobs.db").to_string_lossy()).unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn column(conn: &Connection, user_id: i64, name: &str, limit: Option<i32>) -> i64 {
+    let request = CreateKanbanColumnRequest { name: name.to_string(), color: None, limit };
+    insert_kanban_column(conn, user_id, &request).unwrap().id
+}
+
+fn card(conn: &Connection, column_id: i64, company: &str, position: i32) -> i64 {
+    conn.execute("INSERT INTO jobs (title, company) VALUES ('Dev Rust', ?)", [company]).unwrap();
+    conn.execute(
+        "INSERT INTO kanban_cards (job_id, column_id, position) VALUES (?, ?, ?)",
+        params![conn.last_insert_rowid(), column_id, position],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+fn layout(board: &[KanbanColumn]) -> Vec<Vec<(i64, i32)>> {
+    board
+        .iter()
+        .map(|column| column.cards.iter().map(|card| (card.id, card.position)).collect())
+        .collect()
+}
+
+#[test]
+fn test_board_embeds_jobs_and_is_scoped_by_profile() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let todo = column(&conn, LOCAL_USER_ID, "À postuler", None);
+    let acme = card(&conn, todo, "Acme", 1);
+    let globex = card(&conn, todo, "Globex", 0);
+    conn.execute(
+        "INSERT INTO interviews (card_id, date, interview_type) VALUES (?, '2024-05-02 14:00:00', 'visio')",
+        [acme],
+    )
+    .unwrap();
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    let theirs = column(&conn, other, "À postuler", None);
+    card(&conn, theirs, "Initech", 0);
+
+    let board = load_kanban_board(&conn, LOCAL_USER_ID).unwrap();
+    assert_eq!(board.len(), 1);
+    assert_eq!(layout(&board), vec![vec![(globex, 0), (acme, 1)]]);
+    assert_eq!(board[0].cards[1].job.company, "Acme");
+    assert_eq!(board[0].cards[1].interviews.as_ref().unwrap()[0].interview_type, "visio");
+    assert!(board[0].cards[0].interviews.as_ref().unwrap().is_empty());
+
+    let board = load_kanban_board(&conn, other).unwrap();
+    assert_eq!(board[0].cards.len(), 1);
+    assert_eq!(board[0].cards[0].job.company, "Initech");
+}
+
+#[test]
+fn test_move_renumbers_both_columns() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let todo = column(&conn, LOCAL_USER_ID, "À postuler", None);
+    let done = column(&conn, LOCAL_USER_ID, "Postulé", None);
+    let a = card(&conn, todo, "Acme", 0);
+    let b = card(&conn, todo, "Globex", 1);
+    let c = card(&conn, todo, "Initech", 2);
+    let d = card(&conn, done, "Hooli", 0);
+
+    relocate_card(&conn, LOCAL_USER_ID, b, done, 0).unwrap();
+    let board = load_kanban_board(&conn, LOCAL_USER_ID).unwrap();
+    assert_eq!(layout(&board), vec![vec![(a, 0), (c, 1)], vec![(b, 0), (d, 1)]]);
+
+    // Au-delà de la fin : dernière place ; dans la même colonne : simple réordonnancement
+    relocate_card(&conn, LOCAL_USER_ID, a, done, 10).unwrap();
+    relocate_card(&conn, LOCAL_USER_ID, a, done, 0).unwrap();
+    let board = load_kanban_board(&conn, LOCAL_USER_ID).unwrap();
+    assert_eq!(layout(&board), vec![vec![(c, 0)], vec![(a, 0), (b, 1), (d, 2)]]);
+
+    assert!(matches!(relocate_card(&conn, LOCAL_USER_ID, a, todo, -1), Err(AppError::Validation(_))));
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    assert!(matches!(relocate_card(&conn, other, a, todo, 0), Err(AppError::NotFound(_))));
+}
+
+#[test]
+fn test_full_column_refuses_new_cards() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let todo = column(&conn, LOCAL_USER_ID, "À postuler", None);
+    let interview = column(&conn, LOCAL_USER_ID, "Entretiens", Some(1));
+    let a = card(&conn, todo, "Acme", 0);
+    let b = card(&conn, interview, "Globex", 0);
+
+    match relocate_card(&conn, LOCAL_USER_ID, a, interview, 0) {
+        Err(AppError::Validation(message)) => assert!(message.contains("Entretiens")),
+        other => panic!("déplacement accepté : {:?}", other),
+    }
+    // Rien n'a bougé, et réordonner dans une colonne pleine reste possible
+    let board = load_kanban_board(&conn, LOCAL_USER_ID).unwrap();
+    assert_eq!(layout(&board), vec![vec![(a, 0)], vec![(b, 0)]]);
+    relocate_card(&conn, LOCAL_USER_ID, b, interview, 0).unwrap();
+
+    // Une limite inférieure au nombre de cartes est refusée, 0 la retire
+    let tighter = UpdateKanbanColumnRequest { limit: Some(1), ..Default::default() };
+    relocate_card(&conn, LOCAL_USER_ID, b, todo, 1).unwrap();
+    assert!(matches!(modify_kanban_column(&conn, LOCAL_USER_ID, todo, tighter), Err(AppError::Validation(_))));
+    relocate_card(&conn, LOCAL_USER_ID, a, interview, 0).unwrap();
+    let unlimited = UpdateKanbanColumnRequest { limit: Some(0), ..Default::default() };
+    assert_eq!(modify_kanban_column(&conn, LOCAL_USER_ID, interview, unlimited).unwrap().limit, None);
+    relocate_card(&conn, LOCAL_USER_ID, b, interview, 0).unwrap();
+}
+
+#[test]
+fn test_columns_can_be_renamed_recolored_and_reordered() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let todo = column(&conn, LOCAL_USER_ID, "À postuler", None);
+    let done = column(&conn, LOCAL_USER_ID, "Postulé", None);
+    let refused = column(&conn, LOCAL_USER_ID, "Refusé", None);
+
+    let update = UpdateKanbanColumnRequest {
+        name: Some("  Candidatures envoyées ".to_string()),
+        color: Some("#1E90ff".to_string()),
+        ..Default::default()
+    };
+    let updated = modify_kanban_column(&conn, LOCAL_USER_ID, done, update).unwrap();
+    assert_eq!(updated.name, "Candidatures envoyées");
+    assert_eq!(updated.color.as_deref(), Some("#1E90ff"));
+    for invalid in [
+        UpdateKanbanColumnRequest { name: Some(" ".to_string()), ..Default::default() },
+        UpdateKanbanColumnRequest { name: Some("à POSTULER".to_string()), ..Default::default() },
+        UpdateKanbanColumnRequest { color: Some("bleu".to_string()), ..Default::default() },
+        UpdateKanbanColumnRequest { limit: Some(-2), ..Default::default() },
+    ] {
+        assert!(matches!(modify_kanban_column(&conn, LOCAL_USER_ID, done, invalid), Err(AppError::Validation(_))));
+    }
+
+    assert!(matches!(reorder_user_columns(&conn, LOCAL_USER_ID, &[refused, todo]), Err(AppError::Validation(_))));
+    reorder_user_columns(&conn, LOCAL_USER_ID, &[refused, todo, done]).unwrap();
+    let board = load_kanban_board(&conn, LOCAL_USER_ID).unwrap();
+    let order: Vec<_> = board.iter().map(|column| (column.id, column.position)).collect();
+    assert_eq!(order, vec![(refused, 0), (todo, 1), (done, 2)]);
+
+    // Une colonne qui contient des cartes n'est pas supprimée
+    card(&conn, todo, "Acme", 0);
+    assert!(matches!(remove_kanban_column(&conn, LOCAL_USER_ID, todo), Err(AppError::Validation(_))));
+    assert!(remove_kanban_column(&conn, LOCAL_USER_ID, refused).unwrap());
+    assert!(!remove_kanban_column(&conn, LOCAL_USER_ID, refused).unwrap());
+    let board = load_kanban_board(&conn, LOCAL_USER_ID).unwrap();
+    let order: Vec<_> = board.iter().map(|column| (column.id, column.position)).collect();
+    assert_eq!(order, vec![(todo, 0), (done, 1)]);
+}
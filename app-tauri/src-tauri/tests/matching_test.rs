@@ -0,0 +1,184 @@
+use app_lib::database::migrations::run_migrations;
+use app_lib::matching::{
+    load_match_explanation, load_match_profile, score_all_jobs, score_job, MatchFactorKind, MatchJob, MatchProfile,
+};
+use app_lib::models::{JobPreferences, KeywordWeight};
+use rusqlite::Connection;
+
+const NOW: &str = "2024-03-15 10:00:00";
+
+fn migrated_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn keyword(keyword: &str, weight: i32, required: bool) -> KeywordWeight {
+    KeywordWeight {
+        keyword: keyword.to_string(),
+        weight,
+        required: Some(required),
+    }
+}
+
+fn profile() -> MatchProfile {
+    MatchProfile {
+        skills: vec!["Rust".to_string(), "PostgreSQL".to_string(), "Docker".to_string()],
+        experience_years: Some(4),
+        job_preferences: Some(JobPreferences {
+            min_salary: Some(50000.0),
+            preferred_job_types: Some(vec!["CDI".to_string()]),
+            remote_preference: Some("remote".to_string()),
+        }),
+        keywords: vec![keyword("backend", 3, false), keyword("paiement", 1, false)],
+    }
+}
+
+fn rust_job() -> MatchJob {
+    MatchJob {
+        id: 7,
+        title: "Développeur Rust backend".to_string(),
+        description: "API de facturation".to_string(),
+        skills: vec!["Rust".to_string(), "PostgreSQL".to_string(), "Kafka".to_string(), "Docker".to_string()],
+        experience_level: Some("mid".to_string()),
+        salary_min: Some(45000.0),
+        salary_max: Some(55000.0),
+        job_type: "CDI".to_string(),
+        remote: true,
+        commute_minutes: None,
+    }
+}
+
+fn factor(explanation: &app_lib::matching::MatchExplanation, kind: MatchFactorKind) -> &app_lib::matching::MatchFactor {
+    explanation.factors.iter().find(|f| f.kind == kind).unwrap()
+}
+
+#[test]
+fn test_score_combines_factors_with_breakdown() {
+    let explanation = score_job(&profile(), &rust_job(), NOW);
+
+    let skills = factor(&explanation, MatchFactorKind::Skills);
+    assert_eq!(skills.score, Some(0.75));
+    assert_eq!(skills.missing, vec!["Kafka"]);
+    assert_eq!(factor(&explanation, MatchFactorKind::Experience).score, Some(1.0));
+    assert_eq!(factor(&explanation, MatchFactorKind::Salary).score, Some(1.0));
+    assert_eq!(factor(&explanation, MatchFactorKind::JobType).score, Some(1.0));
+    assert_eq!(factor(&explanation, MatchFactorKind::Remote).score, Some(1.0));
+
+    let keywords = factor(&explanation, MatchFactorKind::Keywords);
+    assert_eq!(keywords.score, Some(0.75));
+    assert_eq!(keywords.matched, vec!["backend"]);
+
+    // Les contributions s'additionnent au score final
+    let total: f64 = explanation.factors.iter().map(|f| f.contribution).sum();
+    assert!((explanation.score - total).abs() < 1e-9);
+    assert!(explanation.score > 0.85 && explanation.score < 1.0, "{}", explanation.score);
+}
+
+#[test]
+fn test_missing_data_is_excluded_rather_than_penalized() {
+    let job = MatchJob {
+        skills: vec!["Rust".to_string()],
+        salary_min: None,
+        salary_max: None,
+        experience_level: None,
+        ..rust_job()
+    };
+    let explanation = score_job(&profile(), &job, NOW);
+
+    assert_eq!(factor(&explanation, MatchFactorKind::Salary).score, None);
+    assert_eq!(factor(&explanation, MatchFactorKind::Salary).contribution, 0.0);
+    assert_eq!(factor(&explanation, MatchFactorKind::Experience).score, None);
+    // Toutes les données disponibles sont favorables : score presque parfait
+    assert!(explanation.score > 0.9, "{}", explanation.score);
+}
+
+#[test]
+fn test_unfavourable_job_scores_low() {
+    let job = MatchJob {
+        title: "Développeur Java".to_string(),
+        skills: vec!["Java".to_string(), "Spring".to_string()],
+        experience_level: Some("lead".to_string()),
+        salary_max: Some(35000.0),
+        job_type: "Stage".to_string(),
+        remote: false,
+        commute_minutes: Some(100),
+        ..rust_job()
+    };
+    let explanation = score_job(&profile(), &job, NOW);
+    assert_eq!(factor(&explanation, MatchFactorKind::Commute).score, Some(0.0));
+    assert_eq!(factor(&explanation, MatchFactorKind::Experience).score, Some(0.0));
+    assert!(explanation.score < 0.2, "{}", explanation.score);
+}
+
+#[test]
+fn test_required_keyword_missing_zeroes_the_score() {
+    let mut profile = profile();
+    profile.keywords.push(keyword("go", 1, true));
+    // « go » ne doit pas correspondre à « Google »
+    let job = MatchJob {
+        description: "Hébergement Google Cloud".to_string(),
+        ..rust_job()
+    };
+    let explanation = score_job(&profile, &job, NOW);
+    assert_eq!(explanation.missing_required, vec!["go"]);
+    assert_eq!(explanation.score, 0.0);
+}
+
+#[test]
+fn test_scores_are_stored_and_explained() {
+    let conn = migrated_conn();
+    conn.execute(
+        "INSERT INTO user_profiles (user_id, name, primary_home, skills, experience_years, job_preferences)
+         VALUES (1, 'Camille', 'Lyon', '[\"Rust\", \"Docker\"]', 6, '{\"min_salary\": 60000, \"preferred_job_types\": [\"CDI\"], \"remote_preference\": null}')",
+        [],
+    )
+    .unwrap();
+    conn.execute_batch(
+        "INSERT INTO search_preferences (id, name, is_active) VALUES (1, 'Principale', 1), (2, 'Archivée', 0);
+         INSERT INTO search_categories (id, preference_id, name) VALUES (1, 1, 'Tech'), (2, 2, 'Ancienne');
+         INSERT INTO keywords (category_id, keyword, weight, required) VALUES (1, 'rust', 2, 0), (2, 'cobol', 5, 1);
+         INSERT INTO jobs (id, title, company, skills, experience_level, salary_max, job_type)
+         VALUES (1, 'Ingénieur Rust', 'Octo', '[\"Rust\", \"Docker\"]', 'senior', 60000, 'CDI'),
+                (2, 'Comptable', 'Fiduciaire', '[\"Excel\"]', 'junior', 30000, 'CDD');
+         INSERT INTO commute_times (job_id, home_type, duration, distance) VALUES (1, 'primary', 25, 8.0), (1, 'secondary', 70, 40.0);",
+    )
+    .unwrap();
+
+    let profile = load_match_profile(&conn, 1).unwrap();
+    assert_eq!(profile.skills, vec!["Rust", "Docker"]);
+    // Seuls les mots-clés des préférences actives comptent
+    assert_eq!(profile.keywords.len(), 1);
+
+    assert_eq!(score_all_jobs(&conn, 1, NOW).unwrap(), 2);
+    let (good, bad): (f64, f64) = conn
+        .query_row(
+            "SELECT (SELECT score FROM job_match_scores WHERE user_id = 1 AND job_id = 1),
+                    (SELECT score FROM job_match_scores WHERE user_id = 1 AND job_id = 2)",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert!((good - 1.0).abs() < 1e-9, "{}", good);
+    assert!(bad < 0.3, "{}", bad);
+    // Le score appartient au profil : la colonne partagée de l'offre n'est pas touchée
+    let shared: f64 = conn.query_row("SELECT matching_score FROM jobs WHERE id = 1", [], |row| row.get(0)).unwrap();
+    assert_eq!(shared, 0.0);
+
+    let explanation = load_match_explanation(&conn, 1, 1, "plus tard").unwrap();
+    assert_eq!(explanation.computed_at, NOW);
+    assert_eq!(explanation.score, good);
+    assert_eq!(factor(&explanation, MatchFactorKind::Commute).detail, "25 min de trajet");
+}
+
+#[test]
+fn test_explanation_is_computed_on_demand() {
+    let conn = migrated_conn();
+    conn.execute("INSERT INTO jobs (id, title, company) VALUES (3, 'Data analyst', 'Acme')", []).unwrap();
+
+    let explanation = load_match_explanation(&conn, 1, 3, NOW).unwrap();
+    assert_eq!(explanation.job_id, 3);
+    assert_eq!(explanation.score, 0.0);
+    assert!(explanation.factors.iter().all(|f| f.score.is_none()));
+    assert!(load_match_explanation(&conn, 1, 99, NOW).is_err());
+}
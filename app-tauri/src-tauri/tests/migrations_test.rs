@@ -0,0 +1,267 @@
+use app_lib::database::migrations::{
+    apply_migrations, current_version, get_migrations, migrate_to, pending_migrations, run_migrations,
+    Migration, MigrationDirection,
+};
+use app_lib::error::AppError;
+use rusqlite::Connection;
+use std::collections::HashSet;
+
+// Tables et colonnes interrogées par les commandes et DatabaseOperations
+const EXPECTED_SCHEMA: &[(&str, &[&str])] = &[
+    ("users", &["id", "email", "password_hash", "name"]),
+    (
+        "user_profiles",
+        &["id", "user_id", "name", "email", "phone", "primary_home", "secondary_home", "cv_path", "cv_last_updated"],
+    ),
+    (
+        "jobs",
+        &[
+            "id", "user_id", "title", "company", "location", "job_type", "salary_min", "salary_max",
+            "salary_currency", "salary_period", "description", "requirements", "url", "posted_at",
+            "experience_level", "skills", "remote", "source", "status", "matching_score",
+            "created_at", "updated_at",
+        ],
+    ),
+    ("commute_times", &["id", "job_id", "home_type", "duration", "distance", "mode"]),
+    (
+        "documents",
+        &["id", "user_id", "title", "document_type", "file_path", "content", "created_at"],
+    ),
+    (
+        "document_templates",
+        &["id", "name", "template_type", "content", "variables", "is_default", "language", "created_at"],
+    ),
+    ("kanban_columns", &["id", "name", "position", "color", "card_limit"]),
+    (
+        "kanban_cards",
+        &["id", "job_id", "column_id", "position", "notes", "applied_at", "follow_up_date"],
+    ),
+    ("interviews", &["id", "card_id", "date", "interview_type", "notes", "outcome"]),
+    ("search_preferences", &["id", "name", "is_active", "last_used"]),
+    ("search_categories", &["id", "preference_id", "name", "priority"]),
+    ("keywords", &["id", "category_id", "keyword", "weight", "required"]),
+    (
+        "applications",
+        &[
+            "id", "user_id", "job_id", "status", "applied_at", "response_received", "notes",
+            "created_at", "updated_at",
+        ],
+    ),
+    (
+        "application_stages",
+        &[
+            "id", "application_id", "stage_type", "scheduled_at", "completed_at", "notes", "outcome",
+            "created_at", "updated_at",
+        ],
+    ),
+    (
+        "application_documents",
+        &["id", "application_id", "document_type", "file_path", "content", "created_at", "updated_at"],
+    ),
+    (
+        "application_notes",
+        &["id", "application_id", "content", "created_at", "updated_at"],
+    ),
+];
+
+fn columns(conn: &Connection, table: &str) -> HashSet<String> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({})", table))
+        .unwrap();
+    stmt.query_map([], |row| row.get::<_, String>(1))
+        .unwrap()
+        .collect::<Result<HashSet<_>, _>>()
+        .unwrap()
+}
+
+#[test]
+fn test_migrations_create_canonical_schema_on_empty_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = Connection::open(dir.path().join("jobs.db")).unwrap();
+
+    run_migrations(&conn).unwrap();
+
+    for (table, expected) in EXPECTED_SCHEMA {
+        let actual = columns(&conn, table);
+        assert!(!actual.is_empty(), "table manquante : {}", table);
+        for column in *expected {
+            assert!(
+                actual.contains(*column),
+                "colonne manquante : {}.{}",
+                table,
+                column
+            );
+        }
+    }
+
+    let latest = get_migrations().last().unwrap().version;
+    assert_eq!(current_version(&conn).unwrap(), latest);
+}
+
+// Schéma créé par `db::init_db` avant le runner de migrations
+const BASELINE_SCHEMA: &str = include_str!("fixtures/baseline_schema.sql");
+
+#[test]
+fn test_migrations_convert_a_pre_migration_database() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("jobs.db");
+    {
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(BASELINE_SCHEMA).unwrap();
+        conn.execute_batch(
+            "INSERT INTO user_profiles (name, email, location, skills) VALUES ('Camille', 'c@example.com', 'Lyon', 'Rust, Go');
+             INSERT INTO user_profile (name, primary_home) VALUES ('Ancien profil', 'Paris');
+             INSERT INTO jobs (title, company, salary_range) VALUES ('Développeur Rust', 'Acme', '45-55k');
+             INSERT INTO kanban_columns (name, position, \"limit\") VALUES ('Entretiens', 0, 3);
+             INSERT INTO kanban_cards (job_id, column_id, position) VALUES (1, 1, 0);
+             INSERT INTO job_stats (total_applications) VALUES (4);",
+        )
+        .unwrap();
+    }
+
+    let conn = Connection::open(&path).unwrap();
+    run_migrations(&conn).unwrap();
+
+    for (table, expected) in EXPECTED_SCHEMA {
+        let actual = columns(&conn, table);
+        for column in *expected {
+            assert!(actual.contains(*column), "colonne manquante : {}.{}", table, column);
+        }
+    }
+    assert_eq!(current_version(&conn).unwrap(), get_migrations().last().unwrap().version);
+
+    // Les lignes sont reprises, rattachées à l'utilisateur local
+    let profile: (i64, String, String, String) = conn
+        .query_row("SELECT user_id, name, primary_home, skills FROM user_profiles", [], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .unwrap();
+    assert_eq!(profile, (1, "Camille".to_string(), "Lyon".to_string(), "[]".to_string()));
+    let job: (String, String) = conn
+        .query_row("SELECT location, skills FROM jobs WHERE id = 1", [], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap();
+    assert_eq!(job, (String::new(), "[]".to_string()));
+    let indexed: i64 = conn
+        .query_row("SELECT rowid FROM jobs_fts WHERE jobs_fts MATCH 'rust'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(indexed, 1);
+    let limit: i64 = conn
+        .query_row("SELECT card_limit FROM kanban_columns WHERE name = 'Entretiens'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(limit, 3);
+    let cards: i64 = conn.query_row("SELECT COUNT(*) FROM kanban_cards", [], |row| row.get(0)).unwrap();
+    assert_eq!(cards, 1);
+
+    // Seule une table sans équivalent canonique reste de côté
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name LIKE 'legacy%' ORDER BY name")
+        .unwrap();
+    let legacy: Vec<String> = stmt.query_map([], |row| row.get(0)).unwrap().collect::<Result<_, _>>().unwrap();
+    assert_eq!(legacy, vec!["legacy_job_stats".to_string()]);
+
+    run_migrations(&conn).unwrap();
+    let jobs: i64 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0)).unwrap();
+    assert_eq!(jobs, 1);
+}
+
+#[test]
+fn test_migrations_are_idempotent() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("jobs.db");
+
+    run_migrations(&Connection::open(&path).unwrap()).unwrap();
+
+    // Une seconde ouverture ne doit rien réappliquer
+    let conn = Connection::open(&path).unwrap();
+    run_migrations(&conn).unwrap();
+
+    let applied: i64 = conn
+        .query_row("SELECT COUNT(*) FROM schema_version", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(applied, get_migrations().len() as i64);
+}
+
+#[test]
+fn test_migrations_seed_local_user() {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+
+    // `create_application` rattache les candidatures à l'utilisateur local
+    let email: String = conn
+        .query_row("SELECT email FROM users WHERE id = 1", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(email, "local@localhost");
+}
+
+#[test]
+fn test_migrate_to_rolls_back_and_reapplies() {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+
+    let steps = migrate_to(&conn, 3, false).unwrap();
+    assert!(steps.iter().all(|s| s.direction == MigrationDirection::Down));
+    assert_eq!(steps.first().unwrap().version, get_migrations().last().unwrap().version);
+    assert_eq!(current_version(&conn).unwrap(), 3);
+    assert!(columns(&conn, "applications").is_empty());
+
+    migrate_to(&conn, get_migrations().last().unwrap().version, false).unwrap();
+    assert!(!columns(&conn, "applications").is_empty());
+}
+
+#[test]
+fn test_migrate_to_unknown_version_fails() {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+
+    assert!(matches!(migrate_to(&conn, 999, false), Err(AppError::Validation(_))));
+}
+
+#[test]
+fn test_dry_run_reports_without_applying() {
+    let conn = Connection::open_in_memory().unwrap();
+
+    let pending = pending_migrations(&conn).unwrap();
+    assert_eq!(pending.len(), get_migrations().len());
+    assert_eq!(current_version(&conn).unwrap(), 0);
+    assert!(columns(&conn, "jobs").is_empty());
+
+    run_migrations(&conn).unwrap();
+    assert!(pending_migrations(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn test_modified_migration_is_detected() {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+
+    conn.execute("UPDATE schema_version SET checksum = 'altéré' WHERE version = 2", [])
+        .unwrap();
+
+    assert!(matches!(run_migrations(&conn), Err(AppError::Database(_))));
+}
+
+#[test]
+fn test_failed_migration_is_rolled_back() {
+    let conn = Connection::open_in_memory().unwrap();
+    let migrations = vec![
+        Migration {
+            version: 1,
+            description: "Table valide",
+            up: "CREATE TABLE a (id INTEGER);",
+            down: "DROP TABLE a;",
+        },
+        Migration {
+            version: 2,
+            description: "Migration en échec",
+            up: "CREATE TABLE b (id INTEGER); INSERT INTO inexistante VALUES (1);",
+            down: "DROP TABLE b;",
+        },
+    ];
+
+    assert!(apply_migrations(&conn, &migrations, 2, false).is_err());
+
+    // La migration 1 est conservée, la 2 n'a laissé aucune trace
+    assert_eq!(current_version(&conn).unwrap(), 1);
+    assert!(!columns(&conn, "a").is_empty());
+    assert!(columns(&conn, "b").is_empty());
+}
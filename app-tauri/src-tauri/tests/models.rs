@@ -64,6 +64,7 @@ fn test_job_serialization() {
         url: "https://example.com/job/1".to_string(),
         source: "LinkedIn".to_string(),
         published_at: "2024-03-21".to_string(),
+        valid_through: Some("2024-04-30".to_string()),
         salary: Some(SalaryRange {
             min: Some(50000.0),
             max: Some(70000.0),
@@ -0,0 +1,189 @@
+use app_lib::commands::search::{find_jobs, AppliedFacet, SearchCriteria};
+use app_lib::database::migrations::run_migrations;
+use app_lib::error::AppError;
+use rusqlite::Connection;
+use std::collections::HashSet;
+
+// 25 offres dont beaucoup partagent la même date et le même salaire :
+// seul l'id permet de les départager
+fn seeded_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    for i in 0..25 {
+        conn.execute(
+            "INSERT INTO jobs (title, company, description, posted_at, salary_max, location)
+             VALUES (?, 'Acme', 'Poste Rust', ?, ?, 'Lyon')",
+            rusqlite::params![
+                format!("Développeur {}", i),
+                format!("2024-01-0{}", 1 + i % 3),
+                if i % 4 == 0 { None } else { Some(40000 + (i % 2) * 5000) },
+            ],
+        )
+        .unwrap();
+    }
+    conn
+}
+
+fn walk_with_cursor(conn: &Connection, keywords: &str, sort_by: &str, limit: u32) -> Vec<i64> {
+    let mut ids = Vec::new();
+    let mut cursor = None;
+    loop {
+        let response = find_jobs(
+            conn,
+            1,
+            SearchCriteria {
+                keywords: keywords.to_string(),
+                sort_by: Some(sort_by.to_string()),
+                limit: Some(limit),
+                cursor: cursor.take(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(response.total, 25);
+        assert!(response.jobs.len() <= limit as usize);
+        ids.extend(response.jobs.iter().map(|job| job.id));
+        match response.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    ids
+}
+
+#[test]
+fn test_cursor_pagination_visits_every_job_once() {
+    let conn = seeded_conn();
+
+    for sort_by in ["date", "salary", "relevance"] {
+        let ids = walk_with_cursor(&conn, "rust", sort_by, 7);
+        let unique: HashSet<_> = ids.iter().collect();
+        assert_eq!(ids.len(), 25, "tri {}", sort_by);
+        assert_eq!(unique.len(), 25, "tri {}", sort_by);
+    }
+}
+
+#[test]
+fn test_cursor_pagination_is_not_shifted_by_new_jobs() {
+    let conn = seeded_conn();
+    let first = find_jobs(
+        &conn,
+        1,
+        SearchCriteria { sort_by: Some("date".to_string()), limit: Some(10), ..Default::default() },
+    )
+    .unwrap();
+
+    // Une offre plus récente arrive pendant le défilement
+    conn.execute(
+        "INSERT INTO jobs (title, company, posted_at) VALUES ('Nouvelle', 'Acme', '2024-02-01')",
+        [],
+    )
+    .unwrap();
+
+    let second = find_jobs(
+        &conn,
+        1,
+        SearchCriteria {
+            sort_by: Some("date".to_string()),
+            limit: Some(10),
+            cursor: first.next_cursor.clone(),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let first_ids: HashSet<_> = first.jobs.iter().map(|job| job.id).collect();
+    assert!(second.jobs.iter().all(|job| !first_ids.contains(&job.id)));
+    assert_eq!(second.jobs.len(), 10);
+    assert_eq!(second.total, 26);
+}
+
+#[test]
+fn test_offset_and_page_pagination_agree() {
+    let conn = seeded_conn();
+    let page = |criteria: SearchCriteria| -> Vec<i64> {
+        find_jobs(&conn, 1, criteria).unwrap().jobs.iter().map(|job| job.id).collect()
+    };
+
+    let by_offset = page(SearchCriteria { limit: Some(10), offset: Some(10), ..Default::default() });
+    let by_page = page(SearchCriteria { limit: Some(10), page: Some(2), ..Default::default() });
+    assert_eq!(by_offset, by_page);
+    assert_eq!(by_offset.len(), 10);
+
+    let last = find_jobs(&conn, 1, SearchCriteria { limit: Some(10), page: Some(3), ..Default::default() }).unwrap();
+    assert_eq!(last.jobs.len(), 5);
+    assert_eq!(last.offset, 20);
+    assert!(last.next_cursor.is_none());
+
+    // Les pages successives couvrent chaque offre une seule fois
+    let all: HashSet<i64> = (0..3)
+        .flat_map(|i| page(SearchCriteria { limit: Some(10), offset: Some(i * 10), ..Default::default() }))
+        .collect();
+    assert_eq!(all.len(), 25);
+
+    let beyond = find_jobs(&conn, 1, SearchCriteria { limit: Some(10), page: Some(u32::MAX), ..Default::default() })
+        .unwrap();
+    assert!(beyond.jobs.is_empty());
+    assert_eq!(beyond.offset, u32::MAX);
+}
+
+#[test]
+fn test_limit_is_clamped_and_defaults_apply() {
+    let conn = seeded_conn();
+
+    let response = find_jobs(&conn, 1, SearchCriteria::default()).unwrap();
+    assert_eq!(response.limit, 50);
+    assert_eq!(response.jobs.len(), 25);
+    assert_eq!(response.sort_by, "date");
+
+    let response = find_jobs(&conn, 1, SearchCriteria { limit: Some(0), ..Default::default() }).unwrap();
+    assert_eq!(response.limit, 1);
+    assert_eq!(response.jobs.len(), 1);
+}
+
+#[test]
+fn test_response_reports_applied_facets() {
+    let conn = seeded_conn();
+
+    let response = find_jobs(
+        &conn,
+        1,
+        SearchCriteria {
+            keywords: "rust".to_string(),
+            location: "Lyon".to_string(),
+            contract_types: vec!["CDI".to_string()],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.applied_facets,
+        vec![
+            AppliedFacet { field: "keywords".to_string(), value: "rust".to_string() },
+            AppliedFacet { field: "location".to_string(), value: "Lyon".to_string() },
+            AppliedFacet { field: "job_type".to_string(), value: "CDI".to_string() },
+        ]
+    );
+}
+
+#[test]
+fn test_invalid_or_mismatched_cursor_is_rejected() {
+    let conn = seeded_conn();
+
+    let result = find_jobs(&conn, 1, SearchCriteria { cursor: Some("pas-un-curseur".to_string()), ..Default::default() });
+    assert!(matches!(result, Err(AppError::Validation(_))));
+
+    let first = find_jobs(
+        &conn,
+        1,
+        SearchCriteria { sort_by: Some("salary".to_string()), limit: Some(5), ..Default::default() },
+    )
+    .unwrap();
+    let result = find_jobs(
+        &conn,
+        1,
+        SearchCriteria { sort_by: Some("date".to_string()), cursor: first.next_cursor, ..Default::default() },
+    );
+    assert!(matches!(result, Err(AppError::Validation(_))));
+}
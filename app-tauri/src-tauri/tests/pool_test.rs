@@ -0,0 +1,109 @@
+use app_lib::database::operations::DatabaseOperations;
+use app_lib::database::pool::ConnectionPool;
+use app_lib::error::AppError;
+use std::time::Duration;
+
+fn temp_db_path(dir: &tempfile::TempDir) -> String {
+    dir.path().join("jobs.db").to_string_lossy().to_string()
+}
+
+#[tokio::test]
+async fn test_connection_returns_to_pool_on_drop() {
+    let dir = tempfile::tempdir().unwrap();
+    let pool = ConnectionPool::new(&temp_db_path(&dir), 2).unwrap();
+
+    {
+        let conn = pool.get().await.unwrap();
+        conn.execute_batch("CREATE TABLE t (id INTEGER)").unwrap();
+        assert_eq!(pool.metrics().in_use, 1);
+    }
+
+    let metrics = pool.metrics();
+    assert_eq!(metrics.in_use, 0);
+    assert_eq!(metrics.idle, 1);
+    assert_eq!(metrics.total_acquired, 1);
+}
+
+#[tokio::test]
+async fn test_waiter_is_served_when_connection_is_released() {
+    let dir = tempfile::tempdir().unwrap();
+    let pool = ConnectionPool::new(&temp_db_path(&dir), 1).unwrap();
+
+    let held = pool.get().await.unwrap();
+    let waiter = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(|_| ()) })
+    };
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.metrics().waiting, 1);
+    drop(held);
+
+    // L'ancien pool gardait le verrou pendant l'attente et bloquait jusqu'au timeout
+    tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .expect("le waiter doit obtenir la connexion libérée")
+        .unwrap()
+        .unwrap();
+    assert!(pool.metrics().max_wait_ms >= 40);
+}
+
+#[tokio::test]
+async fn test_connection_is_returned_after_query_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let pool = ConnectionPool::new(&temp_db_path(&dir), 1).unwrap();
+
+    async fn failing_query(pool: &ConnectionPool) -> Result<i64, AppError> {
+        let conn = pool.get().await?;
+        let value = conn.query_row("SELECT id FROM inexistante", [], |row| row.get(0))?;
+        Ok(value)
+    }
+
+    assert!(failing_query(&pool).await.is_err());
+    assert_eq!(pool.metrics().idle, 1);
+    assert!(pool.get().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_open_transaction_is_rolled_back_on_release() {
+    let dir = tempfile::tempdir().unwrap();
+    let pool = ConnectionPool::new(&temp_db_path(&dir), 1).unwrap();
+
+    {
+        let conn = pool.get().await.unwrap();
+        conn.execute_batch("CREATE TABLE t (id INTEGER); BEGIN; INSERT INTO t VALUES (1);")
+            .unwrap();
+    }
+
+    let conn = pool.get().await.unwrap();
+    assert!(conn.is_autocommit());
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM t", [], |row| row.get(0)).unwrap();
+    assert_eq!(count, 0);
+}
+
+#[tokio::test]
+async fn test_connections_have_pragmas_applied() {
+    let dir = tempfile::tempdir().unwrap();
+    let pool = ConnectionPool::new(&temp_db_path(&dir), 1).unwrap();
+    let conn = pool.get().await.unwrap();
+
+    let journal_mode: String = conn.query_row("PRAGMA journal_mode", [], |row| row.get(0)).unwrap();
+    let foreign_keys: i64 = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0)).unwrap();
+    let busy_timeout: i64 = conn.query_row("PRAGMA busy_timeout", [], |row| row.get(0)).unwrap();
+
+    assert_eq!(journal_mode, "wal");
+    assert_eq!(foreign_keys, 1);
+    assert!(busy_timeout > 0);
+}
+
+#[tokio::test]
+async fn test_operations_init_runs_migrations() {
+    let dir = tempfile::tempdir().unwrap();
+    let operations = DatabaseOperations::new(&temp_db_path(&dir)).unwrap();
+
+    operations.init().await.unwrap();
+
+    let stats = operations.get_job_stats().await.unwrap();
+    assert_eq!(stats.total_jobs, 0);
+    assert_eq!(operations.pool_metrics().in_use, 0);
+}
@@ -0,0 +1,153 @@
+use app_lib::commands::profile::{load_user_profile, save_user_profile};
+use app_lib::database::migrations::run_migrations;
+use app_lib::error::AppError;
+use app_lib::matching::load_match_profile;
+use app_lib::models::*;
+use rusqlite::Connection;
+use std::path::PathBuf;
+
+fn migrated_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn full_profile() -> UserProfile {
+    UserProfile {
+        id: 1,
+        name: "Camille Martin".to_string(),
+        email: Some("camille@example.com".to_string()),
+        phone: Some("+33 6 12 34 56 78".to_string()),
+        locations: UserLocations {
+            primary: "Paris".to_string(),
+            secondary: Some("Lyon".to_string()),
+            coordinates: Some(LocationCoordinates {
+                primary: Coordinates { lat: 48.8566, lng: 2.3522 },
+                secondary: Some(Coordinates { lat: 45.764, lng: 4.8357 }),
+            }),
+        },
+        cv: CVInfo {
+            path: PathBuf::from("/home/camille/cv.pdf"),
+            last_updated: "2024-03-21".to_string(),
+            skills: Some(vec!["Rust".to_string(), "PostgreSQL".to_string()]),
+            experience_years: Some(5),
+            education: Some(vec![Education {
+                institution: "INSA Lyon".to_string(),
+                degree: "Ingénieur".to_string(),
+                field: "Informatique".to_string(),
+                start_year: 2014,
+                end_year: Some(2019),
+            }]),
+            certifications: Some(vec!["AWS Solutions Architect".to_string()]),
+        },
+        preferences: Some(UserPreferences {
+            notifications: Some(true),
+            dark_mode: Some(false),
+            language: Some("fr".to_string()),
+            commute_mode: Some("public_transport".to_string()),
+        }),
+        job_preferences: Some(JobPreferences {
+            min_salary: Some(50000.0),
+            preferred_job_types: Some(vec!["CDI".to_string()]),
+            remote_preference: Some("hybrid".to_string()),
+        }),
+    }
+}
+
+fn minimal_profile() -> UserProfile {
+    UserProfile {
+        id: 1,
+        name: "Camille Martin".to_string(),
+        email: None,
+        phone: None,
+        locations: UserLocations {
+            primary: "Nantes".to_string(),
+            secondary: None,
+            coordinates: None,
+        },
+        cv: CVInfo {
+            path: PathBuf::new(),
+            last_updated: String::new(),
+            skills: None,
+            experience_years: None,
+            education: None,
+            certifications: None,
+        },
+        preferences: None,
+        job_preferences: None,
+    }
+}
+
+fn assert_invalid(conn: &Connection, profile: UserProfile) {
+    assert!(matches!(save_user_profile(conn, 1, &profile), Err(AppError::Validation(_))));
+}
+
+#[test]
+fn test_full_profile_round_trip() {
+    let conn = migrated_conn();
+    let profile = full_profile();
+
+    let saved = save_user_profile(&conn, 1, &profile).unwrap();
+    assert_eq!(saved, profile);
+    assert_eq!(load_user_profile(&conn, 1).unwrap(), profile);
+}
+
+#[test]
+fn test_update_replaces_previous_profile() {
+    let conn = migrated_conn();
+    save_user_profile(&conn, 1, &full_profile()).unwrap();
+
+    let minimal = minimal_profile();
+    assert_eq!(save_user_profile(&conn, 1, &minimal).unwrap(), minimal);
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM user_profiles", [], |row| row.get(0)).unwrap();
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn test_missing_profile_is_not_found() {
+    let conn = migrated_conn();
+    assert!(matches!(load_user_profile(&conn, 1), Err(AppError::NotFound(_))));
+}
+
+#[test]
+fn test_invalid_profiles_are_rejected() {
+    let conn = migrated_conn();
+
+    let mut profile = full_profile();
+    profile.email = Some("camille.example.com".to_string());
+    assert_invalid(&conn, profile);
+
+    let mut profile = full_profile();
+    profile.phone = Some("06 12 AB".to_string());
+    assert_invalid(&conn, profile);
+
+    let mut profile = full_profile();
+    profile.locations.coordinates.as_mut().unwrap().primary.lat = 123.0;
+    assert_invalid(&conn, profile);
+
+    let mut profile = full_profile();
+    profile.cv.education.as_mut().unwrap()[0].end_year = Some(2010);
+    assert_invalid(&conn, profile);
+
+    let mut profile = full_profile();
+    profile.preferences.as_mut().unwrap().language = Some("de".to_string());
+    assert_invalid(&conn, profile);
+
+    let mut profile = full_profile();
+    profile.job_preferences.as_mut().unwrap().remote_preference = Some("sometimes".to_string());
+    assert_invalid(&conn, profile);
+
+    // Rien n'a été écrit
+    assert!(matches!(load_user_profile(&conn, 1), Err(AppError::NotFound(_))));
+}
+
+#[test]
+fn test_saved_profile_feeds_matching() {
+    let conn = migrated_conn();
+    save_user_profile(&conn, 1, &full_profile()).unwrap();
+
+    let profile = load_match_profile(&conn, 1).unwrap();
+    assert_eq!(profile.skills, vec!["Rust".to_string(), "PostgreSQL".to_string()]);
+    assert_eq!(profile.experience_years, Some(5));
+    assert_eq!(profile.job_preferences.unwrap().remote_preference.as_deref(), Some("hybrid"));
+}
@@ -0,0 +1,210 @@
+use app_lib::commands::applications::{
+    insert_application, insert_note, insert_stage, load_application, set_application_status, CreateApplicationRequest,
+};
+use app_lib::commands::profile::{
+    activate_profile, create_profile_user, last_active_user_id, list_profile_summaries, LOCAL_USER_ID,
+};
+use app_lib::commands::saved_searches::{
+    insert_saved_search, list_user_saved_searches, load_saved_search, modify_saved_search, remove_saved_search,
+    SaveSearchRequest, UpdateSavedSearchRequest,
+};
+use app_lib::commands::search::{find_jobs, SearchCriteria};
+use app_lib::database::migrations::run_migrations;
+use app_lib::database::operations::DatabaseOperations;
+use app_lib::database::pool::open_connection;
+use app_lib::error::AppError;
+use app_lib::matching::load_match_profile;
+use app_lib::models::ApplicationStatus;
+use rusqlite::{params, Connection};
+
+fn migrated_conn(dir: &tempfile::TempDir) -> Connection {
+    let conn = open_connection(&dir.path().join("jobs.db").to_string_lossy()).unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn insert_job(conn: &Connection, title: &str, location: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO jobs (title, company, location) VALUES (?, 'Acme', ?)",
+        params![title, location],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+fn application(job_id: i64) -> CreateApplicationRequest {
+    CreateApplicationRequest {
+        job_id,
+        status: ApplicationStatus::Applied,
+        notes: None,
+    }
+}
+
+#[test]
+fn test_create_and_switch_profiles() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    assert_eq!(last_active_user_id(&conn).unwrap(), LOCAL_USER_ID);
+
+    let alex = create_profile_user(&conn, "  Alex  ", None).unwrap();
+    assert_eq!(alex.name, "Alex");
+    assert!(!alex.has_profile);
+    let sam = create_profile_user(&conn, "Sam", Some("sam@example.com")).unwrap();
+    assert_ne!(alex.email, sam.email);
+
+    assert!(matches!(create_profile_user(&conn, " ", None), Err(AppError::Validation(_))));
+    assert!(matches!(
+        create_profile_user(&conn, "Sam bis", Some("sam@example.com")),
+        Err(AppError::Validation(_))
+    ));
+
+    let profiles = list_profile_summaries(&conn).unwrap();
+    let ids: Vec<i64> = profiles.iter().map(|profile| profile.user_id).collect();
+    assert_eq!(ids, vec![LOCAL_USER_ID, alex.user_id, sam.user_id]);
+
+    // Le dernier profil activé est rouvert au démarrage
+    activate_profile(&conn, sam.user_id, "2024-05-01 09:00:00").unwrap();
+    activate_profile(&conn, alex.user_id, "2024-05-02 09:00:00").unwrap();
+    assert_eq!(last_active_user_id(&conn).unwrap(), alex.user_id);
+    assert!(matches!(
+        activate_profile(&conn, 999, "2024-05-03 09:00:00"),
+        Err(AppError::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_profiles_cannot_read_each_others_applications() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    let job_id = insert_job(&conn, "Dev Rust", "Paris");
+
+    let mine = insert_application(&conn, LOCAL_USER_ID, &application(job_id)).unwrap();
+    let theirs = insert_application(&conn, other, &application(job_id)).unwrap();
+    assert_eq!(theirs.user_id, other);
+
+    assert!(matches!(load_application(&conn, other, mine.id), Err(AppError::NotFound(_))));
+    assert!(matches!(
+        set_application_status(&conn, other, mine.id, ApplicationStatus::Rejected, None, "2024-05-01 09:00:00"),
+        Err(AppError::NotFound(_))
+    ));
+    assert!(matches!(insert_note(&conn, other, mine.id, "Relancer"), Err(AppError::NotFound(_))));
+    assert!(matches!(insert_stage(&conn, other, mine.id, "interview", None), Err(AppError::NotFound(_))));
+
+    // Rien n'a changé pour le propriétaire
+    let loaded = load_application(&conn, LOCAL_USER_ID, mine.id).unwrap();
+    assert_eq!(loaded.status, ApplicationStatus::Applied);
+    assert!(loaded.application_notes.unwrap().is_empty());
+    assert!(loaded.stages.unwrap().is_empty());
+
+    insert_note(&conn, other, theirs.id, "Relancer").unwrap();
+    assert_eq!(load_application(&conn, other, theirs.id).unwrap().application_notes.unwrap().len(), 1);
+}
+
+#[test]
+fn test_saved_searches_are_scoped_by_profile() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+
+    let saved = insert_saved_search(
+        &conn,
+        LOCAL_USER_ID,
+        SaveSearchRequest {
+            name: "Rust".to_string(),
+            criteria: SearchCriteria { keywords: "rust".to_string(), ..Default::default() },
+            interval_minutes: None,
+        },
+    )
+    .unwrap();
+
+    assert!(list_user_saved_searches(&conn, other).unwrap().is_empty());
+    assert!(matches!(load_saved_search(&conn, other, saved.id), Err(AppError::NotFound(_))));
+    let rename = UpdateSavedSearchRequest { name: Some("Piratée".to_string()), ..Default::default() };
+    assert!(matches!(modify_saved_search(&conn, other, saved.id, rename), Err(AppError::NotFound(_))));
+    assert!(!remove_saved_search(&conn, other, saved.id).unwrap());
+
+    assert_eq!(load_saved_search(&conn, LOCAL_USER_ID, saved.id).unwrap().name, "Rust");
+}
+
+#[test]
+fn test_radius_search_uses_the_profile_home() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    for (user_id, home) in [(LOCAL_USER_ID, "Paris"), (other, "Lyon")] {
+        conn.execute(
+            "INSERT INTO user_profiles (user_id, name, primary_home) VALUES (?, 'Profil', ?)",
+            params![user_id, home],
+        )
+        .unwrap();
+    }
+    insert_job(&conn, "Poste parisien", "Paris");
+    insert_job(&conn, "Poste lyonnais", "Lyon");
+    app_lib::geo::geocode_missing_jobs(&conn).unwrap();
+
+    let titles = |user_id: i64| -> Vec<String> {
+        let criteria = SearchCriteria { radius_km: Some(30.0), ..Default::default() };
+        find_jobs(&conn, user_id, criteria).unwrap().jobs.into_iter().map(|job| job.title).collect()
+    };
+    assert_eq!(titles(LOCAL_USER_ID), vec!["Poste parisien"]);
+    assert_eq!(titles(other), vec!["Poste lyonnais"]);
+}
+
+#[tokio::test]
+async fn test_job_lists_use_the_profile_scores() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    let rust = insert_job(&conn, "Dev Rust", "Paris");
+    let python = insert_job(&conn, "Dev Python", "Paris");
+    for (user_id, job_id, score) in [(LOCAL_USER_ID, rust, 0.9), (other, python, 0.8), (other, rust, 0.1)] {
+        conn.execute(
+            "INSERT INTO job_match_scores (user_id, job_id, score, breakdown, computed_at, profile_version)
+             VALUES (?, ?, ?, '{}', '2024-05-01 09:00:00', 'v1')",
+            params![user_id, job_id, score],
+        )
+        .unwrap();
+    }
+
+    let operations = DatabaseOperations::new(&dir.path().join("jobs.db").to_string_lossy()).unwrap();
+    let mine = operations.get_jobs(LOCAL_USER_ID).await.unwrap();
+    assert_eq!(mine[0].id, rust);
+    assert_eq!(mine[0].matching_score, 0.9);
+    assert_eq!(mine[1].matching_score, 0.0);
+
+    // Résultats mis en cache séparément pour chaque profil
+    let theirs = operations.search_jobs(other, Some("Dev"), None, 50, 0).await.unwrap();
+    assert_eq!(theirs.iter().map(|job| job.id).collect::<Vec<_>>(), vec![python, rust]);
+    assert_eq!(theirs[1].matching_score, 0.1);
+    let mine = operations.search_jobs(LOCAL_USER_ID, Some("Dev"), None, 50, 0).await.unwrap();
+    assert_eq!(mine[0].id, rust);
+}
+
+#[test]
+fn test_weighted_keywords_belong_to_their_profile() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = migrated_conn(&dir);
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    // Préférence créée avant la migration : rattachée au profil local
+    conn.execute_batch(
+        "INSERT INTO search_preferences (id, name, is_active) VALUES (1, 'Rust', 1);
+         INSERT INTO search_categories (id, preference_id, name) VALUES (1, 1, 'Langages');
+         INSERT INTO keywords (category_id, keyword, weight) VALUES (1, 'rust', 3);",
+    )
+    .unwrap();
+    conn.execute("INSERT INTO search_preferences (id, name, is_active, user_id) VALUES (2, 'Data', 1, ?)", [other])
+        .unwrap();
+    conn.execute_batch(
+        "INSERT INTO search_categories (id, preference_id, name) VALUES (2, 2, 'Outils');
+         INSERT INTO keywords (category_id, keyword, weight) VALUES (2, 'python', 2);",
+    )
+    .unwrap();
+
+    let keywords = |user_id| -> Vec<String> {
+        let profile = load_match_profile(&conn, user_id).unwrap();
+        profile.keywords.into_iter().map(|keyword| keyword.keyword).collect()
+    };
+    assert_eq!(keywords(LOCAL_USER_ID), vec!["rust"]);
+    assert_eq!(keywords(other), vec!["python"]);
+}
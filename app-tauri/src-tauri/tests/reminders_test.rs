@@ -0,0 +1,161 @@
+use app_lib::commands::applications::{
+    insert_application, insert_stage, modify_stage, set_application_status, CreateApplicationRequest,
+    UpdateStageRequest,
+};
+use app_lib::commands::profile::{create_profile_user, LOCAL_USER_ID};
+use app_lib::commands::reminders::{
+    discard_reminder, list_user_reminders, postpone_reminder, snooze_until, sync_reminders, take_due_reminders,
+    ReminderKind, ReminderStatus, MAX_SNOOZE_MINUTES,
+};
+use app_lib::database::migrations::run_migrations;
+use app_lib::database::pool::open_connection;
+use app_lib::error::AppError;
+use app_lib::models::ApplicationStatus;
+use chrono::{TimeZone, Utc};
+use rusqlite::{params, Connection};
+
+fn open(dir: &tempfile::TempDir) -> Connection {
+    let conn = open_connection(&dir.path().join("jobs.db").to_string_lossy()).unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn insert_job(conn: &Connection, company: &str) -> i64 {
+    conn.execute("INSERT INTO jobs (title, company) VALUES ('Dev Rust', ?)", [company]).unwrap();
+    conn.last_insert_rowid()
+}
+
+fn applied_on(conn: &Connection, user_id: i64, company: &str, applied_at: &str) -> i64 {
+    let request = CreateApplicationRequest {
+        job_id: insert_job(conn, company),
+        status: ApplicationStatus::Applied,
+        notes: None,
+    };
+    let id = insert_application(conn, user_id, &request).unwrap().id;
+    conn.execute("UPDATE applications SET applied_at = ? WHERE id = ?", params![applied_at, id]).unwrap();
+    id
+}
+
+#[test]
+fn test_due_reminders_follow_dates_and_rules() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let silent = applied_on(&conn, LOCAL_USER_ID, "Acme", "2024-04-20 10:00:00");
+    let answered = applied_on(&conn, LOCAL_USER_ID, "Globex", "2024-04-20 10:00:00");
+    set_application_status(&conn, LOCAL_USER_ID, answered, ApplicationStatus::Interview, None, "2024-04-22 10:00:00")
+        .unwrap();
+    insert_stage(&conn, LOCAL_USER_ID, answered, "entretien", Some("2024-05-02 14:00:00")).unwrap();
+
+    conn.execute("INSERT INTO kanban_columns (name, position) VALUES ('À relancer', 0)", []).unwrap();
+    let column_id = conn.last_insert_rowid();
+    conn.execute(
+        "INSERT INTO kanban_cards (job_id, column_id, position, follow_up_date) VALUES (?, ?, 0, '2024-05-10')",
+        params![insert_job(&conn, "Initech"), column_id],
+    )
+    .unwrap();
+
+    // Sans réponse au bout de 7 jours ; l'entretien est rappelé la veille
+    let due = take_due_reminders(&conn, LOCAL_USER_ID, "2024-05-01 15:00:00").unwrap();
+    let kinds: Vec<_> = due.iter().map(|reminder| (reminder.kind, reminder.due_at.as_str())).collect();
+    assert_eq!(
+        kinds,
+        vec![(ReminderKind::NoResponse, "2024-04-27 10:00:00"), (ReminderKind::Stage, "2024-05-01 14:00:00")]
+    );
+    assert_eq!(due[0].source_id, silent);
+    assert!(due[0].title.contains("Acme"));
+    assert!(due.iter().all(|reminder| reminder.status == ReminderStatus::Notified));
+
+    // Déjà notifiés : rien de plus avant la relance de la carte
+    assert!(take_due_reminders(&conn, LOCAL_USER_ID, "2024-05-09 00:00:00").unwrap().is_empty());
+    let due = take_due_reminders(&conn, LOCAL_USER_ID, "2024-05-10 00:00:00").unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].kind, ReminderKind::FollowUp);
+    assert_eq!(list_user_reminders(&conn, LOCAL_USER_ID).unwrap().len(), 3);
+}
+
+#[test]
+fn test_snooze_and_dismiss_survive_restarts() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let first = applied_on(&conn, LOCAL_USER_ID, "Acme", "2024-04-20 10:00:00");
+    applied_on(&conn, LOCAL_USER_ID, "Globex", "2024-04-20 10:00:00");
+    let due = take_due_reminders(&conn, LOCAL_USER_ID, "2024-05-01 09:00:00").unwrap();
+    assert_eq!(due.len(), 2);
+    let (snoozed, dismissed) = if due[0].source_id == first { (&due[0], &due[1]) } else { (&due[1], &due[0]) };
+
+    let now = "2024-05-01 09:05:00";
+    assert!(matches!(
+        postpone_reminder(&conn, LOCAL_USER_ID, snoozed.id, "2024-05-01 09:00:00", now),
+        Err(AppError::Validation(_))
+    ));
+    let postponed = postpone_reminder(&conn, LOCAL_USER_ID, snoozed.id, "2024-05-02 09:00:00", now).unwrap();
+    assert_eq!(postponed.status, ReminderStatus::Snoozed);
+    discard_reminder(&conn, LOCAL_USER_ID, dismissed.id).unwrap();
+    drop(conn);
+
+    // L'état est relu depuis la base après redémarrage
+    let conn = open(&dir);
+    let remaining = list_user_reminders(&conn, LOCAL_USER_ID).unwrap();
+    assert_eq!(remaining.iter().map(|reminder| reminder.id).collect::<Vec<_>>(), vec![snoozed.id]);
+    assert!(take_due_reminders(&conn, LOCAL_USER_ID, "2024-05-01 18:00:00").unwrap().is_empty());
+    let due = take_due_reminders(&conn, LOCAL_USER_ID, "2024-05-02 09:00:00").unwrap();
+    assert_eq!(due.iter().map(|reminder| reminder.id).collect::<Vec<_>>(), vec![snoozed.id]);
+    assert!(take_due_reminders(&conn, LOCAL_USER_ID, "2024-06-01 09:00:00").unwrap().is_empty());
+}
+
+#[test]
+fn test_snooze_duration_is_bounded() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap();
+    assert_eq!(snooze_until(now, 90).unwrap(), "2024-05-01 10:30:00");
+    assert_eq!(snooze_until(now, MAX_SNOOZE_MINUTES).unwrap(), "2025-05-01 09:00:00");
+
+    // Une durée démesurée est refusée au lieu de faire paniquer la commande
+    for minutes in [0, -5, MAX_SNOOZE_MINUTES + 1, i64::MAX] {
+        assert!(matches!(snooze_until(now, minutes), Err(AppError::Validation(_))));
+    }
+}
+
+#[test]
+fn test_reminders_disappear_with_their_reason() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let id = applied_on(&conn, LOCAL_USER_ID, "Acme", "2024-04-20 10:00:00");
+    let stage = insert_stage(&conn, LOCAL_USER_ID, id, "entretien", Some("2024-05-02 14:00:00")).unwrap();
+    sync_reminders(&conn).unwrap();
+    assert_eq!(list_user_reminders(&conn, LOCAL_USER_ID).unwrap().len(), 2);
+
+    // Une réponse supprime la relance, une étape déplacée change l'échéance
+    set_application_status(&conn, LOCAL_USER_ID, id, ApplicationStatus::Interview, None, "2024-04-25 10:00:00")
+        .unwrap();
+    let moved = UpdateStageRequest { scheduled_at: Some(Some("2024-05-06 14:00:00".to_string())), ..Default::default() };
+    modify_stage(&conn, LOCAL_USER_ID, stage.id, moved).unwrap();
+    sync_reminders(&conn).unwrap();
+    let reminders = list_user_reminders(&conn, LOCAL_USER_ID).unwrap();
+    assert_eq!(reminders.len(), 1);
+    assert_eq!(reminders[0].kind, ReminderKind::Stage);
+    assert_eq!(reminders[0].due_at, "2024-05-05 14:00:00");
+
+    // Une étape dont la date est effacée n'a plus de rappel
+    let unscheduled = UpdateStageRequest { scheduled_at: Some(None), ..Default::default() };
+    modify_stage(&conn, LOCAL_USER_ID, stage.id, unscheduled).unwrap();
+    sync_reminders(&conn).unwrap();
+    assert!(list_user_reminders(&conn, LOCAL_USER_ID).unwrap().is_empty());
+}
+
+#[test]
+fn test_reminders_are_scoped_by_profile() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    applied_on(&conn, other, "Acme", "2024-04-20 10:00:00");
+
+    // Le rappel d'un profil inactif n'est pas consommé par le profil actif
+    assert!(take_due_reminders(&conn, LOCAL_USER_ID, "2024-05-01 09:00:00").unwrap().is_empty());
+    assert_eq!(list_user_reminders(&conn, other).unwrap()[0].status, ReminderStatus::Pending);
+    let due = take_due_reminders(&conn, other, "2024-05-01 09:00:00").unwrap();
+    assert_eq!(due[0].user_id, other);
+
+    assert!(list_user_reminders(&conn, LOCAL_USER_ID).unwrap().is_empty());
+    assert!(matches!(discard_reminder(&conn, LOCAL_USER_ID, due[0].id), Err(AppError::NotFound(_))));
+    assert_eq!(list_user_reminders(&conn, other).unwrap().len(), 1);
+}
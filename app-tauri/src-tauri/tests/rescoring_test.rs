@@ -0,0 +1,142 @@
+use app_lib::database::executor::Db;
+use app_lib::database::migrations::run_migrations;
+use app_lib::database::pool::open_connection;
+use app_lib::matching::{load_match_explanation, load_match_profile, profile_version, stale_job_ids};
+use app_lib::rescoring::{rescore_stale_jobs, RescoreProgress, Rescorer};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+fn seeded_db(dir: &tempfile::TempDir, jobs: usize) -> Db {
+    let path = dir.path().join("jobs.db").to_string_lossy().to_string();
+    let conn = open_connection(&path).unwrap();
+    run_migrations(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO user_profiles (user_id, name, primary_home, skills) VALUES (1, 'Camille', 'Lyon', '[\"Rust\"]')",
+        [],
+    )
+    .unwrap();
+    for i in 0..jobs {
+        conn.execute(
+            "INSERT INTO jobs (title, company, skills) VALUES (?, 'Acme', '[\"Rust\", \"Go\"]')",
+            [format!("Offre {}", i)],
+        )
+        .unwrap();
+    }
+    Db::open(&path).unwrap()
+}
+
+async fn stale_count(db: &Db) -> usize {
+    db.read(|conn| {
+        let version = profile_version(&load_match_profile(conn, 1)?);
+        Ok(stale_job_ids(conn, 1, &version)?.len())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_rescoring_runs_in_chunks_and_reports_progress() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(&dir, 5);
+    assert_eq!(stale_count(&db).await, 5);
+
+    let mut events: Vec<RescoreProgress> = Vec::new();
+    let result = rescore_stale_jobs(&db, 1, 2, &AtomicBool::new(false), |p| events.push(p.clone()))
+        .await
+        .unwrap();
+
+    assert!(result.done && !result.cancelled);
+    assert_eq!((result.processed, result.total), (5, 5));
+    let processed: Vec<usize> = events.iter().map(|p| p.processed).collect();
+    assert_eq!(processed, vec![2, 4, 5, 5]);
+    assert!(events.last().unwrap().done);
+    assert_eq!(stale_count(&db).await, 0);
+
+    let score: f64 = db
+        .read(|conn| {
+            let sql = "SELECT score FROM job_match_scores WHERE user_id = 1 AND job_id = 1";
+            Ok(conn.query_row(sql, [], |row| row.get(0))?)
+        })
+        .await
+        .unwrap();
+    assert_eq!(score, 0.5);
+
+    // Rien n'est périmé : le recalcul suivant ne touche aucune offre
+    let again = rescore_stale_jobs(&db, 1, 2, &AtomicBool::new(false), |_| {}).await.unwrap();
+    assert_eq!((again.total, again.processed), (0, 0));
+    assert!(again.done);
+}
+
+#[tokio::test]
+async fn test_profile_change_marks_scores_stale() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(&dir, 3);
+    let first = rescore_stale_jobs(&db, 1, 10, &AtomicBool::new(false), |_| {}).await.unwrap();
+
+    db.call(|conn| {
+        conn.execute("UPDATE user_profiles SET skills = '[\"Rust\", \"Go\"]' WHERE user_id = 1", [])?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+    assert_eq!(stale_count(&db).await, 3);
+
+    let explanation = db.call(|conn| load_match_explanation(conn, 1, 1, "maintenant")).await.unwrap();
+    assert!(explanation.stale);
+    assert_eq!(explanation.profile_version, first.profile_version);
+
+    let second = rescore_stale_jobs(&db, 1, 10, &AtomicBool::new(false), |_| {}).await.unwrap();
+    assert_ne!(second.profile_version, first.profile_version);
+    assert_eq!(second.processed, 3);
+
+    let explanation = db.call(|conn| load_match_explanation(conn, 1, 1, "maintenant")).await.unwrap();
+    assert!(!explanation.stale);
+    assert_eq!(explanation.score, 1.0);
+}
+
+#[tokio::test]
+async fn test_updated_job_is_rescored() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(&dir, 3);
+    rescore_stale_jobs(&db, 1, 10, &AtomicBool::new(false), |_| {}).await.unwrap();
+
+    // La collecte a ajouté une compétence à l'offre après le calcul de son score
+    db.call(|conn| {
+        conn.execute(
+            "UPDATE jobs SET skills = '[\"Rust\"]', updated_at = datetime('now', '+1 minute') WHERE id = 2",
+            [],
+        )?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+    assert_eq!(stale_count(&db).await, 1);
+    let explanation = db.call(|conn| load_match_explanation(conn, 1, 2, "maintenant")).await.unwrap();
+    assert!(explanation.stale);
+
+    let again = rescore_stale_jobs(&db, 1, 10, &AtomicBool::new(false), |_| {}).await.unwrap();
+    assert_eq!(again.processed, 1);
+    let explanation = db.call(|conn| load_match_explanation(conn, 1, 2, "maintenant")).await.unwrap();
+    assert_eq!(explanation.score, 1.0);
+}
+
+#[tokio::test]
+async fn test_cancelled_rescoring_stops_between_chunks() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(&dir, 5);
+    let cancel = AtomicBool::new(false);
+
+    let result = rescore_stale_jobs(&db, 1, 2, &cancel, |_| cancel.store(true, Ordering::SeqCst))
+        .await
+        .unwrap();
+    assert!(result.cancelled && !result.done);
+    assert_eq!(result.processed, 2);
+    // Les offres restantes seront reprises au prochain recalcul
+    assert_eq!(stale_count(&db).await, 3);
+}
+
+#[test]
+fn test_cancel_without_running_rescoring() {
+    let rescorer = Rescorer::default();
+    assert!(!rescorer.is_running());
+    assert!(!rescorer.cancel());
+}
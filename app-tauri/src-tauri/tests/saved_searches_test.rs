@@ -0,0 +1,234 @@
+use app_lib::commands::saved_searches::{
+    due_saved_searches, execute_saved_search, insert_saved_search, list_user_saved_searches, modify_saved_search,
+    remove_saved_search, SaveSearchRequest, UpdateSavedSearchRequest, MAX_TRACKED_RESULTS,
+};
+use app_lib::commands::profile::create_profile_user;
+use app_lib::commands::search::SearchCriteria;
+use app_lib::database::migrations::run_migrations;
+use app_lib::error::AppError;
+use rusqlite::Connection;
+
+fn migrated_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn insert_job(conn: &Connection, title: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO jobs (title, company, posted_at) VALUES (?, 'Acme', '2024-01-01')",
+        [title],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+fn save_rust_search(conn: &Connection, interval_minutes: Option<i64>) -> i64 {
+    insert_saved_search(
+        conn,
+        1,
+        SaveSearchRequest {
+            name: "Rust".to_string(),
+            criteria: SearchCriteria {
+                keywords: "rust".to_string(),
+                limit: Some(5),
+                ..Default::default()
+            },
+            interval_minutes,
+        },
+    )
+    .unwrap()
+    .id
+}
+
+#[test]
+fn test_saved_search_round_trip() {
+    let conn = migrated_conn();
+    let id = save_rust_search(&conn, None);
+
+    let searches = list_user_saved_searches(&conn, 1).unwrap();
+    assert_eq!(searches.len(), 1);
+    assert_eq!(searches[0].id, id);
+    assert_eq!(searches[0].criteria.keywords, "rust");
+    // La pagination n'est pas conservée
+    assert_eq!(searches[0].criteria.limit, None);
+    assert_eq!(searches[0].interval_minutes, 60);
+    assert!(searches[0].is_active);
+
+    let updated = modify_saved_search(
+        &conn,
+        1,
+        id,
+        UpdateSavedSearchRequest {
+            is_active: Some(false),
+            interval_minutes: Some(15),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(!updated.is_active);
+    assert_eq!(updated.interval_minutes, 15);
+    assert_eq!(updated.name, "Rust");
+
+    assert!(remove_saved_search(&conn, 1, id).unwrap());
+    assert!(list_user_saved_searches(&conn, 1).unwrap().is_empty());
+}
+
+#[test]
+fn test_invalid_saved_search_is_rejected() {
+    let conn = migrated_conn();
+    let result = insert_saved_search(
+        &conn,
+        1,
+        SaveSearchRequest {
+            name: "  ".to_string(),
+            criteria: SearchCriteria::default(),
+            interval_minutes: None,
+        },
+    );
+    assert!(matches!(result, Err(AppError::Validation(_))));
+
+    let id = save_rust_search(&conn, None);
+    let result = modify_saved_search(
+        &conn,
+        1,
+        id,
+        UpdateSavedSearchRequest { interval_minutes: Some(0), ..Default::default() },
+    );
+    assert!(matches!(result, Err(AppError::Validation(_))));
+}
+
+#[test]
+fn test_runs_report_new_changed_and_removed_jobs() {
+    let conn = migrated_conn();
+    insert_job(&conn, "Développeur Rust");
+    let edited = insert_job(&conn, "Lead Rust");
+    let dropped = insert_job(&conn, "Stage Rust");
+    insert_job(&conn, "Développeur Java");
+    let id = save_rust_search(&conn, None);
+
+    let first = execute_saved_search(&conn, id, "2024-01-01 08:00:00").unwrap();
+    assert!(first.first_run);
+    assert_eq!(first.new_jobs.len(), 3);
+
+    let added = insert_job(&conn, "Architecte Rust");
+    conn.execute("UPDATE jobs SET location = 'Lyon' WHERE id = ?", [edited]).unwrap();
+    conn.execute("UPDATE jobs SET title = 'Stage Go' WHERE id = ?", [dropped]).unwrap();
+
+    let second = execute_saved_search(&conn, id, "2024-01-01 09:00:00").unwrap();
+    assert!(!second.first_run);
+    assert_eq!(second.total, 3);
+    assert_eq!(second.new_jobs.iter().map(|j| j.id).collect::<Vec<_>>(), vec![added]);
+    assert_eq!(second.changed_jobs.iter().map(|j| j.id).collect::<Vec<_>>(), vec![edited]);
+    assert_eq!(second.removed_job_ids, vec![dropped]);
+
+    // Sans modification, l'exécution suivante ne signale rien
+    let third = execute_saved_search(&conn, id, "2024-01-01 10:00:00").unwrap();
+    assert!(!third.has_changes());
+    assert_eq!(third.total, 3);
+}
+
+#[test]
+fn test_runs_follow_every_page_of_results() {
+    let conn = migrated_conn();
+    for i in 0..12 {
+        insert_job(&conn, &format!("Rust {}", i));
+    }
+    // Les critères enregistrés demandaient des pages de 5 offres
+    let id = save_rust_search(&conn, None);
+
+    let run = execute_saved_search(&conn, id, "2024-01-01 08:00:00").unwrap();
+    assert_eq!(run.total, 12);
+    assert_eq!(run.new_jobs.len(), 12);
+}
+
+#[test]
+fn test_jobs_beyond_the_tracking_limit_are_not_removed() {
+    let conn = migrated_conn();
+    for i in 0..MAX_TRACKED_RESULTS {
+        insert_job(&conn, &format!("Dev {}", i));
+    }
+    let request = SaveSearchRequest {
+        name: "Toutes".to_string(),
+        criteria: SearchCriteria::default(),
+        interval_minutes: None,
+    };
+    let id = insert_saved_search(&conn, 1, request).unwrap().id;
+    execute_saved_search(&conn, id, "2024-01-01 08:00:00").unwrap();
+
+    // Une offre plus récente repousse la plus ancienne hors des résultats suivis
+    conn.execute("INSERT INTO jobs (title, company, posted_at) VALUES ('Dev', 'Acme', '2024-02-01')", [])
+        .unwrap();
+    let run = execute_saved_search(&conn, id, "2024-02-01 08:00:00").unwrap();
+    assert_eq!(run.total, MAX_TRACKED_RESULTS + 1);
+    assert_eq!(run.new_jobs.len(), 1);
+    assert!(run.removed_job_ids.is_empty());
+}
+
+#[test]
+fn test_changing_criteria_resets_the_baseline() {
+    let conn = migrated_conn();
+    insert_job(&conn, "Développeur Rust");
+    let id = save_rust_search(&conn, None);
+    execute_saved_search(&conn, id, "2024-01-01 08:00:00").unwrap();
+
+    modify_saved_search(
+        &conn,
+        1,
+        id,
+        UpdateSavedSearchRequest {
+            criteria: Some(SearchCriteria { keywords: "développeur".to_string(), ..Default::default() }),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let run = execute_saved_search(&conn, id, "2024-01-01 09:00:00").unwrap();
+    assert!(run.first_run);
+    assert_eq!(run.new_jobs.len(), 1);
+}
+
+#[test]
+fn test_due_searches_respect_interval_and_active_flag() {
+    let conn = migrated_conn();
+    let hourly = save_rust_search(&conn, Some(60));
+    let quarterly = save_rust_search(&conn, Some(15));
+    let paused = save_rust_search(&conn, Some(15));
+    modify_saved_search(
+        &conn,
+        1,
+        paused,
+        UpdateSavedSearchRequest { is_active: Some(false), ..Default::default() },
+    )
+    .unwrap();
+
+    // Jamais exécutées : toutes les recherches actives sont dues
+    assert_eq!(due_saved_searches(&conn, 1, "2024-01-01 08:00:00").unwrap(), vec![hourly, quarterly]);
+
+    execute_saved_search(&conn, hourly, "2024-01-01 08:00:00").unwrap();
+    execute_saved_search(&conn, quarterly, "2024-01-01 08:00:00").unwrap();
+
+    assert!(due_saved_searches(&conn, 1, "2024-01-01 08:10:00").unwrap().is_empty());
+    assert_eq!(due_saved_searches(&conn, 1, "2024-01-01 08:20:00").unwrap(), vec![quarterly]);
+    assert_eq!(due_saved_searches(&conn, 1, "2024-01-01 09:00:00").unwrap(), vec![hourly, quarterly]);
+}
+
+#[test]
+fn test_due_searches_are_scoped_by_profile() {
+    let conn = migrated_conn();
+    let mine = save_rust_search(&conn, Some(60));
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    let request = SaveSearchRequest {
+        name: "Go".to_string(),
+        criteria: SearchCriteria { keywords: "go".to_string(), ..Default::default() },
+        interval_minutes: Some(60),
+    };
+    let theirs = insert_saved_search(&conn, other, request).unwrap().id;
+
+    // La recherche d'un profil inactif reste due jusqu'à son activation
+    assert_eq!(due_saved_searches(&conn, 1, "2024-01-01 08:00:00").unwrap(), vec![mine]);
+    let run = execute_saved_search(&conn, mine, "2024-01-01 08:00:00").unwrap();
+    assert_eq!(run.user_id, 1);
+    assert_eq!(due_saved_searches(&conn, other, "2024-01-01 08:00:00").unwrap(), vec![theirs]);
+    assert_eq!(execute_saved_search(&conn, theirs, "2024-01-01 08:00:00").unwrap().user_id, other);
+}
@@ -0,0 +1,83 @@
+use app_lib::commands::profile::{create_profile_user, LOCAL_USER_ID};
+use app_lib::commands::search_preferences::{load_search_preferences, save_search_preference};
+use app_lib::database::migrations::run_migrations;
+use app_lib::database::pool::open_connection;
+use app_lib::error::AppError;
+use app_lib::matching::{load_match_profile, profile_version};
+use app_lib::models::{KeywordWeight, SearchCategory, SearchPreference};
+use rusqlite::Connection;
+
+fn open(dir: &tempfile::TempDir) -> Connection {
+    let conn = open_connection(&dir.path().join("jobs.db").to_string_lossy()).unwrap();
+    run_migrations(&conn).unwrap();
+    conn
+}
+
+fn keyword(keyword: &str, weight: i32) -> KeywordWeight {
+    KeywordWeight { keyword: keyword.to_string(), weight, required: None }
+}
+
+fn preference(id: i64, keywords: Vec<KeywordWeight>) -> SearchPreference {
+    SearchPreference {
+        id,
+        name: " Backend ".to_string(),
+        is_active: true,
+        categories: vec![SearchCategory { id: 0, name: "Langages".to_string(), keywords, priority: Some(5) }],
+        last_used: None,
+    }
+}
+
+#[test]
+fn test_preferences_round_trip_and_replace_keywords() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let id = save_search_preference(&conn, LOCAL_USER_ID, &preference(0, vec![keyword("rust", 3)])).unwrap();
+    let before = profile_version(&load_match_profile(&conn, LOCAL_USER_ID).unwrap());
+
+    let saved = load_search_preferences(&conn, LOCAL_USER_ID).unwrap();
+    assert_eq!(saved.len(), 1);
+    assert_eq!(saved[0].id, id);
+    assert_eq!(saved[0].name, "Backend");
+    assert_eq!(saved[0].categories[0].priority, Some(5));
+    assert_eq!(saved[0].categories[0].keywords[0].keyword, "rust");
+
+    // Une mise à jour remplace catégories et mots-clés, et périme les scores
+    save_search_preference(&conn, LOCAL_USER_ID, &preference(id, vec![keyword("go", 2), keyword("kafka", 0)])).unwrap();
+    let saved = load_search_preferences(&conn, LOCAL_USER_ID).unwrap();
+    assert_eq!(saved.len(), 1);
+    let keywords: Vec<_> = saved[0].categories[0].keywords.iter().map(|k| k.keyword.as_str()).collect();
+    assert_eq!(keywords, vec!["go", "kafka"]);
+    assert_eq!(saved[0].categories[0].keywords[1].required, Some(false));
+    assert_ne!(profile_version(&load_match_profile(&conn, LOCAL_USER_ID).unwrap()), before);
+}
+
+#[test]
+fn test_preferences_are_scoped_by_profile() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let other = create_profile_user(&conn, "Alex", None).unwrap().user_id;
+    let id = save_search_preference(&conn, LOCAL_USER_ID, &preference(0, vec![keyword("rust", 3)])).unwrap();
+
+    assert!(load_search_preferences(&conn, other).unwrap().is_empty());
+    assert!(load_match_profile(&conn, other).unwrap().keywords.is_empty());
+    assert!(matches!(
+        save_search_preference(&conn, other, &preference(id, vec![])),
+        Err(AppError::NotFound(_))
+    ));
+    assert_eq!(load_search_preferences(&conn, LOCAL_USER_ID).unwrap()[0].categories[0].keywords.len(), 1);
+}
+
+#[test]
+fn test_invalid_preferences_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = open(&dir);
+    let mut unnamed = preference(0, vec![]);
+    unnamed.name = "  ".to_string();
+    let mut priority = preference(0, vec![]);
+    priority.categories[0].priority = Some(11);
+
+    for invalid in [unnamed, priority, preference(0, vec![keyword(" ", 1)]), preference(0, vec![keyword("rust", -1)])] {
+        assert!(matches!(save_search_preference(&conn, LOCAL_USER_ID, &invalid), Err(AppError::Validation(_))));
+    }
+    assert!(load_search_preferences(&conn, LOCAL_USER_ID).unwrap().is_empty());
+}
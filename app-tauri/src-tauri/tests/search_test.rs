@@ -1,35 +1,15 @@
-use crate::commands::search::{search_jobs, SearchCriteria};
-use crate::AppState;
+use app_lib::commands::search::{find_job, find_jobs, SearchCriteria};
+use app_lib::database::fts::parse_keywords;
+use app_lib::database::migrations::run_migrations;
 use rusqlite::Connection;
-use std::sync::Arc;
-use tokio::sync::Mutex;
 
-#[tokio::test]
-async fn test_search_jobs() {
+#[test]
+fn test_search_jobs() {
     // Créer une connexion à la base de données en mémoire
     let conn = Connection::open_in_memory().unwrap();
     
-    // Créer la table jobs
-    conn.execute(
-        "CREATE TABLE jobs (
-            id INTEGER PRIMARY KEY,
-            title TEXT NOT NULL,
-            company TEXT NOT NULL,
-            location TEXT NOT NULL,
-            job_type TEXT NOT NULL,
-            salary_min INTEGER,
-            salary_max INTEGER,
-            description TEXT NOT NULL,
-            url TEXT NOT NULL,
-            posted_at TEXT NOT NULL,
-            experience_level TEXT NOT NULL,
-            skills TEXT NOT NULL,
-            remote BOOLEAN NOT NULL,
-            source TEXT NOT NULL
-        )",
-        [],
-    )
-    .unwrap();
+    // Créer le schéma complet (dont l'index plein texte)
+    run_migrations(&conn).unwrap();
 
     // Insérer des données de test
     conn.execute(
@@ -55,11 +35,6 @@ async fn test_search_jobs() {
     )
     .unwrap();
 
-    // Créer l'état de l'application
-    let state = AppState {
-        db: Arc::new(Mutex::new(Some(conn))),
-    };
-
     // Test 1: Recherche par mot-clé
     let criteria = SearchCriteria {
         keywords: "React".to_string(),
@@ -72,9 +47,10 @@ async fn test_search_jobs() {
         skills: vec![],
         date_posted: None,
         sort_by: None,
+        ..Default::default()
     };
 
-    let results = search_jobs(state.clone(), criteria).await.unwrap();
+    let results = find_jobs(&conn, 1, criteria).unwrap().jobs;
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].title, "Développeur React");
 
@@ -90,9 +66,10 @@ async fn test_search_jobs() {
         skills: vec![],
         date_posted: None,
         sort_by: None,
+        ..Default::default()
     };
 
-    let results = search_jobs(state.clone(), criteria).await.unwrap();
+    let results = find_jobs(&conn, 1, criteria).unwrap().jobs;
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].location, "Paris");
 
@@ -108,9 +85,10 @@ async fn test_search_jobs() {
         skills: vec![],
         date_posted: None,
         sort_by: None,
+        ..Default::default()
     };
 
-    let results = search_jobs(state.clone(), criteria).await.unwrap();
+    let results = find_jobs(&conn, 1, criteria).unwrap().jobs;
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].job_type, "CDI");
 
@@ -126,9 +104,10 @@ async fn test_search_jobs() {
         skills: vec![],
         date_posted: None,
         sort_by: None,
+        ..Default::default()
     };
 
-    let results = search_jobs(state.clone(), criteria).await.unwrap();
+    let results = find_jobs(&conn, 1, criteria).unwrap().jobs;
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].salary_min, Some(40000));
     assert_eq!(results[0].salary_max, Some(60000));
@@ -145,39 +124,21 @@ async fn test_search_jobs() {
         skills: vec!["React".to_string()],
         date_posted: None,
         sort_by: None,
+        ..Default::default()
     };
 
-    let results = search_jobs(state.clone(), criteria).await.unwrap();
+    let results = find_jobs(&conn, 1, criteria).unwrap().jobs;
     assert_eq!(results.len(), 1);
     assert!(results[0].skills.contains(&"React".to_string()));
 }
 
-#[tokio::test]
-async fn test_get_job_details() {
+#[test]
+fn test_get_job_details() {
     // Créer une connexion à la base de données en mémoire
     let conn = Connection::open_in_memory().unwrap();
     
-    // Créer la table jobs
-    conn.execute(
-        "CREATE TABLE jobs (
-            id INTEGER PRIMARY KEY,
-            title TEXT NOT NULL,
-            company TEXT NOT NULL,
-            location TEXT NOT NULL,
-            job_type TEXT NOT NULL,
-            salary_min INTEGER,
-            salary_max INTEGER,
-            description TEXT NOT NULL,
-            url TEXT NOT NULL,
-            posted_at TEXT NOT NULL,
-            experience_level TEXT NOT NULL,
-            skills TEXT NOT NULL,
-            remote BOOLEAN NOT NULL,
-            source TEXT NOT NULL
-        )",
-        [],
-    )
-    .unwrap();
+    // Créer le schéma complet (dont l'index plein texte)
+    run_migrations(&conn).unwrap();
 
     // Insérer des données de test
     conn.execute(
@@ -203,13 +164,8 @@ async fn test_get_job_details() {
     )
     .unwrap();
 
-    // Créer l'état de l'application
-    let state = AppState {
-        db: Arc::new(Mutex::new(Some(conn))),
-    };
-
     // Test 1: Récupérer un job existant
-    let job = crate::commands::search::get_job_details(state.clone(), 1).await.unwrap();
+    let job = find_job(&conn, 1).unwrap();
     assert!(job.is_some());
     let job = job.unwrap();
     assert_eq!(job.title, "Développeur React");
@@ -217,6 +173,113 @@ async fn test_get_job_details() {
     assert_eq!(job.location, "Paris");
 
     // Test 2: Récupérer un job inexistant
-    let job = crate::commands::search::get_job_details(state.clone(), 999).await.unwrap();
+    let job = find_job(&conn, 999).unwrap();
     assert!(job.is_none());
-} 
\ No newline at end of file
+}
+
+fn criteria(keywords: &str, sort_by: Option<&str>) -> SearchCriteria {
+    SearchCriteria {
+        keywords: keywords.to_string(),
+        location: "".to_string(),
+        salary_min: None,
+        salary_max: None,
+        contract_types: vec![],
+        experience_levels: vec![],
+        remote: None,
+        skills: vec![],
+        date_posted: None,
+        sort_by: sort_by.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn insert_job(conn: &Connection, title: &str, company: &str, description: &str, posted_at: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO jobs (title, company, description, posted_at, skills) VALUES (?, ?, ?, ?, '[]')",
+        rusqlite::params![title, company, description, posted_at],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+fn titles(conn: &Connection, keywords: &str, sort_by: Option<&str>) -> Vec<String> {
+    find_jobs(conn, 1, criteria(keywords, sort_by))
+        .unwrap()
+        .jobs
+        .into_iter()
+        .map(|job| job.title)
+        .collect()
+}
+
+#[test]
+fn test_full_text_search_ranks_by_relevance() {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    insert_job(&conn, "Comptable", "Compta SA", "Nous utilisons un peu de Rust en interne", "2024-03-01");
+    insert_job(&conn, "Développeur Rust", "Ferris", "Backend Rust et Tokio", "2024-01-01");
+
+    // Le tri par date mettrait le comptable en premier
+    assert_eq!(titles(&conn, "rust", Some("date")), vec!["Comptable", "Développeur Rust"]);
+    assert_eq!(titles(&conn, "rust", Some("relevance")), vec!["Développeur Rust", "Comptable"]);
+    // La pertinence est le tri par défaut d'une recherche par mots-clés
+    assert_eq!(titles(&conn, "rust", None), vec!["Développeur Rust", "Comptable"]);
+
+    let results = find_jobs(&conn, 1, criteria("rust", None)).unwrap().jobs;
+    assert!(results[0].relevance.unwrap() > results[1].relevance.unwrap());
+    assert_eq!(results[0].title_snippet.as_deref(), Some("Développeur <mark>Rust</mark>"));
+    assert!(results[1].description_snippet.as_deref().unwrap().contains("<mark>Rust</mark>"));
+}
+
+#[test]
+fn test_full_text_search_respects_word_boundaries_and_accents() {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    insert_job(&conn, "Ingénieur Java", "Acme", "", "2024-01-01");
+    insert_job(&conn, "Développeur JavaScript", "Acme", "", "2024-01-02");
+
+    assert_eq!(titles(&conn, "java", None), vec!["Ingénieur Java"]);
+    assert_eq!(titles(&conn, "ingenieur", None), vec!["Ingénieur Java"]);
+}
+
+#[test]
+fn test_full_text_search_phrase_prefix_and_negation() {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    insert_job(&conn, "Chef de projet digital", "Acme", "", "2024-01-01");
+    insert_job(&conn, "Projet : chef cuisinier", "Bistrot", "", "2024-01-02");
+    insert_job(&conn, "Développeuse Java", "Acme", "", "2024-01-03");
+
+    assert_eq!(titles(&conn, "\"chef de projet\"", None), vec!["Chef de projet digital"]);
+    assert_eq!(titles(&conn, "dév*", None), vec!["Développeuse Java"]);
+    assert_eq!(titles(&conn, "chef -cuisinier", None), vec!["Chef de projet digital"]);
+    assert_eq!(
+        titles(&conn, "-java", Some("date")),
+        vec!["Projet : chef cuisinier", "Chef de projet digital"]
+    );
+}
+
+#[test]
+fn test_full_text_index_follows_updates_and_deletes() {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    let id = insert_job(&conn, "Développeur PHP", "Acme", "", "2024-01-01");
+
+    conn.execute("UPDATE jobs SET title = 'Développeur Go' WHERE id = ?", [id]).unwrap();
+    assert!(titles(&conn, "php", None).is_empty());
+    assert_eq!(titles(&conn, "go", None), vec!["Développeur Go"]);
+
+    conn.execute("DELETE FROM jobs WHERE id = ?", [id]).unwrap();
+    assert!(titles(&conn, "go", None).is_empty());
+}
+
+#[test]
+fn test_keywords_are_escaped_for_fts() {
+    let query = parse_keywords("c++ AND \"node.js\" -NEAR rust*");
+    assert_eq!(query.matching.as_deref(), Some("\"c++\" \"AND\" \"node.js\" \"rust\"*"));
+    assert_eq!(query.excluded.as_deref(), Some("\"NEAR\""));
+
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    assert!(find_jobs(&conn, 1, criteria("c++ AND \"node.js OR -", None)).is_ok());
+    assert!(parse_keywords("  - * ").is_empty());
+}
@@ -0,0 +1,161 @@
+use app_lib::commands::search::{find_jobs, SearchCriteria};
+use app_lib::database::migrations::run_migrations;
+use app_lib::skills::{normalize_stored_skills, skill_key, sync_skill_taxonomy, SkillTaxonomy};
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+
+fn migrated_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    run_migrations(&conn).unwrap();
+    sync_skill_taxonomy(&conn, SkillTaxonomy::bundled()).unwrap();
+    conn
+}
+
+fn insert_job(conn: &Connection, title: &str, skills: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO jobs (title, company, location, description, url, source, skills)
+         VALUES (?, 'Acme', 'Paris', 'Description', ?, 'test', ?)",
+        params![title, format!("https://example.com/{}", title), skills],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+fn search_skills(conn: &Connection, skills: &[&str]) -> Vec<String> {
+    let criteria = SearchCriteria {
+        skills: skills.iter().map(|skill| skill.to_string()).collect(),
+        ..Default::default()
+    };
+    let mut titles: Vec<String> = find_jobs(conn, 1, criteria).unwrap().jobs.into_iter().map(|job| job.title).collect();
+    titles.sort();
+    titles
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn test_bundled_taxonomy_is_consistent() {
+    let taxonomy = SkillTaxonomy::bundled();
+    assert!(taxonomy.skills().len() > 50);
+
+    let ids: HashSet<&str> = taxonomy.skills().iter().map(|skill| skill.id.as_str()).collect();
+    assert_eq!(ids.len(), taxonomy.skills().len());
+    for skill in taxonomy.skills() {
+        if let Some(parent) = &skill.parent_id {
+            assert!(ids.contains(parent.as_str()), "parent inconnu pour {}", skill.id);
+        }
+        // Chaque synonyme désigne bien sa propre compétence
+        for alias in &skill.aliases {
+            assert_eq!(taxonomy.resolve(alias).unwrap().id, skill.id, "synonyme {} partagé", alias);
+        }
+    }
+}
+
+#[test]
+fn test_resolve_synonyms() {
+    let taxonomy = SkillTaxonomy::bundled();
+    for label in ["JS", "javascript", "Java Script", "ECMAScript"] {
+        assert_eq!(taxonomy.resolve(label).unwrap().name, "JavaScript", "{}", label);
+    }
+    for label in ["Node.js", "node js", "NodeJS"] {
+        assert_eq!(taxonomy.resolve(label).unwrap().name, "Node.js", "{}", label);
+    }
+    assert_eq!(taxonomy.resolve("java").unwrap().name, "Java");
+    assert!(taxonomy.resolve("Cobol sur mainframe").is_none());
+
+    assert_eq!(skill_key("C++"), "c++");
+    assert_eq!(skill_key("C#"), "c#");
+    assert_ne!(skill_key("C++"), skill_key("C#"));
+}
+
+#[test]
+fn test_canonicalize_dedupes_and_keeps_unknown_skills() {
+    let taxonomy = SkillTaxonomy::bundled();
+    let canonical = taxonomy.canonicalize(&strings(&["js", "ReactJS", "JavaScript", "  Cobol   mainframe ", "cobol mainframe", ""]));
+    assert_eq!(canonical, strings(&["JavaScript", "React", "Cobol mainframe"]));
+}
+
+#[test]
+fn test_custom_taxonomy_from_csv() {
+    let taxonomy = SkillTaxonomy::from_csv(
+        "id;nom;categorie;parent;alias\n\
+         rust;Rust;langage;;rs|rustlang\n\
+         tokio;Tokio;framework;rust;\n\
+         orphan;Orphan;framework;inconnu;rs\n\
+         ligne invalide\n",
+    );
+    assert_eq!(taxonomy.skills().len(), 3);
+    assert_eq!(taxonomy.resolve("RS").unwrap().id, "rust");
+    assert_eq!(taxonomy.resolve("tokio").unwrap().parent_id.as_deref(), Some("rust"));
+    // Parent inconnu ignoré
+    assert_eq!(taxonomy.resolve("orphan").unwrap().parent_id, None);
+}
+
+#[test]
+fn test_sync_skill_taxonomy_is_idempotent() {
+    let conn = migrated_conn();
+    let count = sync_skill_taxonomy(&conn, SkillTaxonomy::bundled()).unwrap();
+
+    let stored: usize = conn.query_row("SELECT COUNT(*) FROM skills", [], |row| row.get(0)).unwrap();
+    assert_eq!(stored, count);
+    let parent: String = conn
+        .query_row("SELECT parent_id FROM skills WHERE id = 'react'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(parent, "javascript");
+    let alias: String = conn
+        .query_row("SELECT skill_id FROM skill_aliases WHERE alias = 'js'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(alias, "javascript");
+
+    // Une compétence retirée du référentiel disparaît de la base
+    let reduced = SkillTaxonomy::from_csv("id;nom;categorie;parent;alias\nrust;Rust;langage;;rs\n");
+    sync_skill_taxonomy(&conn, &reduced).unwrap();
+    let stored: usize = conn.query_row("SELECT COUNT(*) FROM skills", [], |row| row.get(0)).unwrap();
+    assert_eq!(stored, 1);
+}
+
+#[test]
+fn test_normalize_stored_skills() {
+    let conn = migrated_conn();
+    let job_id = insert_job(&conn, "Front", r#"["reactjs", "js", "JavaScript", "Figma avancé"]"#);
+    insert_job(&conn, "Déjà normalisée", r#"["Python"]"#);
+    conn.execute(
+        "INSERT INTO user_profiles (user_id, name, primary_home, skills) VALUES (1, 'Camille', 'Paris', ?)",
+        [r#"["py", "postgres"]"#],
+    )
+    .unwrap();
+
+    assert_eq!(normalize_stored_skills(&conn, SkillTaxonomy::bundled()).unwrap(), 2);
+    let skills: String = conn
+        .query_row("SELECT skills FROM jobs WHERE id = ?", [job_id], |row| row.get(0))
+        .unwrap();
+    assert_eq!(skills, r#"["React","JavaScript","Figma avancé"]"#);
+    let skills: String = conn
+        .query_row("SELECT skills FROM user_profiles WHERE user_id = 1", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(skills, r#"["Python","PostgreSQL"]"#);
+
+    // Rien à refaire au second passage
+    assert_eq!(normalize_stored_skills(&conn, SkillTaxonomy::bundled()).unwrap(), 0);
+}
+
+#[test]
+fn test_skill_filter_follows_synonyms_and_hierarchy() {
+    let conn = migrated_conn();
+    insert_job(&conn, "Front React", r#"["React"]"#);
+    insert_job(&conn, "Back Java", r#"["Java"]"#);
+    insert_job(&conn, "Angular", r#"["Angular"]"#);
+    insert_job(&conn, "Data", r#"["Python", "Cobol mainframe"]"#);
+    insert_job(&conn, "Invalide", "pas du json");
+
+    // Synonyme et sous-compétences (React, Angular via TypeScript), sans Java
+    assert_eq!(search_skills(&conn, &["JS"]), strings(&["Angular", "Front React"]));
+    assert_eq!(search_skills(&conn, &["javascript"]), strings(&["Angular", "Front React"]));
+    assert_eq!(search_skills(&conn, &["reactjs"]), strings(&["Front React"]));
+    assert_eq!(search_skills(&conn, &["java"]), strings(&["Back Java"]));
+    // Compétence inconnue : recherche textuelle
+    assert_eq!(search_skills(&conn, &["Cobol"]), strings(&["Data"]));
+    assert_eq!(search_skills(&conn, &["py", "react"]), strings(&["Data", "Front React"]));
+}
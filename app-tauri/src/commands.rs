@@ -1,4 +1,3 @@
-use crate::models::{JobSearchResult, SearchCriteria};
 use serde_json::Value;
 use std::process::Command;
 use tokio::process::Command as AsyncCommand;
@@ -25,79 +24,6 @@ pub struct Interview {
     pub notes: String,
 }
 
-#[tauri::command]
-pub async fn search_jobs(criteria: SearchCriteria) -> Result<JobSearchResult, String> {
-    let python_script = format!(
-        r#"
-from modules.job_scraper import JobScraper
-import json
-from datetime import datetime
-
-scraper = JobScraper('jobs.db')
-results = scraper.search_jobs(
-    keywords={keywords},
-    location='{location}',
-    job_type={job_type},
-    is_remote={is_remote},
-    skills={skills},
-    date_posted={date_posted},
-    sort_by='{sort_by}',
-    page={page},
-    hours_old=72
-)
-
-print(json.dumps({{
-    'jobs': [{{ 
-        'id': str(job.id),
-        'title': job.title,
-        'company': job.company_name,
-        'location': job.location,
-        'description': job.description,
-        'url': job.url,
-        'date_posted': job.date_posted.isoformat() if job.date_posted else '',
-        'salary': f"{job.salary_min}-{job.salary_max} {job.salary_currency}" if job.salary_min else None,
-        'matching_score': job.matching_score,
-        'skills': job.skills,
-        'job_type': job.job_type,
-        'experience_level': job.experience_level,
-        'remote': job.is_remote
-    }} for job in results['jobs']],
-    'stats': {{
-        'total': results['stats']['total'],
-        'new': results['stats']['new'],
-        'updated': results['stats']['updated']
-    }}
-}}))
-"#,
-        keywords = criteria.keywords,
-        location = criteria.location,
-        job_type = criteria.contract_types.unwrap_or_default(),
-        is_remote = criteria.remote.unwrap_or(false),
-        skills = criteria.skills.unwrap_or_default(),
-        date_posted = criteria.date_posted.map(|d| d.to_string()).unwrap_or_default(),
-        sort_by = criteria.sort_by.unwrap_or("relevance".to_string()),
-        page = criteria.page.unwrap_or(1)
-    );
-
-    let output = AsyncCommand::new("python3")
-        .arg("-c")
-        .arg(python_script)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute Python script: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Python script error: {}", stderr));
-    }
-
-    let stdout = String::from_utf8(output.stdout)
-        .map_err(|e| format!("Failed to read Python output: {}", e))?;
-
-    serde_json::from_str(&stdout)
-        .map_err(|e| format!("Failed to parse search results: {}", e))
-}
-
 #[tauri::command]
 pub async fn get_card(card_id: String) -> Result<Card, String> {
     let python_script = format!(